@@ -0,0 +1,203 @@
+use constants::PKW;
+use container::Container;
+use data_pka::PKAMAP;
+use gibbs::written_charge;
+use ion::Ion;
+use trait_properties::Properties;
+use types::*;
+
+/// The range in which the pH is searched
+const PH_BOUNDS: (f64, f64) = (-3.0, 17.0);
+
+#[derive(Debug, Clone, PartialEq)]
+/// A chain of conjugated acids and bases, e.g. H₃PO₄, H₂PO₄⁻, HPO₄²⁻ and PO₄³⁻
+pub struct AcidBaseSystem {
+    /// The species, from the most to the least protonated one
+    pub species: Vec<Ion>,
+
+    /// The pKa of every step, so one less than the amount of species
+    pub pkas: Vec<PK>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The acid-base equilibrium of a solution
+pub struct AcidBaseEquilibrium {
+    /// The pH of the solution
+    pub ph: f64,
+
+    /// The concentration of every acid-base species, H⁺ and OH⁻ included [ mol / L ]
+    pub concentrations: Vec<(Ion, f64)>,
+}
+
+impl AcidBaseSystem {
+    /// Get the fraction of the total amount every species makes up at a given pH
+    pub fn fractions(&self, ph: f64) -> Vec<f64> {
+        let n = self.pkas.len();
+
+        // log10 of h^(n - i) * K_1 * ... * K_i
+        let mut logs = vec![];
+        let mut pka_sum = 0.0;
+        for i in 0..=n {
+            if i > 0 {
+                pka_sum += f64::from(self.pkas[i - 1].0);
+            }
+
+            logs.push(-((n - i) as f64) * ph - pka_sum);
+        }
+
+        let max = logs.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let weights: Vec<f64> = logs.iter().map(|x| 10f64.powf(x - max)).collect();
+        let total: f64 = weights.iter().sum();
+
+        weights.iter().map(|x| x / total).collect()
+    }
+
+    /// Get the average charge of a species of this system at a given pH
+    fn average_charge(&self, ph: f64) -> f64 {
+        self.fractions(ph)
+            .iter()
+            .zip(self.species.iter())
+            .map(|(fraction, species)| fraction * written_charge(species))
+            .sum()
+    }
+}
+
+/// Get all acid-base systems of the `PKAMAP`, by chaining the acid dissociations
+pub fn get_acid_base_systems() -> Vec<AcidBaseSystem> {
+    let mut steps: Vec<(Ion, Ion, PK)> = PKAMAP
+        .iter()
+        .map(|(reaction, &pka)| {
+            let acid = reaction.lhs.compounds[0].element.clone();
+            let base = reaction.rhs.compounds[0].element.clone();
+
+            (acid, base, pka)
+        })
+        .collect();
+
+    // Make sure the outcome doesn't depend on the order of the map
+    steps.sort_by_key(|x| x.0.symbol());
+
+    let mut systems = vec![];
+
+    // Every chain starts at an acid that is not the base of another acid
+    for (acid, _, _) in steps.iter().filter(|x| !steps.iter().any(|y| y.1 == x.0)) {
+        let mut system = AcidBaseSystem {
+            species: vec![acid.clone()],
+            pkas: vec![],
+        };
+
+        while let Some(step) = steps
+            .iter()
+            .find(|x| &x.0 == system.species.last().unwrap())
+        {
+            system.species.push(step.1.clone());
+            system.pkas.push(step.2);
+        }
+
+        systems.push(system);
+    }
+
+    systems
+}
+
+impl Container<Ion> {
+    /// Calculate the acid-base equilibrium of the contents, using the `PKAMAP`
+    /// and the autoionisation of water. The concentrations follow from the charge balance,
+    /// so the contents are taken to be electrically neutral as a whole.
+    /// Returns None if the container doesn't contain water
+    pub fn acid_base_equilibrium(&self) -> Option<AcidBaseEquilibrium> {
+        let volume = self.solution_volume()?;
+
+        let water = Ion::from_string("H2O").unwrap();
+        let protons = Ion::from_string("H;1").unwrap();
+        let hydroxide = Ion::from_string("OH;-").unwrap();
+
+        // The total concentration of every system that is present
+        let mut systems = vec![];
+        for system in get_acid_base_systems() {
+            let total: f64 = system.species.iter().map(|x| self.moles_of(x).0).sum();
+
+            if total > 0.0 {
+                systems.push((system, total / volume));
+            }
+        }
+
+        // All other charged species don't take part, but do count for the charge balance
+        let mut spectator_charge = 0.0;
+        for compound in &self.contents {
+            let ion = &compound.element;
+
+            if ion == &water
+                || ion == &protons
+                || ion == &hydroxide
+                || systems.iter().any(|x| x.0.species.contains(ion))
+            {
+                continue;
+            }
+
+            spectator_charge += written_charge(ion) * compound.moles.0 / volume;
+        }
+
+        // The charge is positive at low pH and decreases with increasing pH
+        let charge = |ph: f64| {
+            let mut charge = 10f64.powf(-ph) - 10f64.powf(ph - PKW) + spectator_charge;
+
+            for (system, total) in &systems {
+                charge += total * system.average_charge(ph);
+            }
+
+            charge
+        };
+
+        let (mut low, mut high) = PH_BOUNDS;
+        for _ in 0..100 {
+            let middle = (low + high) / 2.0;
+
+            if charge(middle) > 0.0 {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+
+        let ph = (low + high) / 2.0;
+
+        let mut concentrations = vec![
+            (protons, 10f64.powf(-ph)),
+            (hydroxide, 10f64.powf(ph - PKW)),
+        ];
+
+        for (system, total) in &systems {
+            for (species, fraction) in system.species.iter().zip(system.fractions(ph)) {
+                concentrations.push((species.clone(), total * fraction));
+            }
+        }
+
+        Some(AcidBaseEquilibrium { ph, concentrations })
+    }
+
+    /// Calculate the pH of the contents
+    pub fn ph(&self) -> Option<f64> {
+        self.acid_base_equilibrium().map(|x| x.ph)
+    }
+
+    /// Calculate the pOH of the contents
+    pub fn poh(&self) -> Option<f64> {
+        self.acid_base_equilibrium().map(|x| x.poh())
+    }
+}
+
+impl AcidBaseEquilibrium {
+    /// Get the pOH of the solution
+    pub fn poh(&self) -> f64 {
+        PKW - self.ph
+    }
+
+    /// Get the concentration of a species [ mol / L ]
+    pub fn concentration_of(&self, ion: &Ion) -> f64 {
+        self.concentrations
+            .iter()
+            .find(|x| &x.0 == ion)
+            .map_or(0.0, |x| x.1)
+    }
+}
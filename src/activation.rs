@@ -0,0 +1,158 @@
+use constants::GAS_CONSTANT;
+use container::Container;
+use reaction::ElemReaction;
+use trait_element::Element;
+use trait_properties::Properties;
+use trait_reaction::Reaction;
+use types::*;
+
+/// Activation energy, in units of RT, below which a reaction takes place by itself
+/// With a typical pre-exponential factor of 1e13 /s, that takes about a second
+const THERMAL_BARRIER: f64 = 30.0;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The energy barrier a reaction has to overcome before it takes place
+pub struct Activation<E: Element> {
+    /// The activation energy without catalysts or inhibitors [ J / mol ]
+    pub energy: Energy,
+
+    /// Species that lower the activation energy to the given one when present,
+    /// without being used up
+    pub catalysts: Vec<(E, Energy)>,
+
+    /// Species that raise the activation energy by the given amount when present
+    pub inhibitors: Vec<(E, Energy)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A reaction with an activation barrier
+pub struct ActivatedReaction<E: Element> {
+    /// The reaction taking place
+    pub reaction: ElemReaction<E>,
+
+    /// The barrier it has to overcome
+    pub activation: Activation<E>,
+}
+
+impl<E: Element> Activation<E> {
+    /// Create an activation barrier, without catalysts or inhibitors [ J / mol ]
+    pub fn new(energy: Energy) -> Activation<E> {
+        Activation {
+            energy,
+            catalysts: vec![],
+            inhibitors: vec![],
+        }
+    }
+
+    /// Add a catalyst, which lowers the activation energy to the given one [ J / mol ]
+    pub fn with_catalyst(mut self, catalyst: E, energy: Energy) -> Activation<E> {
+        self.catalysts.push((catalyst, energy));
+        self
+    }
+
+    /// Add an inhibitor, which raises the activation energy by the given amount [ J / mol ]
+    pub fn with_inhibitor(mut self, inhibitor: E, energy: Energy) -> Activation<E> {
+        self.inhibitors.push((inhibitor, energy));
+        self
+    }
+
+    /// Get the activation energy in a container [ J / mol ]
+    /// The best catalyst present is used, and all inhibitors present add up
+    pub fn barrier(&self, container: &Container<E>) -> Energy {
+        let is_present = |element: &E| container.moles_of(element) > Moles::from(0.0);
+
+        let mut barrier = self
+            .catalysts
+            .iter()
+            .filter(|x| is_present(&x.0))
+            .map(|x| x.1)
+            .fold(self.energy, |a, b| if b < a { b } else { a });
+
+        for &(ref inhibitor, energy) in &self.inhibitors {
+            if is_present(inhibitor) {
+                barrier += energy;
+            }
+        }
+
+        if barrier < Energy::from(0.0) {
+            Energy::from(0.0)
+        } else {
+            barrier
+        }
+    }
+}
+
+impl<E: Element> ActivatedReaction<E> {
+    /// Give a reaction an activation barrier
+    pub fn new(reaction: ElemReaction<E>, activation: Activation<E>) -> ActivatedReaction<E> {
+        ActivatedReaction {
+            reaction,
+            activation,
+        }
+    }
+}
+
+impl<E: Element> Container<E> {
+    /// Check if a reaction can overcome its activation barrier in this container
+    /// a given amount of times [ mol ]
+    /// That is the case if it is hot enough for the reaction to take place by itself,
+    /// or if the available energy can supply the barrier, like a spark.
+    /// The energy of the barrier is released again as the reaction takes place,
+    /// so it isn't used up
+    pub fn is_activated(&self, activation: &Activation<E>, extent: MolesType) -> bool {
+        let barrier = activation.barrier(self);
+
+        barrier.0 <= THERMAL_BARRIER * GAS_CONSTANT * self.temperature.0
+            || self.available_energy >= barrier * extent
+    }
+}
+
+impl<E: Element> Reaction<E> for ActivatedReaction<E> {
+    fn equalise(&self) -> bool {
+        self.reaction.equalise()
+    }
+
+    fn is_valid(&self) -> bool {
+        self.reaction.is_valid()
+    }
+
+    fn energy_cost(&self) -> Energy {
+        self.reaction.energy_cost()
+    }
+
+    fn elem_reaction(&self) -> ElemReaction<E> {
+        self.reaction.clone()
+    }
+
+    fn activation(&self) -> Option<Activation<E>> {
+        Some(self.activation.clone())
+    }
+}
+
+impl<E: Element> Properties for ActivatedReaction<E> {
+    fn symbol(&self) -> String {
+        format!(
+            "{}    (Ea = {:.3} J/mol)",
+            self.reaction.symbol(),
+            self.activation.energy
+        )
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "{}    (Ea = {:.3} J/mol)",
+            self.reaction.name(),
+            self.activation.energy
+        )
+    }
+
+    fn mass(&self) -> AtomMass {
+        // Law of Conservation of Mass
+        AtomMass::from(0.0)
+    }
+
+    fn is_diatomic(&self) -> bool {
+        // Reactions can't be diatomic
+        false
+    }
+}
@@ -39,6 +39,22 @@ impl Atom {
         None
     }
 
+    /// Check if an atom is a metal, which means it forms cations in ionic compounds
+    pub fn is_metal(&self) -> bool {
+        let group = self.group.0;
+        let number = self.number.0;
+
+        match group {
+            1 => number != 1,
+            2..=12 => true,
+            13 => number > 5,
+            14 => number >= 50,
+            15 => number >= 83,
+            16 => number >= 84,
+            _ => false,
+        }
+    }
+
     /// Get the charge an atom has based on its group
     pub fn charge_by_group(&self) -> Option<AtomCharge> {
         let group = self.group.0;
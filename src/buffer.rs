@@ -0,0 +1,170 @@
+use acid_base::get_acid_base_systems;
+use constants::{STANDARD_TEMPERATURE, WATER_MOLAR_VOLUME};
+use container::*;
+use gas::GasMode;
+use gibbs::written_charge;
+use ion::Ion;
+use precipitation::salt_dissolution;
+use trait_properties::Properties;
+use types::*;
+
+/// How far the pH of a buffer can be from its pKa for it to still be effective
+const BUFFER_RANGE: f64 = 1.0;
+
+#[derive(Debug, Clone, PartialEq)]
+/// The recipe of a buffer solution of a weak acid and its conjugate base
+pub struct BufferRecipe {
+    /// The weak acid
+    pub acid: Ion,
+
+    /// The conjugate base
+    pub base: Ion,
+
+    /// The pKa of the acid
+    pub pka: PK,
+
+    /// The pH of the buffer
+    pub ph: f64,
+
+    /// The volume of the buffer [ L ]
+    pub volume: f64,
+
+    /// The amount of the acid [ mol ]
+    pub acid_moles: Moles,
+
+    /// The amount of the conjugate base [ mol ]
+    pub base_moles: Moles,
+
+    /// The neutral compounds to weigh out, charged species are taken
+    /// as their sodium salt (anions) or chloride (cations)
+    pub compounds: Vec<ContainerCompound<Ion>>,
+}
+
+/// Calculate the pH of a buffer using the Henderson–Hasselbalch equation
+pub fn henderson_hasselbalch(pka: PK, acid: &Moles, base: &Moles) -> f64 {
+    f64::from(pka.0) + (base.0 / acid.0).log10()
+}
+
+/// Get the neutral compound to weigh out for a species, and the counter-ion used
+fn weighable(species: &Ion) -> (Ion, Option<(Ion, f64)>) {
+    let charge = written_charge(species);
+
+    let (counter_ion, dissolution) = if charge < 0.0 {
+        let sodium = Ion::from_string("Na;+").unwrap();
+
+        (sodium.clone(), salt_dissolution(&sodium, species))
+    } else if charge > 0.0 {
+        let chloride = Ion::from_string("Cl;-").unwrap();
+
+        (chloride.clone(), salt_dissolution(species, &chloride))
+    } else {
+        return (species.clone(), None);
+    };
+
+    let salt = dissolution.lhs.compounds[0].element.clone();
+
+    (salt, Some((counter_ion, charge.abs())))
+}
+
+impl BufferRecipe {
+    /// Design a buffer of a weak acid from the `PKAMAP` and its conjugate base,
+    /// with a given pH, buffer capacity [ mol / (L pH) ] and volume [ L ].
+    /// Returns None if the acid is unknown, or if the pH is outside of the range
+    /// in which the acid can buffer (pKa ± 1)
+    pub fn design(acid: &Ion, ph: f64, capacity: f64, volume: f64) -> Option<BufferRecipe> {
+        let (base, pka) = get_acid_base_systems().into_iter().find_map(|system| {
+            let index = system.species.iter().position(|x| x == acid)?;
+
+            Some((system.species.get(index + 1)?.clone(), system.pkas[index]))
+        })?;
+
+        if (ph - f64::from(pka.0)).abs() > BUFFER_RANGE || capacity <= 0.0 || volume <= 0.0 {
+            return None;
+        }
+
+        let h = 10f64.powf(-ph);
+        let ka = 10f64.powf(-f64::from(pka.0));
+
+        // β = ln(10) C Ka [H⁺] / (Ka + [H⁺])²
+        let concentration = capacity * (ka + h).powi(2) / (10f64.ln() * ka * h);
+        let total = concentration * volume;
+
+        let acid_moles = Moles::from(total * h / (ka + h));
+        let base_moles = Moles::from(total * ka / (ka + h));
+
+        let compounds = vec![
+            ContainerCompound {
+                element: weighable(acid).0,
+                moles: acid_moles.clone(),
+            },
+            ContainerCompound {
+                element: weighable(&base).0,
+                moles: base_moles.clone(),
+            },
+        ];
+
+        Some(BufferRecipe {
+            acid: acid.clone(),
+            base,
+            pka,
+            ph,
+            volume,
+            acid_moles,
+            base_moles,
+            compounds,
+        })
+    }
+
+    /// Get the mass of every compound to weigh out [ g ]
+    pub fn grams(&self) -> Vec<(Ion, AtomMass)> {
+        self.compounds
+            .iter()
+            .map(|x| (x.element.clone(), x.mass()))
+            .collect()
+    }
+
+    /// Get the buffer as dissolved in water
+    pub fn container(&self) -> Container<Ion> {
+        let mut container = Container {
+            contents: Contents::new(),
+            available_energy: Energy::from(0.0),
+            temperature: Temperature::from(STANDARD_TEMPERATURE),
+            gas_mode: GasMode::default(),
+        };
+
+        for (species, moles) in &[
+            (&self.acid, &self.acid_moles),
+            (&self.base, &self.base_moles),
+        ] {
+            container.add_elements(&[ContainerCompound {
+                element: (*species).clone(),
+                moles: (*moles).clone(),
+            }]);
+
+            if let Some((counter_ion, amount)) = weighable(species).1 {
+                container.add_elements(&[ContainerCompound {
+                    element: counter_ion,
+                    moles: (*moles).clone() * amount,
+                }]);
+            }
+        }
+
+        container.add_elements(&[ContainerCompound {
+            element: Ion::from_string("H2O").unwrap(),
+            moles: Moles::from(self.volume / WATER_MOLAR_VOLUME),
+        }]);
+
+        container
+    }
+}
+
+impl Container<Ion> {
+    /// Calculate how much the pH changes when adding compounds, like a strong acid or base
+    /// Returns None if the container doesn't contain water
+    pub fn ph_change(&self, added: &[ContainerCompound<Ion>]) -> Option<f64> {
+        let mut container = self.clone();
+        container.add_elements(added);
+
+        Some(container.ph()? - self.ph()?)
+    }
+}
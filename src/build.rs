@@ -25,6 +25,7 @@ struct Atom {
 }
 
 /// Write an example TOML file to the data_atoms.rs file
+#[allow(dead_code)]
 fn write(mut atoms_rs_file: &File) {
     let mut atoms: HashMap<String, Atom> = HashMap::new();
 
@@ -42,7 +43,7 @@ fn write(mut atoms_rs_file: &File) {
     );
 
     // Generate config
-    let config = Config { atoms: atoms };
+    let config = Config { atoms };
 
     // Convert to TOML
     let config_string = toml::to_string(&config).unwrap();
@@ -59,11 +60,10 @@ fn read_and_write(mut atoms_toml_file: &File, mut atoms_rs_file: &File) {
     atoms_toml_file.read_to_string(&mut atoms_toml).ok();
 
     // Convert to config struct
-    let config: Config;
-    match toml::from_str(&atoms_toml) {
-        Ok(x) => config = x,
+    let config: Config = match toml::from_str(&atoms_toml) {
+        Ok(x) => x,
         Err(e) => panic!("{:?}", e),
-    }
+    };
 
     // Write header to file
     atoms_rs_file.write_all(b"use atom::Atom;\n").ok();
@@ -85,8 +85,8 @@ fn read_and_write(mut atoms_toml_file: &File, mut atoms_rs_file: &File) {
         let rust_atom = format!(
             "
 pub const {capsname}: Atom = Atom {{
-    number: AtomNumber{{0:{number}}}, mass: AtomMass{{0:{mass:.5}}}, symbol: \"{symbol}\",
-    name: \"{name}\", group: AtomGroup{{0:{group:?}}}, diatomic: {diatomic} }};
+    number: AtomNumber({number}), mass: AtomMass({mass:.5}), symbol: \"{symbol}\",
+    name: \"{name}\", group: AtomGroup({group}), diatomic: {diatomic} }};
 ",
             capsname = capsname,
             name = name,
@@ -116,11 +116,11 @@ pub const {capsname}: Atom = Atom {{
 }
 
 fn main() {
-    let mut atoms_toml_file = File::open("src/data_atoms.toml").unwrap();
-    let mut atoms_rs_file = File::create("src/data_atoms.rs").unwrap();
+    let atoms_toml_file = File::open("src/data_atoms.toml").unwrap();
+    let atoms_rs_file = File::create("src/data_atoms.rs").unwrap();
 
     // NOTE: For debugging only:
     // write(&mut atoms_rs_file);
 
-    read_and_write(&mut atoms_toml_file, &mut atoms_rs_file);
+    read_and_write(&atoms_toml_file, &atoms_rs_file);
 }
@@ -0,0 +1,283 @@
+use constants::*;
+use data_sep::*;
+use electron::is_electron;
+use ion::Ion;
+use math::lcm;
+use namings::from_unicode_notation;
+use reaction::{ElemReaction, ReactionSide};
+use redox::*;
+use trait_element::Element;
+use trait_properties::Properties;
+use trait_reaction::Reaction;
+use types::*;
+
+/// Electrodes that only conduct electrons, without taking part in the reaction
+const INERT_ELECTRODES: &[&str] = &["Pt", "C"];
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+/// An electrochemical cell, made out of two half-reactions of the `SEPMAP`
+/// Both half-reactions are stored as reductions (electrons on the left-hand side)
+pub struct GalvanicCell {
+    /// The half-reaction at the anode, where the oxidation takes place
+    pub anode: ElemReaction<Ion>,
+
+    /// The half-reaction at the cathode, where the reduction takes place
+    pub cathode: ElemReaction<Ion>,
+}
+
+impl GalvanicCell {
+    /// Create a cell from two half-reactions
+    /// The half-reaction with the highest SEP becomes the cathode
+    pub fn from_half_reactions(
+        first: &ElemReaction<Ion>,
+        second: &ElemReaction<Ion>,
+    ) -> Option<GalvanicCell> {
+        let first = as_reduction(first.clone());
+        let second = as_reduction(second.clone());
+
+        if get_sep(&first)? >= get_sep(&second)? {
+            Some(GalvanicCell {
+                anode: second,
+                cathode: first,
+            })
+        } else {
+            Some(GalvanicCell {
+                anode: first,
+                cathode: second,
+            })
+        }
+    }
+
+    /// Create the cell belonging to a redox reaction:
+    /// the reductor is oxidised at the anode, the oxidator is reduced at the cathode
+    pub fn from_redox(redox: &RedoxReaction) -> Option<GalvanicCell> {
+        let anode = as_reduction(redox.reductor.clone());
+        let cathode = as_reduction(redox.oxidator.clone());
+
+        get_sep(&anode)?;
+        get_sep(&cathode)?;
+
+        Some(GalvanicCell { anode, cathode })
+    }
+
+    /// Convert cell notation (e.g. "Zn|Zn²⁺||Cu²⁺|Cu" or "Zn|Zn;2+||Cu;2+|Cu") into a cell
+    /// The left half-cell is the anode, the right half-cell is the cathode
+    pub fn from_string(notation: &str) -> Option<GalvanicCell> {
+        let mut half_cells = notation.split("||");
+
+        let anode = half_reaction_from_notation(half_cells.next()?)?;
+        let cathode = half_reaction_from_notation(half_cells.next()?)?;
+
+        if half_cells.next().is_some() {
+            return None;
+        }
+
+        Some(GalvanicCell { anode, cathode })
+    }
+
+    /// Get the SEP of the anode, or None if it is unknown
+    pub fn anode_potential(&self) -> Option<SEP> {
+        get_sep(&self.anode)
+    }
+
+    /// Get the SEP of the cathode, or None if it is unknown
+    pub fn cathode_potential(&self) -> Option<SEP> {
+        get_sep(&self.cathode)
+    }
+
+    /// Calculate the electromotive force of this cell: E_cell = E_cathode - E_anode
+    /// Returns None if the SEP of either electrode is unknown
+    pub fn emf(&self) -> Option<SEP> {
+        Some(self.cathode_potential()? - self.anode_potential()?)
+    }
+
+    /// Get the amount of electrons transferred in the total reaction
+    pub fn electrons(&self) -> u16 {
+        lcm(
+            i32::from(electrons_in(&self.anode)),
+            i32::from(electrons_in(&self.cathode)),
+        ) as u16
+    }
+
+    /// Calculate the Gibbs free energy of the total reaction: ΔG = -nFE
+    pub fn gibbs_energy(&self) -> Option<Energy> {
+        let n = f64::from(self.electrons());
+        let emf = f64::from(self.emf()?.0);
+
+        Some(Energy::from(-n * FARADAY * emf))
+    }
+
+    /// Calculate the equilibrium constant of the total reaction: K = e^(nFE / RT)
+    pub fn equilibrium_constant(&self) -> Option<f64> {
+        let n = f64::from(self.electrons());
+        let emf = f64::from(self.emf()?.0);
+
+        Some((n * FARADAY * emf / (GAS_CONSTANT * STANDARD_TEMPERATURE)).exp())
+    }
+
+    /// Check if this cell delivers energy by itself
+    /// A cell of which an SEP is unknown is not taken to be spontaneous
+    pub fn is_spontaneous(&self) -> bool {
+        self.emf().is_some_and(|emf| emf > SEP::from(0.0))
+    }
+
+    /// Get the `RedoxReaction` taking place in this cell
+    pub fn redox_reaction(&self) -> RedoxReaction {
+        RedoxReaction {
+            oxidator: self.cathode.clone(),
+            reductor: self.anode.clone().swap(),
+        }
+    }
+
+    /// Get the total reaction taking place in this cell
+    pub fn elem_reaction(&self) -> ElemReaction<Ion> {
+        self.redox_reaction().elem_reaction()
+    }
+}
+
+/// Get the species of a reaction side that show up in cell notation (everything but
+/// electrons and water)
+fn notation_species(side: &ReactionSide<Ion>) -> Vec<Ion> {
+    side.compounds
+        .iter()
+        .map(|x| x.element.clone())
+        .filter(|x| !is_electron(x) && x.symbol() != "H₂O")
+        .collect()
+}
+
+/// Check if an ion is a plain metal, which can act as its own electrode
+fn is_metal(ion: &Ion) -> bool {
+    ion.molecule.compounds.len() == 1
+        && ion.molecule.compounds[0].amount == 1
+        && ion.get_charge() == Some(AtomCharge::from(0))
+}
+
+/// Find the half-reaction in the `SEPMAP` that matches a half-cell in cell notation
+fn half_reaction_from_notation(half_cell: &str) -> Option<ElemReaction<Ion>> {
+    let mut species = vec![];
+
+    for token in half_cell.split(['|', ',']) {
+        // Strip phase annotations like (s) or (aq)
+        let token = token.split('(').next().unwrap().trim();
+
+        if token.is_empty() || INERT_ELECTRODES.contains(&token) {
+            continue;
+        }
+
+        species.push(Ion::from_string(&from_unicode_notation(token))?);
+    }
+
+    if species.is_empty() {
+        return None;
+    }
+
+    // The best match leaves out the least species, then has the highest SEP
+    let mut best: Option<(usize, ElemReaction<Ion>, SEP)> = None;
+
+    for (reaction, &sep) in SEPMAP.iter() {
+        let reaction_species: Vec<Ion> = reaction
+            .lhs
+            .compounds
+            .iter()
+            .chain(reaction.rhs.compounds.iter())
+            .map(|x| x.element.clone())
+            .filter(|x| !is_electron(x))
+            .collect();
+
+        // Every species in the notation has to take part in the reaction ...
+        if !species.iter().all(|x| reaction_species.contains(x)) {
+            continue;
+        }
+
+        // ... and the reaction can only leave out the medium
+        if !reaction_species
+            .iter()
+            .all(|x| is_medium(x) || species.contains(x))
+        {
+            continue;
+        }
+
+        let left_out = reaction_species
+            .iter()
+            .filter(|x| !species.contains(x))
+            .count();
+
+        let is_better = match best {
+            Some((best_left_out, _, best_sep)) => {
+                left_out < best_left_out || (left_out == best_left_out && sep > best_sep)
+            }
+            None => true,
+        };
+
+        if is_better {
+            best = Some((left_out, reaction.clone(), sep));
+        }
+    }
+
+    best.map(|(_, reaction, _)| reaction)
+}
+
+/// Convert a half-reaction into cell notation, e.g. "Zn|Zn²⁺" or "Fe³⁺,Fe²⁺|Pt"
+fn half_cell_notation(half_reaction: &ElemReaction<Ion>, is_anode: bool) -> String {
+    let oxidised = notation_species(&half_reaction.lhs);
+    let reduced = notation_species(&half_reaction.rhs);
+
+    let join = |ions: &[Ion]| {
+        ions.iter()
+            .map(|x| x.symbol())
+            .collect::<Vec<String>>()
+            .join(",")
+    };
+
+    if reduced.len() == 1 && is_metal(&reduced[0]) {
+        if is_anode {
+            format!("{}|{}", join(&reduced), join(&oxidised))
+        } else {
+            format!("{}|{}", join(&oxidised), join(&reduced))
+        }
+    } else if is_anode {
+        format!(
+            "{}|{},{}",
+            INERT_ELECTRODES[0],
+            join(&reduced),
+            join(&oxidised)
+        )
+    } else {
+        format!(
+            "{},{}|{}",
+            join(&oxidised),
+            join(&reduced),
+            INERT_ELECTRODES[0]
+        )
+    }
+}
+
+impl Properties for GalvanicCell {
+    fn symbol(&self) -> String {
+        let mut symbol = String::new();
+
+        symbol += &half_cell_notation(&self.anode, true);
+        symbol += "||";
+        symbol += &half_cell_notation(&self.cathode, false);
+
+        symbol
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "anode: {}\ncathode: {}",
+            self.anode.name(),
+            self.cathode.name()
+        )
+    }
+
+    fn mass(&self) -> AtomMass {
+        // Law of Conservation of Mass
+        AtomMass::from(0.0)
+    }
+
+    fn is_diatomic(&self) -> bool {
+        // Cells can't be diatomic
+        false
+    }
+}
@@ -0,0 +1,25 @@
+// Reference: https://physics.nist.gov/cuu/Constants/ (CODATA 2018)
+
+/// Faraday constant [ C / mol ]
+pub const FARADAY: f64 = 96_485.332_12;
+
+/// Molar gas constant [ J / (mol K) ]
+pub const GAS_CONSTANT: f64 = 8.314_462_618;
+
+/// Avogadro constant [ 1 / mol ]
+pub const AVOGADRO: f64 = 6.022_140_76e23;
+
+/// Standard temperature used for all tabulated data [ K ]
+pub const STANDARD_TEMPERATURE: f64 = 298.15;
+
+/// Negative logarithm of the ion product of water (Kw) at standard temperature
+pub const PKW: f64 = 14.0;
+
+/// Volume of a mole of liquid water [ L / mol ]
+pub const WATER_MOLAR_VOLUME: f64 = 0.018_015;
+
+/// Standard pressure used for all tabulated data [ Pa ]
+pub const STANDARD_PRESSURE: f64 = 100_000.0;
+
+/// Amount below which an element counts as used up [ mol ]
+pub const DEPLETED: f64 = 1e-12;
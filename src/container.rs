@@ -1,24 +1,101 @@
+use activation::Activation;
+use constants::{DEPLETED, STANDARD_TEMPERATURE, WATER_MOLAR_VOLUME};
+use data_molecules::WATER;
 use data_sep::*;
+use gas::{reaction_enthalpy, GasMode};
 use ion::Ion;
 use molecule::Molecule;
 use reaction::ReactionSide;
 use reaction::{ElemReaction, ReactionCompound};
-use redox::RedoxReaction;
+use redox::{get_redox_candidates, RedoxReaction};
 use trait_element::Element;
 use trait_properties::Properties;
 use trait_reaction::Reaction;
 use types::*;
 
+use std::collections::HashMap;
+use std::error::Error;
 use std::hash::{Hash, Hasher};
+use std::slice;
 
 #[derive(Debug, Clone)]
 /// A container for elements
 pub struct Container<E: Element> {
-    /// A vector with the contents of this container
-    pub contents: Vec<ContainerCompound<E>>,
+    /// The contents of this container
+    pub contents: Contents<E>,
 
     /// The amount of energy available
     pub available_energy: Energy,
+
+    /// The temperature of the contents
+    pub temperature: Temperature,
+
+    /// What is kept constant of the gas phase
+    pub gas_mode: GasMode,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// What happened when a reaction took place in a container
+pub struct ReactionOutcome<E: Element> {
+    /// The elements that were used up
+    pub consumed: Vec<ContainerCompound<E>>,
+
+    /// The elements that were formed, which is the theoretical yield of the reaction
+    pub produced: Vec<ContainerCompound<E>>,
+
+    /// The change in available energy
+    pub energy_delta: Energy,
+
+    /// The heat released into the contents, changing the temperature (absorbed, if negative)
+    pub heat: Energy,
+
+    /// How many times the reaction took place [ mol ]
+    pub extent: MolesType,
+
+    /// The reactant that got used up, if any
+    pub limiting_reagent: Option<E>,
+
+    /// What is left of the reactants
+    pub leftover: Vec<ContainerCompound<E>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Why a reaction couldn't take place in a container
+pub enum ReactionError<E: Element> {
+    /// The reaction needs more energy than available
+    NotEnoughEnergy {
+        /// The energy the reaction needs
+        required: Energy,
+
+        /// How much energy is missing
+        shortfall: Energy,
+    },
+
+    /// The container doesn't contain enough of some elements
+    NotEnoughElements {
+        /// The elements that are missing, and how many moles of them
+        shortfalls: Vec<ContainerCompound<E>>,
+    },
+
+    /// The container is too cold and has too little energy to overcome the activation barrier
+    NotActivated {
+        /// The activation energy in the container [ J / mol ]
+        barrier: Energy,
+    },
+}
+
+#[derive(Debug, Clone)]
+/// The elements in a container, indexed by element
+/// Elements with an amount at or below epsilon count as used up, and are removed
+pub struct Contents<E: Element> {
+    /// The compounds, in the order they were added
+    compounds: Vec<ContainerCompound<E>>,
+
+    /// The position of every element in `compounds`
+    index: HashMap<E, usize>,
+
+    /// The amount below which an element is used up [ mol ]
+    epsilon: MolesType,
 }
 
 #[derive(Debug, Clone)]
@@ -39,139 +116,196 @@ pub fn rc_to_cc<E: Element>(rc: ReactionCompound<E>) -> ContainerCompound<E> {
     }
 }
 
-pub fn get_redox_reaction(container: &Container<Ion>) -> Option<RedoxReaction> {
-    let mut oxidator: Option<(ElemReaction<Ion>, SEP)> = None;
-    let mut reductor: Option<(ElemReaction<Ion>, SEP)> = None;
-
-    for reaction in container.get_redox_reactions() {
-        // TODO: But what if there exists an oxidator that provides this reductor with its needed molecules?
-        if !container.has_enough_compounds_for_reaction(&reaction.0) {
-            continue;
-        }
-
-        print!("{}           \twith SEP {}  ", reaction.0, reaction.1);
+/// Convert a reaction side into container compounds, multiplied by the given extent
+/// Compounds occurring more than once are merged
+pub fn scaled_compounds<E: Element>(
+    side: &ReactionSide<E>,
+    extent: MolesType,
+) -> Vec<ContainerCompound<E>> {
+    let mut compounds: Vec<ContainerCompound<E>> = vec![];
 
-        // Find electrons
-        if reaction
-            .0
-            .lhs
-            .total_atoms(true)
-            .contains_key(&AtomNumber::from(0))
-        {
-            println!("[oxi]");
+    for rc in &side.compounds {
+        let moles = Moles::from(MolesType::from(rc.amount) * extent);
 
-            if let Some(oxi) = oxidator.clone() {
-                if reaction.1 > oxi.1 {
-                    oxidator = Some(reaction);
-                }
-            } else {
-                oxidator = Some(reaction);
-            }
-        } else {
-            println!("[red]");
-
-            if let Some(red) = reductor.clone() {
-                if reaction.1 < red.1 {
-                    reductor = Some(reaction);
-                }
-            } else {
-                reductor = Some(reaction);
-            }
+        if let Some(compound) = compounds.iter_mut().find(|x| x.element == rc.element) {
+            compound.moles += moles;
+            continue;
         }
-    }
 
-    if let Some(ref oxi) = oxidator {
-        println!("oxidator: {}  \twith SEP {}", oxi.0, oxi.1);
-    } else {
-        println!("failed to find oxidator");
+        compounds.push(ContainerCompound {
+            element: rc.element.clone(),
+            moles,
+        });
     }
 
-    if let Some(ref red) = reductor {
-        println!("reductor: {}  \twith SEP {}", red.0, red.1);
-    } else {
-        println!("failed to find reductor");
-    }
-
-    if oxidator.is_some() && reductor.is_some() {
-        let oxi = oxidator.unwrap();
-        let red = reductor.unwrap();
+    compounds
+}
 
-        Some(RedoxReaction {
-            oxidator: oxi.0,
-            reductor: red.0,
-        })
-    } else {
-        None
-    }
+/// Get the redox reaction with the highest driving force that can take place in a container
+/// See `get_redox_candidates` for all possible ones
+pub fn get_redox_reaction(container: &Container<Ion>) -> Option<RedoxReaction> {
+    get_redox_candidates(container)
+        .into_iter()
+        .find(|x| x.is_feasible())
+        .map(|x| x.redox)
 }
 
 impl<E: Element> Container<E> {
     /// Applies given `Reaction` to `Container`
     /// Removing the elements on the left-hand side
     /// and adding the elements on the right-hand side.
-    /// If there is enough energy for the reaction, that amount will be consumed
+    /// If there is enough energy for the reaction, that amount will be consumed
     /// otherwise the reaction won't occur.
-    /// Returns if the reaction succeeded
-    pub fn react<R: Reaction<E>>(&mut self, reaction: &R) -> bool {
+    /// If the reaction has an activation barrier, the container has to overcome it,
+    /// see `is_activated`.
+    /// The heat released (or absorbed) by the reaction changes the temperature.
+    /// Returns what happened, or why the reaction couldn't occur,
+    /// in which case the container is left untouched
+    pub fn react<R: Reaction<E>>(
+        &mut self,
+        reaction: &R,
+    ) -> Result<ReactionOutcome<E>, ReactionError<E>> {
+        self.react_extent(reaction, 1.0)
+    }
+
+    /// Applies given `Reaction` to `Container` a fractional amount of times [ mol ]
+    /// See `react` for the details
+    pub fn react_extent<R: Reaction<E>>(
+        &mut self,
+        reaction: &R,
+        extent: MolesType,
+    ) -> Result<ReactionOutcome<E>, ReactionError<E>> {
+        let elem_reaction = reaction.elem_reaction();
+
         // Get required items
-        let required_energy = reaction.energy_cost();
-        let mut required_elements = vec![];
-        let mut resulting_elements = vec![];
+        let required_energy = reaction.energy_cost() * extent;
+        let required_elements = scaled_compounds(&elem_reaction.lhs, extent);
+        let resulting_elements = scaled_compounds(&elem_reaction.rhs, extent);
+
+        self.apply(
+            &elem_reaction,
+            reaction.activation(),
+            extent,
+            required_energy,
+            required_elements,
+            resulting_elements,
+        )
+    }
 
-        // Convert lhs compounds into `ContainerCompound`s
-        for rc in &reaction.elem_reaction().lhs.compounds {
-            let cc = rc_to_cc(rc.clone());
+    /// Applies given `Reaction` to `Container` until the limiting reagent is used up,
+    /// or until there is no energy left for it
+    /// Fails if the reaction can't take place at all,
+    /// in which case the container is left untouched
+    pub fn react_to_completion<R: Reaction<E>>(
+        &mut self,
+        reaction: &R,
+    ) -> Result<ReactionOutcome<E>, ReactionError<E>> {
+        let elem_reaction = reaction.elem_reaction();
+        let energy_cost = reaction.energy_cost();
+
+        // Find how far the reaction can go until a reactant runs out
+        let mut extent = scaled_compounds(&elem_reaction.lhs, 1.0)
+            .iter()
+            .map(|x| self.moles_of(&x.element).0 / x.moles.0)
+            .fold(MolesType::INFINITY, MolesType::min);
+
+        // The available energy might not be enough to use it up
+        if energy_cost > Energy::from(0.0) {
+            extent = extent.min(self.available_energy.0 / energy_cost.0);
+        }
 
-            required_elements.push(cc);
+        if extent <= 0.0 || extent.is_infinite() {
+            // Reacting once fails for the same reason as not even a bit of it can take place
+            return self.react(reaction);
         }
 
-        // Convert rhs compounds into `ContainerCompound`s
-        for rc in &reaction.elem_reaction().rhs.compounds {
-            let cc = rc_to_cc(rc.clone());
+        // Rounding errors are within the epsilon of the contents, so it's used up completely
+        self.apply(
+            &elem_reaction,
+            reaction.activation(),
+            extent,
+            energy_cost * extent,
+            scaled_compounds(&elem_reaction.lhs, extent),
+            scaled_compounds(&elem_reaction.rhs, extent),
+        )
+    }
 
-            resulting_elements.push(cc);
+    /// Replace the given required elements by the resulting elements, using the required energy
+    /// The reaction heat changes the temperature of the contents as well
+    pub(crate) fn apply(
+        &mut self,
+        elem_reaction: &ElemReaction<E>,
+        activation: Option<Activation<E>>,
+        extent: MolesType,
+        required_energy: Energy,
+        required_elements: Vec<ContainerCompound<E>>,
+        resulting_elements: Vec<ContainerCompound<E>>,
+    ) -> Result<ReactionOutcome<E>, ReactionError<E>> {
+        // Check if the container can overcome the activation barrier
+        if let Some(activation) = activation {
+            if !self.is_activated(&activation, extent) {
+                return Err(ReactionError::NotActivated {
+                    barrier: activation.barrier(self),
+                });
+            }
         }
 
         // Check if the container has enough energy
         if self.available_energy < required_energy {
-            println!("####    Not enough energy");
-            return false;
+            return Err(ReactionError::NotEnoughEnergy {
+                required: required_energy,
+                shortfall: required_energy - self.available_energy,
+            });
         }
 
-        // Check if the container has the required elements
-        if !self.has_elements(&required_elements) {
-            println!("####    Not enough elements");
-            return false;
-        }
+        // Remove required elements, this fails if the container doesn't have them
+        self.remove_elements(&required_elements)?;
 
         // Subtract needed energy (or add, in case of an exothermic reaction)
         self.available_energy -= required_energy;
 
-        // Remove required elements
-        self.remove_elements(&required_elements);
-
         // Add reaction results
-        self.add_elements(&resulting_elements);
-
-        true
+        self.add_undissolved(&resulting_elements);
+
+        // The heat of the reaction changes the temperature of the products
+        let heat = Energy::from(-reaction_enthalpy(elem_reaction).0 * extent);
+        self.add_heat(heat);
+
+        let limiting_reagent = required_elements
+            .iter()
+            .find(|x| self.moles_of(&x.element) == Moles::from(0.0))
+            .map(|x| x.element.clone());
+
+        let leftover = required_elements
+            .iter()
+            .filter(|x| self.moles_of(&x.element) > Moles::from(0.0))
+            .map(|x| ContainerCompound {
+                element: x.element.clone(),
+                moles: self.moles_of(&x.element),
+            })
+            .collect();
+
+        Ok(ReactionOutcome {
+            consumed: required_elements,
+            produced: resulting_elements,
+            energy_delta: Energy::from(0.0) - required_energy,
+            heat,
+            extent,
+            limiting_reagent,
+            leftover,
+        })
     }
 
     /// Check if the container contains a container compound
+    /// Falling short by less than the epsilon of the contents is allowed
     pub fn contains(&self, element: &ContainerCompound<E>) -> bool {
-        // Find element in self.contents
-        if let Some(position) = self.contents.iter().position(|comp| comp == element) {
-            let compound = &self.contents[position];
-
-            // Check if more elements are required than available
-            if element.moles > compound.moles {
-                return false;
-            }
-
-            return true;
-        }
+        self.contents.get(&element.element).is_some()
+            && self.contents.has(&element.element, &element.moles)
+    }
 
-        // Element is not available
-        false
+    /// Get the amount of moles of an element in the container
+    pub fn moles_of(&self, element: &E) -> Moles {
+        self.contents.moles_of(element)
     }
 
     /// Check if the container has all given elements
@@ -205,51 +339,170 @@ impl<E: Element> Container<E> {
     }
 
     /// Remove given elements from container
-    pub fn remove_elements(&mut self, elements: &[ContainerCompound<E>]) {
+    /// Fails if not all of them are available, in which case nothing is removed
+    pub fn remove_elements(
+        &mut self,
+        elements: &[ContainerCompound<E>],
+    ) -> Result<(), ReactionError<E>> {
+        // Find out what is missing first, so nothing changes on failure
+        let shortfalls: Vec<ContainerCompound<E>> = elements
+            .iter()
+            .filter(|x| !self.contains(x))
+            .map(|x| ContainerCompound {
+                element: x.element.clone(),
+                moles: x.moles.clone() - self.moles_of(&x.element),
+            })
+            .collect();
+
+        if !shortfalls.is_empty() {
+            return Err(ReactionError::NotEnoughElements { shortfalls });
+        }
+
+        // If none is available anymore, the element is removed from the container
         for element in elements {
-            let mut to_remove = None;
+            self.contents
+                .remove(&element.element, element.moles.clone());
+        }
 
-            // Find element in self.contents
-            if let Some(position) = self.contents.iter().position(|comp| comp == element) {
-                let compound = &mut self.contents[position];
+        Ok(())
+    }
 
-                // Check if we have enough
-                if compound.moles < element.moles {
-                    panic!("Can't remove element {} (not enough)", element.symbol());
-                }
+    /// Add given elements to container
+    /// Salts that end up in water dissolve right away, see `dissolve`
+    pub fn add_elements(&mut self, elements: &[ContainerCompound<E>]) {
+        self.add_undissolved(elements);
 
-                // Remove amount
-                compound.moles -= element.moles.clone();
+        E::dissolve_in(self, elements);
+    }
 
-                // If none is available anymore, let element be removed from container
-                if compound.moles == Moles::from(0.0) {
-                    to_remove = Some(position);
-                }
-            } else {
-                panic!("Can't remove element {} (not found)", element.symbol());
-            }
+    /// Add given elements to container, without letting salts dissolve
+    pub fn add_undissolved(&mut self, elements: &[ContainerCompound<E>]) {
+        for element in elements {
+            self.contents.add(&element.element, element.moles.clone());
+        }
+    }
 
-            // Remove element if needed
-            if let Some(pos) = to_remove {
-                self.contents.remove(pos);
-            }
+    /// Check if an element is water, the solvent of all solutions
+    pub fn is_solvent(element: &E) -> bool {
+        element.clone().get_molecule().as_ref() == Some(&*WATER)
+    }
+
+    /// Get the volume of the solution, taking it to be the volume of the water [ L ]
+    /// Returns None if the container doesn't contain water
+    pub fn solution_volume(&self) -> Option<f64> {
+        let water: MolesType = self
+            .contents
+            .iter()
+            .filter(|x| Container::is_solvent(&x.element))
+            .map(|x| x.moles.0)
+            .sum();
+
+        if water > 0.0 {
+            Some(water * WATER_MOLAR_VOLUME)
+        } else {
+            None
         }
     }
 
-    /// Add given elements to container
-    pub fn add_elements(&mut self, elements: &[ContainerCompound<E>]) {
-        for element in elements {
-            // Find element in self.contents
-            if let Some(position) = self.contents.iter().position(|comp| comp == element) {
-                let compound = &mut self.contents[position];
-
-                // Add amount
-                compound.moles += element.moles.clone();
-            } else {
-                // If the element is not found in the container, add it
-                self.contents.push(element.clone());
-            }
+    /// Get the concentration of an element in the solution [ mol / L ]
+    /// Returns None if the container doesn't contain water
+    pub fn concentration_of(&self, element: &E) -> Option<f64> {
+        Some(self.moles_of(element).0 / self.solution_volume()?)
+    }
+
+    /// Get the concentrations of all dissolved elements, the water excluded [ mol / L ]
+    /// Returns None if the container doesn't contain water
+    pub fn concentrations(&self) -> Option<Vec<(E, f64)>> {
+        let volume = self.solution_volume()?;
+
+        Some(
+            self.contents
+                .iter()
+                .filter(|x| !Container::is_solvent(&x.element))
+                .map(|x| (x.element.clone(), x.moles.0 / volume))
+                .collect(),
+        )
+    }
+
+    /// Pour the contents and energy of another container into this one
+    /// The temperatures even out, weighted by the heat capacities
+    pub fn merge(&mut self, other: Container<E>) {
+        let heat_capacities = (self.heat_capacity(), other.heat_capacity());
+
+        if heat_capacities.0 + heat_capacities.1 > 0.0 {
+            self.temperature = Temperature::from(
+                (heat_capacities.0 * self.temperature.0 + heat_capacities.1 * other.temperature.0)
+                    / (heat_capacities.0 + heat_capacities.1),
+            );
+        }
+
+        let added: Vec<ContainerCompound<E>> = other.contents.iter().cloned().collect();
+        self.add_undissolved(&added);
+        self.available_energy += other.available_energy;
+
+        // Salts that end up in water dissolve right away
+        E::dissolve_in(self, &added);
+    }
+
+    /// Add water until the solution has the given volume [ L ]
+    /// Returns false if the container doesn't contain water, or already has a larger volume
+    pub fn dilute(&mut self, volume: f64) -> bool {
+        let current = match self.solution_volume() {
+            Some(current) => current,
+            None => return false,
+        };
+
+        if volume < current {
+            return false;
         }
+
+        let water = self
+            .contents
+            .iter()
+            .find(|x| Container::is_solvent(&x.element))
+            .unwrap()
+            .element
+            .clone();
+
+        self.add_elements(&[ContainerCompound {
+            element: water,
+            moles: Moles::from((volume - current) / WATER_MOLAR_VOLUME),
+        }]);
+
+        true
+    }
+
+    /// Take out a given volume of the solution [ L ], which contains a proportional part
+    /// of all elements at the same temperature. The energy stays in this container.
+    /// Returns None if the container doesn't contain water, or not enough of it
+    pub fn aliquot(&mut self, volume: f64) -> Option<Container<E>> {
+        let fraction = volume / self.solution_volume()?;
+
+        if fraction > 1.0 {
+            return None;
+        }
+
+        let portion: Vec<ContainerCompound<E>> = self
+            .contents
+            .iter()
+            .map(|x| ContainerCompound {
+                element: x.element.clone(),
+                moles: x.moles.clone() * fraction,
+            })
+            .collect();
+
+        // Only a part of every element is taken, so they are all available
+        self.remove_elements(&portion).unwrap();
+
+        let mut contents = Contents::from(portion);
+        contents.set_epsilon(self.contents.epsilon());
+
+        Some(Container {
+            contents,
+            available_energy: Energy::from(0.0),
+            temperature: self.temperature,
+            gas_mode: GasMode::default(),
+        })
     }
 
     /// Get all possible redox reactions and their SEP's
@@ -302,7 +555,7 @@ impl<E: Element> Container<E> {
                     _contents.push(rc_to_cc(rc));
                 }
 
-                contents = Some(_contents);
+                contents = Some(Contents::from(_contents));
 
                 token = String::new();
             } else if c == ']' {
@@ -321,11 +574,12 @@ impl<E: Element> Container<E> {
             token.push(c);
         }
 
-        if contents.is_some() && energy.is_some() {
+        if let (Some(contents), Some(available_energy)) = (contents, energy) {
             Some(Container {
-                contents: contents.unwrap(),
-
-                available_energy: energy.unwrap(),
+                contents,
+                available_energy,
+                temperature: Temperature::from(STANDARD_TEMPERATURE),
+                gas_mode: GasMode::default(),
             })
         } else {
             None
@@ -346,7 +600,7 @@ impl<E: Element> Container<E> {
                     _contents.push(rc_to_cc(rc));
                 }
 
-                contents = Some(_contents);
+                contents = Some(Contents::from(_contents));
 
                 token = String::new();
             } else if c == ']' {
@@ -365,11 +619,12 @@ impl<E: Element> Container<E> {
             token.push(c);
         }
 
-        if contents.is_some() && energy.is_some() {
+        if let (Some(contents), Some(available_energy)) = (contents, energy) {
             Some(Container {
-                contents: contents.unwrap(),
-
-                available_energy: energy.unwrap(),
+                contents,
+                available_energy,
+                temperature: Temperature::from(STANDARD_TEMPERATURE),
+                gas_mode: GasMode::default(),
             })
         } else {
             None
@@ -377,6 +632,128 @@ impl<E: Element> Container<E> {
     }
 }
 
+impl<E: Element> Contents<E> {
+    /// Create empty contents
+    pub fn new() -> Contents<E> {
+        Contents {
+            compounds: vec![],
+            index: HashMap::new(),
+            epsilon: DEPLETED,
+        }
+    }
+
+    /// Get the amount below which an element is used up [ mol ]
+    pub fn epsilon(&self) -> MolesType {
+        self.epsilon
+    }
+
+    /// Set the amount below which an element is used up [ mol ]
+    /// Elements that are used up by the new epsilon are removed
+    pub fn set_epsilon(&mut self, epsilon: MolesType) {
+        self.epsilon = epsilon;
+
+        let depleted: Vec<E> = self
+            .compounds
+            .iter()
+            .filter(|x| x.moles.0 <= epsilon)
+            .map(|x| x.element.clone())
+            .collect();
+
+        for element in &depleted {
+            self.discard(element);
+        }
+    }
+
+    /// Get the compound of an element
+    pub fn get(&self, element: &E) -> Option<&ContainerCompound<E>> {
+        self.index
+            .get(element)
+            .map(|&position| &self.compounds[position])
+    }
+
+    /// Get the amount of moles of an element
+    pub fn moles_of(&self, element: &E) -> Moles {
+        self.get(element)
+            .map(|x| x.moles.clone())
+            .unwrap_or_else(|| Moles::from(0.0))
+    }
+
+    /// Check if at least the given amount of an element is available,
+    /// allowing it to fall short by epsilon
+    pub fn has(&self, element: &E, moles: &Moles) -> bool {
+        match self.get(element) {
+            Some(compound) => moles.0 <= compound.moles.0 + self.epsilon,
+            None => moles.0 <= 0.0,
+        }
+    }
+
+    /// Add an amount of an element
+    /// The element is only cloned when it wasn't available yet
+    pub fn add(&mut self, element: &E, moles: Moles) {
+        if let Some(&position) = self.index.get(element) {
+            self.compounds[position].moles += moles;
+            return;
+        }
+
+        if moles.0 > self.epsilon {
+            self.index.insert(element.clone(), self.compounds.len());
+            self.compounds.push(ContainerCompound {
+                element: element.clone(),
+                moles,
+            });
+        }
+    }
+
+    /// Remove an amount of an element, removing the element once it's used up
+    /// Nothing happens if the element isn't available
+    pub fn remove(&mut self, element: &E, moles: Moles) {
+        if let Some(&position) = self.index.get(element) {
+            self.compounds[position].moles -= moles;
+
+            if self.compounds[position].moles.0 <= self.epsilon {
+                self.discard(element);
+            }
+        }
+    }
+
+    /// Remove an element completely, keeping the order of the others
+    fn discard(&mut self, element: &E) {
+        if let Some(position) = self.index.remove(element) {
+            self.compounds.remove(position);
+
+            for compound in &self.compounds[position..] {
+                *self.index.get_mut(&compound.element).unwrap() -= 1;
+            }
+        }
+    }
+
+    /// Iterate over all compounds
+    pub fn iter(&self) -> slice::Iter<'_, ContainerCompound<E>> {
+        self.compounds.iter()
+    }
+
+    /// Get the amount of different elements
+    pub fn len(&self) -> usize {
+        self.compounds.len()
+    }
+
+    /// Check if there are no elements
+    pub fn is_empty(&self) -> bool {
+        self.compounds.is_empty()
+    }
+}
+
+impl<E: Element> ReactionOutcome<E> {
+    /// Get the amount of moles of an element that was formed
+    pub fn theoretical_yield(&self, element: &E) -> Moles {
+        self.produced
+            .iter()
+            .find(|x| &x.element == element)
+            .map(|x| x.moles.clone())
+            .unwrap_or_else(|| Moles::from(0.0))
+    }
+}
+
 impl<E: Element> ContainerCompound<E> {
     pub fn ion_from_string(string: &str) -> Option<ContainerCompound<Ion>> {
         let rc = ReactionCompound::<Ion>::ion_from_string(string)?;
@@ -391,6 +768,43 @@ impl<E: Element> ContainerCompound<E> {
     }
 }
 
+impl<E: Element> Error for ReactionError<E> {}
+
+impl<E: Element> Default for Contents<E> {
+    fn default() -> Contents<E> {
+        Contents::new()
+    }
+}
+
+impl<E: Element> From<Vec<ContainerCompound<E>>> for Contents<E> {
+    /// Compounds occurring more than once are merged
+    fn from(compounds: Vec<ContainerCompound<E>>) -> Contents<E> {
+        let mut contents = Contents::new();
+
+        for compound in compounds {
+            contents.add(&compound.element, compound.moles);
+        }
+
+        contents
+    }
+}
+
+impl<'a, E: Element> IntoIterator for &'a Contents<E> {
+    type Item = &'a ContainerCompound<E>;
+    type IntoIter = slice::Iter<'a, ContainerCompound<E>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.compounds.iter()
+    }
+}
+
+impl<E: Element> PartialEq for Contents<E> {
+    /// Two contents are equal when they have the same elements in the same order
+    fn eq(&self, rhs: &Contents<E>) -> bool {
+        self.compounds == rhs.compounds
+    }
+}
+
 impl<E: Element> Eq for ContainerCompound<E> {}
 
 impl<E: Element> PartialEq for ContainerCompound<E> {
@@ -0,0 +1,122 @@
+use ion::Ion;
+
+use std::collections::HashMap;
+
+// Reference: https://webbook.nist.gov/chemistry/
+// In doubt: Reference: Binas 6th edition, table 57
+
+/// Get the standard molar entropy of a ion [ J / (mol K) ]
+/// The entropy of dissolved ions is relative to that of H⁺
+pub fn get_entropy(ion: &Ion) -> Option<f64> {
+    ENTROPYMAP.get(ion).cloned()
+}
+
+// This is mainly used for debugging purposes, to make sure no invalid ions are added
+macro_rules! str_to_ion {
+    ($s:expr) => {
+        safe_unwrap_ion(Ion::from_string($s), $s)
+    };
+}
+
+macro_rules! add_str_ion {
+    ($map:expr, $s:expr, $entropy:expr) => {
+        $map.insert(str_to_ion!($s), $entropy)
+    };
+}
+
+/// Check if the ion is defined, then unwrap. Otherwise: panic!
+fn safe_unwrap_ion(ion: Option<Ion>, s: &str) -> Ion {
+    if ion.is_none() {
+        panic!("Ion failed to create: {}", s);
+    }
+
+    ion.unwrap()
+}
+
+lazy_static! {
+    pub static ref ENTROPYMAP: HashMap<Ion, f64> = {
+        let mut map = HashMap::new();
+
+        // NOTE: Conditions: T = 298K, p = p0, in the phase of the `PHASEMAP`
+
+        // Gases
+        add_str_ion!(map, "H2", 130.68);
+        add_str_ion!(map, "He", 126.15);
+        add_str_ion!(map, "N2", 191.61);
+        add_str_ion!(map, "O2", 205.15);
+        add_str_ion!(map, "O3", 238.93);
+        add_str_ion!(map, "F2", 202.79);
+        add_str_ion!(map, "Ne", 146.33);
+        add_str_ion!(map, "Cl2", 223.08);
+        add_str_ion!(map, "Ar", 154.85);
+        add_str_ion!(map, "CO", 197.66);
+        add_str_ion!(map, "CO2", 213.79);
+        add_str_ion!(map, "CH4", 186.25);
+        add_str_ion!(map, "NH3", 192.77);
+        add_str_ion!(map, "NO", 210.76);
+        add_str_ion!(map, "NO2", 240.04);
+        add_str_ion!(map, "N2O4", 304.38);
+        add_str_ion!(map, "SO2", 248.22);
+        add_str_ion!(map, "SO3", 256.77);
+        add_str_ion!(map, "H2S", 205.81);
+        add_str_ion!(map, "HCl", 186.90);
+
+        // Liquids
+        add_str_ion!(map, "H2O", 69.95);
+        add_str_ion!(map, "H2O2;0", 109.6);
+        add_str_ion!(map, "Br2", 152.21);
+        add_str_ion!(map, "Hg", 75.90);
+
+        // Solids
+        add_str_ion!(map, "C", 5.74);
+        add_str_ion!(map, "Na", 51.30);
+        add_str_ion!(map, "Mg", 32.67);
+        add_str_ion!(map, "Al", 28.30);
+        add_str_ion!(map, "S", 32.07);
+        add_str_ion!(map, "K", 64.68);
+        add_str_ion!(map, "Ca", 41.59);
+        add_str_ion!(map, "Fe", 27.28);
+        add_str_ion!(map, "Cu", 33.15);
+        add_str_ion!(map, "Zn", 41.63);
+        add_str_ion!(map, "Ag", 42.55);
+        add_str_ion!(map, "I2", 116.14);
+        add_str_ion!(map, "Pb", 64.81);
+        add_str_ion!(map, "NaCl;0", 72.11);
+        add_str_ion!(map, "KCl;0", 82.55);
+        add_str_ion!(map, "AgCl;0", 96.25);
+        add_str_ion!(map, "CaCO3;0", 91.71);
+        add_str_ion!(map, "SiO2", 41.46);
+        add_str_ion!(map, "Fe2O3", 87.40);
+        add_str_ion!(map, "MgO", 26.95);
+        add_str_ion!(map, "CaO", 38.1);
+        add_str_ion!(map, "CuO", 42.6);
+        add_str_ion!(map, "ZnO", 43.65);
+
+        // Dissolved
+        add_str_ion!(map, "H;+", 0.0);
+        add_str_ion!(map, "OH;-", -10.75);
+        add_str_ion!(map, "Na;+", 59.0);
+        add_str_ion!(map, "K;+", 102.5);
+        add_str_ion!(map, "Ag;+", 72.68);
+        add_str_ion!(map, "NH4;+", 113.4);
+        add_str_ion!(map, "Ca;2+", -53.1);
+        add_str_ion!(map, "Mg;2+", -138.1);
+        add_str_ion!(map, "Cu;2+", -99.6);
+        add_str_ion!(map, "Zn;2+", -112.1);
+        add_str_ion!(map, "Fe;2+", -137.7);
+        add_str_ion!(map, "Fe;3+", -315.9);
+        add_str_ion!(map, "Pb;2+", 10.5);
+        add_str_ion!(map, "Al;3+", -321.7);
+        add_str_ion!(map, "Cl;-", 56.5);
+        add_str_ion!(map, "Br;-", 82.4);
+        add_str_ion!(map, "I;-", 111.3);
+        add_str_ion!(map, "F;-", -13.8);
+        add_str_ion!(map, "NO3;-", 146.4);
+        add_str_ion!(map, "SO4;2-", 20.1);
+        add_str_ion!(map, "CO3;2-", -56.9);
+        add_str_ion!(map, "HCO3;-", 91.2);
+        add_str_ion!(map, "CH3COO;-", 86.6);
+
+        map
+    };
+}
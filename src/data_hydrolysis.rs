@@ -0,0 +1,78 @@
+use ion::Ion;
+use reaction::ElemReaction;
+use trait_reaction::Reaction;
+use types::*;
+
+use std::collections::HashMap;
+
+// Reference: https://en.wikipedia.org/wiki/Solubility_table
+// In doubt: Reference: Binas 6th edition, table 46
+// NOTE: Calculated from the solubility products as pK = n * pKw - pKsp
+
+/// Get the pK (-log K) of the hydrolysis of a metal ion into its hydroxide
+pub fn get_hydrolysis_pk(elem_reaction: &ElemReaction<Ion>) -> Option<PK> {
+    if let Some(&pk) = HYDROLYSISMAP.get(elem_reaction) {
+        Some(pk)
+    } else {
+        HYDROLYSISMAP
+            .get(&elem_reaction.clone().swap())
+            .map(|&pk| PK::from(-pk.0))
+    }
+}
+
+// This is mainly used for debugging purposes, to make sure no invalid reaction are added
+macro_rules! str_to_reaction {
+    ($s:expr) => {
+        valid_or_panic(safe_unwrap_reaction(
+            ElemReaction::<Ion>::ion_from_string($s),
+            $s,
+        ))
+    };
+}
+
+macro_rules! add_str_reaction {
+    ($map:expr, $r:expr, $pk:expr) => {
+        $map.insert(str_to_reaction!($r), PK::from($pk))
+    };
+}
+
+/// Make sure the reaction is valid, panic otherwise
+fn valid_or_panic(reaction: ElemReaction<Ion>) -> ElemReaction<Ion> {
+    if !reaction.is_valid() {
+        panic!("Invalid reaction: {}", reaction);
+    }
+
+    reaction
+}
+
+/// Check if the reaction is defined, then unwrap. Otherwise: panic!
+fn safe_unwrap_reaction(reaction: Option<ElemReaction<Ion>>, s: &str) -> ElemReaction<Ion> {
+    if reaction.is_none() {
+        panic!("Reaction failed to create: {}", s);
+    }
+
+    reaction.unwrap()
+}
+
+lazy_static! {
+    pub static ref HYDROLYSISMAP: HashMap<ElemReaction<Ion>, PK> = {
+        let mut map = HashMap::new();
+
+        // NOTE: Conditions: T = 298K
+        // NOTE: Hydroxides are written with parentheses, like in the `SEFMAP`
+
+        add_str_reaction!(map, "Mg;2 + 2H2O <> Mg(OH)2;0 + 2H;1", 16.75);
+        add_str_reaction!(map, "Ca;2 + 2H2O <> Ca(OH)2;0 + 2H;1", 22.70);
+        add_str_reaction!(map, "Al;3 + 3H2O <> Al(OH)3;0 + 3H;1", 8.50);
+        add_str_reaction!(map, "Cr;3 + 3H2O <> Cr(OH)3;0 + 3H;1", 11.80);
+        add_str_reaction!(map, "Mn;2 + 2H2O <> Mn(OH)2;0 + 2H;1", 15.30);
+        add_str_reaction!(map, "Fe;2 + 2H2O <> Fe(OH)2;0 + 2H;1", 11.69);
+        add_str_reaction!(map, "Fe;3 + 3H2O <> Fe(OH)3;0 + 3H;1", 3.45);
+        add_str_reaction!(map, "Co;2 + 2H2O <> Co(OH)2;0 + 2H;1", 13.77);
+        add_str_reaction!(map, "Cu;2 + 2H2O <> Cu(OH)2;0 + 2H;1", 8.68);
+        add_str_reaction!(map, "Zn;2 + 2H2O <> Zn(OH)2;0 + 2H;1", 11.50);
+        add_str_reaction!(map, "Sn;2 + 2H2O <> Sn(OH)2;0 + 2H;1", 1.74);
+
+        map
+    };
+}
@@ -1,7 +1,67 @@
 use ion::Ion;
+use molecule::Molecule;
 
 lazy_static! {
     pub static ref HYDROXIDE: Ion = Ion::from_string("OH;-").unwrap();
     pub static ref AMMONIUM: Ion = Ion::from_string("NH4;1+").unwrap();
     pub static ref SULPHATE: Ion = Ion::from_string("SO4;2-").unwrap();
+    pub static ref NITRATE: Ion = Ion::from_string("NO3;-").unwrap();
+    pub static ref PHOSPHATE: Ion = Ion::from_string("PO4;3-").unwrap();
+    pub static ref CARBONATE: Ion = Ion::from_string("CO3;2-").unwrap();
+    pub static ref ACETATE: Ion = Ion::from_string("CH3COO;-").unwrap();
+    pub static ref PERMANGANATE: Ion = Ion::from_string("MnO4;-").unwrap();
+    pub static ref DICHROMATE: Ion = Ion::from_string("Cr2O7;2-").unwrap();
+
+    // Reference: Binas 6th edition, table 66A
+    /// Polyatomic ions and their names, as recognised inside molecules
+    pub static ref POLYATOMIC_IONS: Vec<(Ion, &'static str)> = {
+        let mut ions = vec![
+            (HYDROXIDE.clone(), "hydroxide"),
+            (AMMONIUM.clone(), "ammonium"),
+            (SULPHATE.clone(), "sulfate"),
+            (NITRATE.clone(), "nitrate"),
+            (PHOSPHATE.clone(), "phosphate"),
+            (CARBONATE.clone(), "carbonate"),
+            (ACETATE.clone(), "acetate"),
+            (PERMANGANATE.clone(), "permanganate"),
+            (DICHROMATE.clone(), "dichromate"),
+        ];
+
+        for &(symbol, name) in &[
+            ("H3O;+", "oxonium"),
+            ("Hg2;2+", "mercury(I)"),
+            ("HSO4;-", "hydrogen sulfate"),
+            ("SO3;2-", "sulfite"),
+            ("S2O3;2-", "thiosulfate"),
+            ("NO2;-", "nitrite"),
+            ("HPO4;2-", "hydrogen phosphate"),
+            ("H2PO4;-", "dihydrogen phosphate"),
+            ("HCO3;-", "hydrogen carbonate"),
+            ("C2O4;2-", "oxalate"),
+            ("CrO4;2-", "chromate"),
+            ("ClO4;-", "perchlorate"),
+            ("ClO3;-", "chlorate"),
+            ("ClO;-", "hypochlorite"),
+            ("CN;-", "cyanide"),
+            ("O2;2-", "peroxide"),
+        ] {
+            ions.push((Ion::from_string(symbol).unwrap(), name));
+        }
+
+        ions
+    };
+
+    /// Polyatomic ions that are neutral molecules as well, like nitrogen dioxide,
+    /// so they only get their charge if it is written
+    static ref NEUTRAL_LOOKALIKES: Vec<Molecule> = vec![Molecule::from_string("NO2").unwrap()];
+}
+
+/// Find the polyatomic ion a molecule is, together with its name
+pub fn get_polyatomic_ion(molecule: &Molecule) -> Option<&'static (Ion, &'static str)> {
+    POLYATOMIC_IONS.iter().find(|x| &x.0.molecule == molecule)
+}
+
+/// Check if a molecule without a written charge should be taken to be a polyatomic ion
+pub fn is_implied_polyatomic_ion(molecule: &Molecule) -> bool {
+    get_polyatomic_ion(molecule).is_some() && !NEUTRAL_LOOKALIKES.contains(molecule)
 }
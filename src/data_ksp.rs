@@ -0,0 +1,103 @@
+use ion::Ion;
+use reaction::ElemReaction;
+use trait_reaction::Reaction;
+use types::*;
+
+use std::collections::HashMap;
+
+// Reference: https://en.wikipedia.org/wiki/Solubility_table
+// In doubt: Reference: Binas 6th edition, table 46
+// NOTE: The hydroxides match the HYDROLYSISMAP: pKsp = n * pKw - pK
+
+/// Get the pKsp (-log Ksp) of the dissolution of a salt
+pub fn get_ksp(elem_reaction: &ElemReaction<Ion>) -> Option<PK> {
+    if let Some(&pk) = KSPMAP.get(elem_reaction) {
+        Some(pk)
+    } else {
+        KSPMAP
+            .get(&elem_reaction.clone().swap())
+            .map(|&pk| PK::from(-pk.0))
+    }
+}
+
+// This is mainly used for debugging purposes, to make sure no invalid reaction are added
+macro_rules! str_to_reaction {
+    ($s:expr) => {
+        valid_or_panic(safe_unwrap_reaction(
+            ElemReaction::<Ion>::ion_from_string($s),
+            $s,
+        ))
+    };
+}
+
+macro_rules! add_str_reaction {
+    ($map:expr, $r:expr, $pk:expr) => {
+        $map.insert(str_to_reaction!($r), PK::from($pk))
+    };
+}
+
+/// Make sure the reaction is valid, panic otherwise
+fn valid_or_panic(reaction: ElemReaction<Ion>) -> ElemReaction<Ion> {
+    if !reaction.is_valid() {
+        panic!("Invalid reaction: {}", reaction);
+    }
+
+    reaction
+}
+
+/// Check if the reaction is defined, then unwrap. Otherwise: panic!
+fn safe_unwrap_reaction(reaction: Option<ElemReaction<Ion>>, s: &str) -> ElemReaction<Ion> {
+    if reaction.is_none() {
+        panic!("Reaction failed to create: {}", s);
+    }
+
+    reaction.unwrap()
+}
+
+lazy_static! {
+    pub static ref KSPMAP: HashMap<ElemReaction<Ion>, PK> = {
+        let mut map = HashMap::new();
+
+        // NOTE: Conditions: T = 298K
+        // NOTE: Hydroxides are written with parentheses, like in the `SEFMAP`
+
+        add_str_reaction!(map, "AgCl;0 <> Ag;+ + Cl;-", 9.75);
+        add_str_reaction!(map, "AgBr;0 <> Ag;+ + Br;-", 12.30);
+        add_str_reaction!(map, "AgI;0 <> Ag;+ + I;-", 16.08);
+        add_str_reaction!(map, "Ag2SO4;0 <> 2Ag;+ + SO4;2-", 4.92);
+        add_str_reaction!(map, "Ag2CrO4;0 <> 2Ag;+ + CrO4;2-", 11.95);
+        add_str_reaction!(map, "Ag2CO3;0 <> 2Ag;+ + CO3;2-", 11.08);
+        add_str_reaction!(map, "Ag2S;0 <> 2Ag;+ + S;2-", 49.20);
+
+        add_str_reaction!(map, "PbCl2;0 <> Pb;2+ + 2Cl;-", 4.79);
+        add_str_reaction!(map, "PbI2;0 <> Pb;2+ + 2I;-", 8.15);
+        add_str_reaction!(map, "PbSO4;0 <> Pb;2+ + SO4;2-", 7.60);
+        add_str_reaction!(map, "PbS;0 <> Pb;2+ + S;2-", 27.50);
+
+        add_str_reaction!(map, "BaSO4;0 <> Ba;2+ + SO4;2-", 9.97);
+        add_str_reaction!(map, "BaCO3;0 <> Ba;2+ + CO3;2-", 8.59);
+        add_str_reaction!(map, "CaSO4;0 <> Ca;2+ + SO4;2-", 4.31);
+        add_str_reaction!(map, "CaCO3;0 <> Ca;2+ + CO3;2-", 8.48);
+        add_str_reaction!(map, "CaF2;0 <> Ca;2+ + 2F;-", 10.46);
+        add_str_reaction!(map, "Ca3(PO4)2;0 <> 3Ca;2+ + 2PO4;3-", 32.68);
+        add_str_reaction!(map, "MgCO3;0 <> Mg;2+ + CO3;2-", 5.17);
+
+        add_str_reaction!(map, "Mg(OH)2;0 <> Mg;2+ + 2OH;-", 11.25);
+        add_str_reaction!(map, "Ca(OH)2;0 <> Ca;2+ + 2OH;-", 5.30);
+        add_str_reaction!(map, "Al(OH)3;0 <> Al;3+ + 3OH;-", 33.50);
+        add_str_reaction!(map, "Cr(OH)3;0 <> Cr;3+ + 3OH;-", 30.20);
+        add_str_reaction!(map, "Mn(OH)2;0 <> Mn;2+ + 2OH;-", 12.70);
+        add_str_reaction!(map, "Fe(OH)2;0 <> Fe;2+ + 2OH;-", 16.31);
+        add_str_reaction!(map, "Fe(OH)3;0 <> Fe;3+ + 3OH;-", 38.55);
+        add_str_reaction!(map, "Co(OH)2;0 <> Co;2+ + 2OH;-", 14.23);
+        add_str_reaction!(map, "Cu(OH)2;0 <> Cu;2+ + 2OH;-", 19.32);
+        add_str_reaction!(map, "Zn(OH)2;0 <> Zn;2+ + 2OH;-", 16.50);
+        add_str_reaction!(map, "Sn(OH)2;0 <> Sn;2+ + 2OH;-", 26.26);
+
+        add_str_reaction!(map, "FeS;0 <> Fe;2+ + S;2-", 17.20);
+        add_str_reaction!(map, "CuS;0 <> Cu;2+ + S;2-", 35.20);
+        add_str_reaction!(map, "ZnS;0 <> Zn;2+ + S;2-", 23.80);
+
+        map
+    };
+}
@@ -0,0 +1,126 @@
+use gas::Phase;
+use ion::Ion;
+
+use std::collections::HashMap;
+
+// Reference: https://webbook.nist.gov/chemistry/
+// In doubt: Reference: Binas 6th edition, table 51
+
+/// Get the phase of a ion at standard conditions
+pub fn get_phase(ion: &Ion) -> Option<Phase> {
+    PHASEMAP.get(ion).map(|&(phase, _)| phase)
+}
+
+/// Get the molar heat capacity at constant pressure of a ion [ J / (mol K) ]
+pub fn get_heat_capacity(ion: &Ion) -> Option<f64> {
+    PHASEMAP.get(ion).map(|&(_, heat_capacity)| heat_capacity)
+}
+
+/// Get the boiling point of a ion at standard pressure [ K ]
+pub fn get_boiling_point(ion: &Ion) -> Option<f64> {
+    BOILINGPOINTMAP.get(ion).cloned()
+}
+
+// This is mainly used for debugging purposes, to make sure no invalid ions are added
+macro_rules! str_to_ion {
+    ($s:expr) => {
+        safe_unwrap_ion(Ion::from_string($s), $s)
+    };
+}
+
+macro_rules! add_str_ion {
+    ($map:expr, $s:expr, $phase:ident, $cp:expr) => {
+        $map.insert(str_to_ion!($s), (Phase::$phase, $cp))
+    };
+
+    ($map:expr, $s:expr, $boiling_point:expr) => {
+        $map.insert(str_to_ion!($s), $boiling_point)
+    };
+}
+
+/// Check if the ion is defined, then unwrap. Otherwise: panic!
+fn safe_unwrap_ion(ion: Option<Ion>, s: &str) -> Ion {
+    if ion.is_none() {
+        panic!("Ion failed to create: {}", s);
+    }
+
+    ion.unwrap()
+}
+
+lazy_static! {
+    pub static ref PHASEMAP: HashMap<Ion, (Phase, f64)> = {
+        let mut map = HashMap::new();
+
+        // NOTE: Conditions: T = 298K, p = p0
+
+        // Gases
+        add_str_ion!(map, "H2", Gas, 28.836);
+        add_str_ion!(map, "He", Gas, 20.786);
+        add_str_ion!(map, "N2", Gas, 29.124);
+        add_str_ion!(map, "O2", Gas, 29.378);
+        add_str_ion!(map, "O3", Gas, 39.20);
+        add_str_ion!(map, "F2", Gas, 31.304);
+        add_str_ion!(map, "Ne", Gas, 20.786);
+        add_str_ion!(map, "Cl2", Gas, 33.907);
+        add_str_ion!(map, "Ar", Gas, 20.786);
+        add_str_ion!(map, "CO", Gas, 29.142);
+        add_str_ion!(map, "CO2", Gas, 37.135);
+        add_str_ion!(map, "CH4", Gas, 35.695);
+        add_str_ion!(map, "NH3", Gas, 35.652);
+        add_str_ion!(map, "NO", Gas, 29.845);
+        add_str_ion!(map, "NO2", Gas, 37.178);
+        add_str_ion!(map, "SO2", Gas, 39.842);
+        add_str_ion!(map, "H2S", Gas, 34.192);
+        add_str_ion!(map, "HCl", Gas, 29.136);
+
+        // Liquids
+        add_str_ion!(map, "H2O", Liquid, 75.375);
+        add_str_ion!(map, "Br2", Liquid, 75.69);
+        add_str_ion!(map, "Hg", Liquid, 27.983);
+
+        // Solids
+        add_str_ion!(map, "C", Solid, 8.517);
+        add_str_ion!(map, "Na", Solid, 28.23);
+        add_str_ion!(map, "Mg", Solid, 24.869);
+        add_str_ion!(map, "Al", Solid, 24.20);
+        add_str_ion!(map, "S", Solid, 22.64);
+        add_str_ion!(map, "K", Solid, 29.58);
+        add_str_ion!(map, "Fe", Solid, 25.10);
+        add_str_ion!(map, "Cu", Solid, 24.44);
+        add_str_ion!(map, "Zn", Solid, 25.39);
+        add_str_ion!(map, "Ag", Solid, 25.35);
+        add_str_ion!(map, "I2", Solid, 54.44);
+        add_str_ion!(map, "Pb", Solid, 26.44);
+        add_str_ion!(map, "NaCl;0", Solid, 50.50);
+        add_str_ion!(map, "KCl;0", Solid, 51.30);
+        add_str_ion!(map, "CaCO3;0", Solid, 83.47);
+        add_str_ion!(map, "SiO2", Solid, 44.43);
+        add_str_ion!(map, "Fe2O3", Solid, 103.9);
+
+        map
+    };
+}
+
+lazy_static! {
+    pub static ref BOILINGPOINTMAP: HashMap<Ion, f64> = {
+        let mut map = HashMap::new();
+
+        // NOTE: Conditions: p = p0
+        // NOTE: Solids are taken to boil right away, without melting first
+
+        // Liquids
+        add_str_ion!(map, "H2O", 373.15);
+        add_str_ion!(map, "Br2", 332.0);
+        add_str_ion!(map, "Hg", 629.88);
+
+        // Solids
+        add_str_ion!(map, "Na", 1156.0);
+        add_str_ion!(map, "Mg", 1363.0);
+        add_str_ion!(map, "S", 717.8);
+        add_str_ion!(map, "K", 1032.0);
+        add_str_ion!(map, "Zn", 1180.0);
+        add_str_ion!(map, "I2", 457.4);
+
+        map
+    };
+}
@@ -0,0 +1,92 @@
+use ion::Ion;
+use reaction::ElemReaction;
+use trait_reaction::Reaction;
+use types::*;
+
+use std::collections::HashMap;
+
+// Reference: https://en.wikipedia.org/wiki/Acid_dissociation_constant
+// In doubt: Reference: Binas 6th edition, table 49
+
+/// Get the pKa of an acid dissociation
+pub fn get_pka(elem_reaction: &ElemReaction<Ion>) -> Option<PK> {
+    if let Some(&pk) = PKAMAP.get(elem_reaction) {
+        Some(pk)
+    } else {
+        PKAMAP
+            .get(&elem_reaction.clone().swap())
+            .map(|&pk| PK::from(-pk.0))
+    }
+}
+
+// This is mainly used for debugging purposes, to make sure no invalid reaction are added
+macro_rules! str_to_reaction {
+    ($s:expr) => {
+        valid_or_panic(safe_unwrap_reaction(
+            ElemReaction::<Ion>::ion_from_string($s),
+            $s,
+        ))
+    };
+}
+
+macro_rules! add_str_reaction {
+    ($map:expr, $r:expr, $pk:expr) => {
+        $map.insert(str_to_reaction!($r), PK::from($pk))
+    };
+}
+
+/// Make sure the reaction is valid, panic otherwise
+fn valid_or_panic(reaction: ElemReaction<Ion>) -> ElemReaction<Ion> {
+    if !reaction.is_valid() {
+        panic!("Invalid reaction: {}", reaction);
+    }
+
+    reaction
+}
+
+/// Check if the reaction is defined, then unwrap. Otherwise: panic!
+fn safe_unwrap_reaction(reaction: Option<ElemReaction<Ion>>, s: &str) -> ElemReaction<Ion> {
+    if reaction.is_none() {
+        panic!("Reaction failed to create: {}", s);
+    }
+
+    reaction.unwrap()
+}
+
+lazy_static! {
+    pub static ref PKAMAP: HashMap<ElemReaction<Ion>, PK> = {
+        let mut map = HashMap::new();
+
+        // NOTE: Conditions: T = 298K
+        // NOTE: Every step of a polyprotic acid is a separate entry
+        // NOTE: Organic acids are written as e.g. CH3COOH, so they need to be entered that way
+
+        // Strong acids
+        add_str_reaction!(map, "HClO4 <> ClO4;- + H;1", -10.0);
+        add_str_reaction!(map, "HI <> I;- + H;1", -9.3);
+        add_str_reaction!(map, "HBr <> Br;- + H;1", -8.7);
+        add_str_reaction!(map, "HCl <> Cl;- + H;1", -6.3);
+        add_str_reaction!(map, "H2SO4 <> HSO4;- + H;1", -3.0);
+        add_str_reaction!(map, "HNO3 <> NO3;- + H;1", -1.4);
+
+        // Weak acids
+        add_str_reaction!(map, "HSO4;- <> SO4;2- + H;1", 1.99);
+        add_str_reaction!(map, "H2C2O4 <> HC2O4;- + H;1", 1.25);
+        add_str_reaction!(map, "HC2O4;- <> C2O4;2- + H;1", 4.27);
+        add_str_reaction!(map, "H3PO4 <> H2PO4;- + H;1", 2.15);
+        add_str_reaction!(map, "H2PO4;- <> HPO4;2- + H;1", 7.20);
+        add_str_reaction!(map, "HPO4;2- <> PO4;3- + H;1", 12.35);
+        add_str_reaction!(map, "HF <> F;- + H;1", 3.17);
+        add_str_reaction!(map, "HNO2 <> NO2;- + H;1", 3.35);
+        add_str_reaction!(map, "CH3COOH <> CH3COO;- + H;1", 4.76);
+        add_str_reaction!(map, "H2CO3 <> HCO3;- + H;1", 6.35);
+        add_str_reaction!(map, "HCO3;- <> CO3;2- + H;1", 10.33);
+        add_str_reaction!(map, "H2S <> HS;- + H;1", 7.05);
+        add_str_reaction!(map, "HS;- <> S;2- + H;1", 12.9);
+        add_str_reaction!(map, "HClO <> ClO;- + H;1", 7.53);
+        add_str_reaction!(map, "NH4;+ <> NH3 + H;1", 9.25);
+        add_str_reaction!(map, "HCN <> CN;- + H;1", 9.21);
+
+        map
+    };
+}
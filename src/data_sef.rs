@@ -10,7 +10,7 @@ use std::collections::HashMap;
 
 /// Get the Standard Enthalpy of Formation (SEF) of a ion
 pub fn get_sef(ion: &Ion) -> Option<SEF> {
-    if let Some(&sef) = SEFMAP.get(&ion) {
+    if let Some(&sef) = SEFMAP.get(ion) {
         Some(sef)
     } else {
         None
@@ -32,7 +32,7 @@ macro_rules! add_str_ion {
 
 /// Check if the ion is defined, then unwrap. Otherwise: panic!
 fn safe_unwrap_ion(ion: Option<Ion>, s: &str) -> Ion {
-    if ion == None {
+    if ion.is_none() {
         panic!("Ion failed to create: {}", s);
     }
 
@@ -47,17 +47,19 @@ lazy_static! {
 
         add_str_ion!(map, "AlCl3", -705.63);
         add_str_ion!(map, "Al2O3", -1669.8);
-        // add_str_ion!(map, "Al(OH)3", -1277.0);
-        // add_str_ion!(map, "Al2(SO4)3", -3440.0);
+        add_str_ion!(map, "Al(OH)3", -1277.0);
+        add_str_ion!(map, "Al2(SO4)3", -3440.0);
+        add_str_ion!(map, "Al;3+", -531.0); // (aq)
 
         add_str_ion!(map, "BaCl2", -858.6);
         add_str_ion!(map, "BaCO3", -1213.0);
-        // add_str_ion!(map, "Ba(OH)2", -944.7);
+        add_str_ion!(map, "Ba(OH)2", -944.7);
+        add_str_ion!(map, "Ba;2+", -537.6); // (aq)
         add_str_ion!(map, "BaO", -548.1);
         add_str_ion!(map, "BaSO4", -1473.2);
 
         add_str_ion!(map, "Be", 0.0);
-        // add_str_ion!(map, "Be(OH)2", -902.9999);
+        add_str_ion!(map, "Be(OH)2", -902.9999);
         add_str_ion!(map, "BeO", -609.4);
 
         add_str_ion!(map, "BCl3", -402.96);
@@ -69,21 +71,24 @@ lazy_static! {
         add_str_ion!(map, "HBr", -36.29);
 
         add_str_ion!(map, "CdO", -258.0);
-        // add_str_ion!(map, "Cd(OH)2", -561.0);
+        add_str_ion!(map, "Cd(OH)2", -561.0);
         add_str_ion!(map, "CdS", -162.0);
         add_str_ion!(map, "CdSO4", -935.0);
 
         add_str_ion!(map, "Ca", 178.2); // (g)
-        add_str_ion!(map, "Ca;2+", 1925.9); // (g)
+        // NOTE: 1925.9 is the value of the gaseous ion, while the other ions are dissolved
+        // add_str_ion!(map, "Ca;2+", 1925.9); // (g)
+        add_str_ion!(map, "Ca;2+", -542.8); // (aq)
         add_str_ion!(map, "CaC2", -59.8);
         add_str_ion!(map, "CaCO3", -1206.9);
+        // NOTE: A later entry replaces an earlier one, so only the solid is kept, like for NaCl
         add_str_ion!(map, "CaCl2", -795.8);
-        add_str_ion!(map, "CaCl2", -877.3);
-        // add_str_ion!(map, "Ca3(PO4)2", -4132.0);
+        // add_str_ion!(map, "CaCl2", -877.3); // (aq)
+        add_str_ion!(map, "Ca3(PO4)2", -4132.0);
         add_str_ion!(map, "CaF2", -1219.6);
         add_str_ion!(map, "CaH2", -186.2);
-        //add_str_ion!(map, "Ca(OH)2", -986.09);
-        //add_str_ion!(map, "Ca(OH)2", -1002.82);
+        add_str_ion!(map, "Ca(OH)2", -986.09);
+        //add_str_ion!(map, "Ca(OH)2", -1002.82); // (aq)
         add_str_ion!(map, "CaO", -635.09);
         add_str_ion!(map, "CaSO4", -1434.52);
         add_str_ion!(map, "CaS", -482.4);
@@ -107,10 +112,13 @@ lazy_static! {
 
         add_str_ion!(map, "HCO3;-", -689.93);
         add_str_ion!(map, "CO3;2-", -675.23);
+        add_str_ion!(map, "CH3COOH", -485.8); // (aq)
+        add_str_ion!(map, "CH3COO;-", -486.0); // (aq)
 
         add_str_ion!(map, "Cl", 121.7);
         add_str_ion!(map, "Cl;-", -167.2); // (aq)
 
+        add_str_ion!(map, "Cu;2+", 64.8); // (aq)
         add_str_ion!(map, "CuO", -155.2);
         add_str_ion!(map, "CuSO4", -769.98);
 
@@ -120,7 +128,7 @@ lazy_static! {
         add_str_ion!(map, "H;+", 0.0); // (aq)
         add_str_ion!(map, "OH;-", -230.0); // (aq)
 
-        add_str_ion!(map, "H2O2", -187.8);
+        add_str_ion!(map, "H2O2;0", -187.8);
         add_str_ion!(map, "H3PO4", -1288.0);
         add_str_ion!(map, "HCN", 130.5);
         add_str_ion!(map, "HBr", -36.3);
@@ -133,6 +141,8 @@ lazy_static! {
         add_str_ion!(map, "I2", 23.0); // (aq)
         add_str_ion!(map, "I;-", -55.0); // (aq)
 
+        add_str_ion!(map, "F;-", -332.6); // (aq)
+
 
         add_str_ion!(map, "Fe;2+", -89.1); // From conradnaleway
         add_str_ion!(map, "Fe;3+", -48.5); // From conradnaleway
@@ -142,27 +152,26 @@ lazy_static! {
         add_str_ion!(map, "FeO", -272.0);
         add_str_ion!(map, "Fe3O4", -1118.4);
         add_str_ion!(map, "Fe2O3", -824.2);
-        //add_str_ion!(map, "Fe(OH)3", -823.0); // From mrbigler
-        //add_str_ion!(map, "Fe(OH)2", -569.0); // From conradnaleway
-        // NOTE: Temporary until the parentheses are implemented
-        add_str_ion!(map, "FeO2H2;", -569.0); // From conradnaleway
+        add_str_ion!(map, "Fe(OH)3", -823.0); // From mrbigler
+        add_str_ion!(map, "Fe(OH)2", -569.0); // From conradnaleway
 
         add_str_ion!(map, "FeSO4", -929.0);
-        //add_str_ion!(map, "Fe2(SO4)3", -2583.0);
+        add_str_ion!(map, "Fe2(SO4)3", -2583.0);
         add_str_ion!(map, "FeS", -102.0);
         add_str_ion!(map, "FeS2", -178.0);
 
         add_str_ion!(map, "PbO2", -277.0);
         add_str_ion!(map, "PbS", -100.0);
         add_str_ion!(map, "PbSO4", -920.0);
-        //add_str_ion!(map, "Pb(NO3)2", -452.0);
+        add_str_ion!(map, "Pb(NO3)2", -452.0);
+        add_str_ion!(map, "Pb;2+", -1.7); // (aq)
         add_str_ion!(map, "PbSO4", -920.0);
 
         add_str_ion!(map, "Mg;2", -466.85); // (aq)
 
         add_str_ion!(map, "MgCO3", -1095.797);
         add_str_ion!(map, "MgCl2", -641.8);
-        //add_str_ion!(map, "Mg(OH)2", -924.54); // (s)
+        add_str_ion!(map, "Mg(OH)2", -924.54); // (s)
         //add_str_ion!(map, "Mg(OH)2", -926.8); // (aq)
         add_str_ion!(map, "MgO", -601.6);
         add_str_ion!(map, "MgSO4", -1278.2);
@@ -183,6 +192,8 @@ lazy_static! {
         // NOTE: According to https://webbook.nist.gov/cgi/cbook.cgi?ID=C7664417&Mask=1 it has to be -45.94
         add_str_ion!(map, "NH3", -45.94); // (g)
 
+        add_str_ion!(map, "NH4;+", -132.5); // (aq)
+        add_str_ion!(map, "NO3;-", -205.0); // (aq)
         add_str_ion!(map, "NH4Cl", -314.55);
         add_str_ion!(map, "NH4NO3", -365.6);
         add_str_ion!(map, "NO2", 33.2);
@@ -191,6 +202,12 @@ lazy_static! {
         add_str_ion!(map, "N2O4", 9.16);
         add_str_ion!(map, "N2O5", -43.1);
         add_str_ion!(map, "N2O5", 11.3);
+        // NOTE: Neutral molecules written like NO2;0 are other keys than NO2, so they are entered twice
+        add_str_ion!(map, "NO;0", 90.29);
+        add_str_ion!(map, "NO2;0", 33.2);
+        add_str_ion!(map, "N2O4;0", 9.16);
+        // NOTE: Estimated as 2 NO, as the dimer is bound by only about 10.5 kJ / mol
+        add_str_ion!(map, "N2O2;0", 170.1);
 
         add_str_ion!(map, "O", 249.0);
         add_str_ion!(map, "O3", 143.0);
@@ -202,6 +219,9 @@ lazy_static! {
         add_str_ion!(map, "PCl3", -319.7);
         add_str_ion!(map, "PCl3", -278.0);
         add_str_ion!(map, "PCl5", -440.0);
+        add_str_ion!(map, "PO4;3-", -1277.4); // (aq)
+
+        add_str_ion!(map, "K;+", -252.4); // (aq)
         add_str_ion!(map, "KBr", -392.2);
         add_str_ion!(map, "K2CO3", -1150.0);
         add_str_ion!(map, "KClO3", -391.4);
@@ -215,6 +235,7 @@ lazy_static! {
         add_str_ion!(map, "SiCl4", -640.1);
         add_str_ion!(map, "SiO2", -910.86);
 
+        add_str_ion!(map, "Ag;+", 105.6); // (aq)
         add_str_ion!(map, "AgBr", -99.5);
         add_str_ion!(map, "AgCl", -127.01);
         add_str_ion!(map, "AgI", -62.4);
@@ -222,12 +243,14 @@ lazy_static! {
         add_str_ion!(map, "Ag2S", -31.8);
 
         add_str_ion!(map, "Na", 107.5); // (g)
+        add_str_ion!(map, "Na;+", -240.1); // (aq)
         add_str_ion!(map, "NaHCO3", -950.8);
         add_str_ion!(map, "Na2CO3", -1130.77);
-        add_str_ion!(map, "NaCl", -407.27);
-        add_str_ion!(map, "NaCl", -411.12);
-        add_str_ion!(map, "NaCl", -385.92);
-        add_str_ion!(map, "NaCl", -181.42);
+        // NOTE: A later entry replaces an earlier one, which left NaCl with the value of the gas
+        // add_str_ion!(map, "NaCl", -407.27); // (aq)
+        // add_str_ion!(map, "NaCl", -385.92);
+        // add_str_ion!(map, "NaCl", -181.42); // (g)
+        add_str_ion!(map, "NaCl", -411.12); // (s)
         add_str_ion!(map, "NaF", -569.0);
         add_str_ion!(map, "NaOH", -469.15);
         add_str_ion!(map, "NaOH", -425.93);
@@ -239,6 +262,7 @@ lazy_static! {
         add_str_ion!(map, "SO2", -296.84);
         add_str_ion!(map, "SO3", -395.7);
         add_str_ion!(map, "H2SO4", -814.0);
+        add_str_ion!(map, "SO4;2-", -909.3); // (aq)
 
         add_str_ion!(map, "Ti", 468.0); // (g)
         add_str_ion!(map, "TiCl4", -763.2);
@@ -246,6 +270,7 @@ lazy_static! {
         add_str_ion!(map, "TiO2", -944.7);
 
         add_str_ion!(map, "Zn", 130.7); // (g)
+        add_str_ion!(map, "Zn;2+", -153.9); // (aq)
         add_str_ion!(map, "ZnCl2", -415.1);
         add_str_ion!(map, "ZnO", -348.0);
         add_str_ion!(map, "ZnSO4", -980.14);
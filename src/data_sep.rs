@@ -10,7 +10,7 @@ use std::collections::HashMap;
 
 /// Get the Standard Electrode Potential (SEP) of a reaction
 pub fn get_sep(elem_reaction: &ElemReaction<Ion>) -> Option<SEP> {
-    if let Some(&sep) = SEPMAP.get(&elem_reaction) {
+    if let Some(&sep) = SEPMAP.get(elem_reaction) {
         Some(sep)
     } else if let Some(&sep) = SEPMAP.get(&elem_reaction.clone().swap()) {
         Some(sep)
@@ -46,7 +46,7 @@ fn valid_or_panic(reaction: ElemReaction<Ion>) -> ElemReaction<Ion> {
 
 /// Check if the reaction is defined, then unwrap. Otherwise: panic!
 fn safe_unwrap_reaction(reaction: Option<ElemReaction<Ion>>, s: &str) -> ElemReaction<Ion> {
-    if reaction == None {
+    if reaction.is_none() {
         panic!("Reaction failed to create: {}", s);
     }
 
@@ -88,19 +88,19 @@ lazy_static! {
 
         // NOTE: These are all the common ones
 
-        add_str_reaction!(map, "Ca;1 + e <> Ca", -3.8);
-        add_str_reaction!(map, "Li;1 + e <> Li", -3.0401);
-        add_str_reaction!(map, "Cs;1 + e <> Cs", -3.026);
-        add_str_reaction!(map, "Rb;1 + e <> Rb", -2.98);
-        add_str_reaction!(map, "K;1 + e <> K", -2.931);
-        add_str_reaction!(map, "Ba;2 + 2e <> Ba", -2.912);
-        add_str_reaction!(map, "Fr;1 + e <> Fr", -2.9);
-        add_str_reaction!(map, "Ca;2 + 2e <> Ca", -2.899);
-        add_str_reaction!(map, "Na;1 + e <> Na", -2.71);
-        add_str_reaction!(map, "Mg;1 + e <> Mg", -2.70);
-        add_str_reaction!(map, "Mg;2 + 2e <> Mg", -2.372);
+        add_str_reaction!(map, "Ca;1 + e <> Ca;0", -3.8);
+        add_str_reaction!(map, "Li;1 + e <> Li;0", -3.0401);
+        add_str_reaction!(map, "Cs;1 + e <> Cs;0", -3.026);
+        add_str_reaction!(map, "Rb;1 + e <> Rb;0", -2.98);
+        add_str_reaction!(map, "K;1 + e <> K;0", -2.931);
+        add_str_reaction!(map, "Ba;2 + 2e <> Ba;0", -2.912);
+        add_str_reaction!(map, "Fr;1 + e <> Fr;0", -2.9);
+        add_str_reaction!(map, "Ca;2 + 2e <> Ca;0", -2.899);
+        add_str_reaction!(map, "Na;1 + e <> Na;0", -2.71);
+        add_str_reaction!(map, "Mg;1 + e <> Mg;0", -2.70);
+        add_str_reaction!(map, "Mg;2 + 2e <> Mg;0", -2.372);
         add_str_reaction!(map, "H2 + 2e <> 2H;-", -2.23);
-        add_str_reaction!(map, "Be;2 + 2e <> Be", -1.847);
+        add_str_reaction!(map, "Be;2 + 2e <> Be;0", -1.847);
         add_str_reaction!(map, "Al;3 + 3e <> Al", -1.662);
         add_str_reaction!(map, "Mn;2 + 2e <> Mn", -1.185);
         add_str_reaction!(map, "Sn + 4H;1 + 4e <> SnH4", -1.07);
@@ -118,15 +118,17 @@ lazy_static! {
         add_str_reaction!(map, "Sn;4 + 2e <> Sn;2", 0.15);
         add_str_reaction!(map, "Cu;2 + e <> Cu;1", 0.159);
         add_str_reaction!(map, "HSO4;- + 3H;1 + 2e <> SO2 + 2H2O", 0.16);
-        add_str_reaction!(map, "SO4;- + 4H;1 + 2e <> SO2 + 2H2O", 0.17);
+        // NOTE: Sulfate was written as SO4;-, which left the charge unbalanced
+        add_str_reaction!(map, "SO4;2- + 4H;1 + 2e <> SO2 + 2H2O", 0.17);
         add_str_reaction!(map, "Cu;2 + 2e <> Cu", 0.337);
-        add_str_reaction!(map, "O2 + 2H2O + 2e <> 4OH;-", 0.40); // From Binas
+        // NOTE: This took 2 electrons, while 4 are needed to balance the charge
+        add_str_reaction!(map, "O2 + 2H2O + 4e <> 4OH;-", 0.40); // From Binas
         add_str_reaction!(map, "SO2 + 4H;1 + 4e <> S + 2H2O", 0.50);
         add_str_reaction!(map, "Cu;1 + e <> Cu", 0.520);
         add_str_reaction!(map, "I3;- + 2e <> 3I;-", 0.53);
         add_str_reaction!(map, "I2 + 2e <> 2I;-", 0.54);
         add_str_reaction!(map, "S2O3;2- + 6H;1 + 4e <> 2S + 3H2O", 0.60);
-        add_str_reaction!(map, "O2 + 2H;1 + 2e <> H2O2", 0.70);
+        add_str_reaction!(map, "O2 + 2H;1 + 2e <> H2O2;0", 0.70);
         add_str_reaction!(map, "Fe;3 + e <> Fe;2", 0.77);
         add_str_reaction!(map, "Ag;1 + e <> Ag", 0.7996);
         add_str_reaction!(map, "NO3;- + 2H;1 + e <> NO2 + H2O", 0.80);
@@ -141,7 +143,7 @@ lazy_static! {
         add_str_reaction!(map, "Au;3 + 3e <> Au", 1.52);
         add_str_reaction!(map, "Pb;4 + 2e <> Pb;2", 1.69);
         add_str_reaction!(map, "MnO4;- + 4H;1 + 3e <> MnO2 + 2H2O", 1.70);
-        add_str_reaction!(map, "H2O2 + 2H;1 + 2e <> 2H2O", 1.78);
+        add_str_reaction!(map, "H2O2;0 + 2H;1 + 2e <> 2H2O", 1.78);
         add_str_reaction!(map, "Co;3 + e <> Co;2", 1.82);
         add_str_reaction!(map, "Au;1 + e <> Au", 1.83);
         add_str_reaction!(map, "Ag;2 + e <> Ag;1", 1.98);
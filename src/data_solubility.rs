@@ -0,0 +1,84 @@
+use ion::Ion;
+
+use std::collections::HashMap;
+
+// Reference: CRC Handbook of Chemistry and Physics, aqueous solubility of inorganic compounds
+// NOTE: Converted from g / 100 g water to mol / L, taking the volume to be that of the water
+
+/// Get the solubility of a salt in water [ mol / L ]
+/// This is mainly known for soluble salts, insoluble ones are in the `KSPMAP`
+pub fn get_solubility(salt: &Ion) -> Option<f64> {
+    SOLUBILITYMAP.get(salt).cloned()
+}
+
+// This is mainly used for debugging purposes, to make sure no invalid ions are added
+macro_rules! str_to_ion {
+    ($s:expr) => {
+        safe_unwrap_ion(Ion::from_string($s), $s)
+    };
+}
+
+macro_rules! add_str_ion {
+    ($map:expr, $s:expr, $solubility:expr) => {
+        $map.insert(str_to_ion!($s), $solubility)
+    };
+}
+
+/// Check if the ion is defined, then unwrap. Otherwise: panic!
+fn safe_unwrap_ion(ion: Option<Ion>, s: &str) -> Ion {
+    if ion.is_none() {
+        panic!("Ion failed to create: {}", s);
+    }
+
+    ion.unwrap()
+}
+
+lazy_static! {
+    pub static ref SOLUBILITYMAP: HashMap<Ion, f64> = {
+        let mut map = HashMap::new();
+
+        // NOTE: Conditions: T = 298K
+
+        add_str_ion!(map, "AgNO3", 15.1);
+
+        add_str_ion!(map, "BaCl2", 1.72);
+
+        add_str_ion!(map, "CaCl2", 7.32);
+        add_str_ion!(map, "Ca(NO3)2", 8.80);
+
+        add_str_ion!(map, "CuSO4", 1.38);
+        add_str_ion!(map, "CuCl2", 5.63);
+
+        add_str_ion!(map, "FeSO4", 1.93);
+
+        add_str_ion!(map, "KBr", 5.70);
+        add_str_ion!(map, "KCl", 4.61);
+        add_str_ion!(map, "KI", 8.92);
+        add_str_ion!(map, "KNO3", 3.79);
+        add_str_ion!(map, "KOH", 21.6);
+        add_str_ion!(map, "K2SO4", 0.69);
+        add_str_ion!(map, "K2CO3", 8.10);
+
+        add_str_ion!(map, "LiCl", 19.6);
+
+        add_str_ion!(map, "MgCl2", 5.74);
+        add_str_ion!(map, "MgSO4", 2.92);
+
+        add_str_ion!(map, "NH4Cl", 7.38);
+        add_str_ion!(map, "NH4NO3", 26.6);
+
+        add_str_ion!(map, "NaCl", 6.15);
+        add_str_ion!(map, "NaBr", 9.17);
+        add_str_ion!(map, "NaNO3", 10.7);
+        add_str_ion!(map, "NaOH", 27.8);
+        add_str_ion!(map, "NaHCO3", 1.23);
+        add_str_ion!(map, "Na2CO3", 2.90);
+        add_str_ion!(map, "Na2SO4", 1.98);
+        add_str_ion!(map, "NaCH3COO", 6.00);
+
+        add_str_ion!(map, "ZnCl2", 29.8);
+        add_str_ion!(map, "ZnSO4", 3.57);
+
+        map
+    };
+}
@@ -1,6 +1,10 @@
+use activation::ActivatedReaction;
 use atom::Atom;
+use cell::GalvanicCell;
 use container::*;
+use ice_table::*;
 use ion::Ion;
+use latimer::LatimerDiagram;
 use molecule::Molecule;
 use reaction::*;
 use trait_element::Element;
@@ -29,10 +33,96 @@ macro_rules! fmt_type {
 }
 
 fmt!(Atom);
+fmt!(GalvanicCell);
 fmt!(Ion);
+fmt!(LatimerDiagram);
 fmt!(Molecule);
+fmt_type!(ActivatedReaction);
 fmt_type!(Container);
 fmt_type!(ContainerCompound);
 fmt_type!(ElemReaction);
 fmt_type!(ReactionCompound);
 fmt_type!(ReactionSide);
+
+impl<E: Element> Display for ReactionError<E> {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        match *self {
+            ReactionError::NotEnoughEnergy {
+                ref required,
+                ref shortfall,
+            } => write!(
+                formatter,
+                "Not enough energy: {} J required, {} J short",
+                required, shortfall
+            ),
+
+            ReactionError::NotEnoughElements { ref shortfalls } => {
+                let missing: Vec<String> = shortfalls
+                    .iter()
+                    .map(|x| format!("{} mol {}", x.moles, x.element.symbol()))
+                    .collect();
+
+                write!(
+                    formatter,
+                    "Not enough elements: {} short",
+                    missing.join(", ")
+                )
+            }
+
+            ReactionError::NotActivated { ref barrier } => write!(
+                formatter,
+                "Not activated: the barrier of {} J/mol can't be overcome",
+                barrier
+            ),
+        }
+    }
+}
+
+impl<E: Element> Display for IceTable<E> {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        let unit = match self.unit {
+            IceUnit::Moles => "mol",
+            IceUnit::Molar => "mol/L",
+        };
+
+        writeln!(
+            formatter,
+            "{}    (K = {}, in {})",
+            self.reaction,
+            format_number(self.equilibrium_constant),
+            unit
+        )?;
+
+        let mut rows = vec![
+            vec![String::new()],
+            vec![String::from("I")],
+            vec![String::from("C")],
+            vec![String::from("E")],
+        ];
+
+        for column in &self.columns {
+            rows[0].push(column.element.symbol());
+            rows[1].push(format_number(column.initial));
+            rows[2].push(column.change());
+            rows[3].push(column.equilibrium());
+        }
+
+        // Pad every cell to the width of its column
+        let widths: Vec<usize> = (0..rows[0].len())
+            .map(|i| rows.iter().map(|row| row[i].chars().count()).max().unwrap())
+            .collect();
+
+        for row in &rows {
+            let line: String = row
+                .iter()
+                .zip(widths.iter())
+                .map(|(cell, width)| format!("{:width$}   ", cell, width = width))
+                .collect();
+
+            write!(formatter, "\n{}", line.trim_end())?;
+        }
+
+        writeln!(formatter, "\n")?;
+        write!(formatter, "{}", self.steps().join("\n"))
+    }
+}
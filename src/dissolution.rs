@@ -0,0 +1,272 @@
+use container::*;
+use data_ksp::KSPMAP;
+use data_sef::get_sef;
+use data_solubility::get_solubility;
+use ion::Ion;
+use precipitation::{is_soluble, salt_dissolution};
+use reaction::ElemReaction;
+use trait_element::Element;
+use trait_properties::Properties;
+use types::*;
+
+#[derive(Debug, Clone, PartialEq)]
+/// A salt that dissolved into its ions
+pub struct Dissolution {
+    /// The dissolution of the salt, with the salt on the left-hand side
+    pub dissolution: ElemReaction<Ion>,
+
+    /// The pKsp of the salt, or None if it is soluble according to the solubility rules
+    pub pksp: Option<PK>,
+
+    /// The amount of moles that dissolved
+    pub moles: Moles,
+
+    /// The enthalpy change of the dissolution, or None if the enthalpy of solution is unknown
+    pub enthalpy: Option<Energy>,
+
+    /// The change in available energy, booked like for a reaction
+    pub energy_delta: Energy,
+
+    /// The heat released into the solution, changing the temperature (absorbed, if negative)
+    pub heat: Energy,
+
+    /// Whether the solution is saturated, leaving some of the salt undissolved
+    pub is_saturated: bool,
+}
+
+impl Dissolution {
+    /// Get the salt that dissolved
+    pub fn salt(&self) -> Ion {
+        self.dissolution.lhs.compounds[0].element.clone()
+    }
+}
+
+/// Calculate the pKsp of a salt from its solubility [ mol / L ]
+/// Every mole of salt gives ν moles of each ion, so Ksp = Π (ν s)^ν
+pub fn solubility_to_pksp(dissolution: &ElemReaction<Ion>, solubility: f64) -> PK {
+    let log_ksp: f64 = dissolution
+        .rhs
+        .compounds
+        .iter()
+        .map(|x| {
+            let amount = f64::from(x.amount);
+
+            amount * (amount * solubility).log10()
+        })
+        .sum();
+
+    PK::from(-log_ksp as PKType)
+}
+
+/// Calculate the enthalpy change of a reaction in solution, like the dissolution of a salt [ J ]
+/// Unlike `reaction_enthalpy`, this returns None if any of the compounds has no SEF data
+pub fn solution_enthalpy(reaction: &ElemReaction<Ion>) -> Option<Energy> {
+    let mut enthalpy = 0.0;
+
+    for (side, sign) in &[(&reaction.lhs, -1.0), (&reaction.rhs, 1.0)] {
+        for compound in &side.compounds {
+            let sef = get_sef(&compound.element)?;
+
+            // [ kJ ] -> [ J ]
+            enthalpy += sign * f64::from(sef.0) * 1000.0 * f64::from(compound.amount);
+        }
+    }
+
+    Some(Energy::from(enthalpy))
+}
+
+impl Container<Ion> {
+    /// Take up the enthalpy change of a process in solution, like the dissolution of a salt [ J ]
+    /// This is booked like a reaction in `Container::react`: the enthalpy is taken from
+    /// the available energy (or added to it, if exothermic), and its heat changes the
+    /// temperature. A process in solution can't be held back, so the available energy
+    /// only pays for an endothermic one as far as it goes.
+    /// Returns the change in available energy and the heat released into the solution
+    pub fn take_up_enthalpy(&mut self, enthalpy: Energy) -> (Energy, Energy) {
+        let supplied = if enthalpy > self.available_energy && enthalpy.0 > 0.0 {
+            self.available_energy
+        } else {
+            enthalpy
+        };
+
+        self.available_energy -= supplied;
+
+        let heat = Energy::from(0.0) - enthalpy;
+        self.add_heat(heat);
+
+        (Energy::from(0.0) - supplied, heat)
+    }
+
+    /// Get all salts in this container that could dissolve, together with their pKsp
+    /// (None if soluble according to the solubility rules, without data)
+    /// Salts that are insoluble according to the solubility rules, or unknown, are left out
+    pub fn dissolution_candidates(&self) -> Vec<(ElemReaction<Ion>, Option<PK>)> {
+        let elements: Vec<Ion> = self.contents.iter().map(|x| x.element.clone()).collect();
+
+        self.dissolution_candidates_among(&elements)
+    }
+
+    /// Get the given salts that could dissolve, like `dissolution_candidates`
+    fn dissolution_candidates_among(&self, salts: &[Ion]) -> Vec<(ElemReaction<Ion>, Option<PK>)> {
+        let mut candidates = vec![];
+
+        for salt in salts {
+            if self.moles_of(salt) <= Moles::from(0.0)
+                || salt.get_charge() != Some(AtomCharge::from(0))
+            {
+                continue;
+            }
+
+            if let Some((dissolution, &pksp)) = KSPMAP
+                .iter()
+                .find(|(reaction, _)| reaction.lhs.compounds[0].element == *salt)
+            {
+                candidates.push((dissolution.clone(), Some(pksp)));
+                continue;
+            }
+
+            let parts = match salt.molecule.ionic_parts() {
+                Some(parts) => parts,
+                None => continue,
+            };
+
+            let dissolution = salt_dissolution(&parts.cation, &parts.anion);
+
+            if let Some(solubility) = get_solubility(salt) {
+                let pksp = solubility_to_pksp(&dissolution, solubility);
+                candidates.push((dissolution, Some(pksp)));
+            } else if is_soluble(&parts.cation, &parts.anion) == Some(true) {
+                candidates.push((dissolution, None));
+            }
+        }
+
+        // Make sure the outcome doesn't depend on the order of the contents
+        candidates.sort_by_key(|(reaction, _)| reaction.lhs.symbol());
+        candidates.dedup_by(|a, b| a.0 == b.0);
+
+        candidates
+    }
+
+    /// Get the amount of a salt that can dissolve before the solution is saturated [ mol ]
+    fn dissolution_extent(&self, dissolution: &ElemReaction<Ion>, pksp: Option<PK>) -> f64 {
+        let maximum = self.moles_of(&dissolution.lhs.compounds[0].element).0;
+
+        let pksp = match pksp {
+            Some(pksp) => f64::from(pksp.0),
+            None => return maximum,
+        };
+
+        let volume = self.solution_volume().unwrap();
+
+        // Σ ν log10(c + ν x) + pKsp increases with x, and is 0 at saturation
+        let supersaturation = |extent: f64| -> f64 {
+            dissolution
+                .rhs
+                .compounds
+                .iter()
+                .map(|x| {
+                    let amount = f64::from(x.amount);
+                    let moles = self.moles_of(&x.element).0 + amount * extent;
+
+                    amount * (moles / volume).log10()
+                })
+                .sum::<f64>()
+                + pksp
+        };
+
+        if supersaturation(maximum) <= 0.0 {
+            return maximum;
+        }
+
+        let (mut low, mut high) = (0.0, maximum);
+        for _ in 0..100 {
+            let middle = (low + high) / 2.0;
+
+            if supersaturation(middle) < 0.0 {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+
+        low
+    }
+
+    /// Get the salts that would dissolve, without changing the container
+    pub fn get_dissolutions(&self) -> Vec<Dissolution> {
+        let mut container = self.clone();
+
+        container.dissolve()
+    }
+
+    /// Let all salts dissolve into their ions until the solution is saturated with them
+    /// Salts without data that are soluble according to the solubility rules dissolve completely.
+    /// This happens by itself when elements are added to water, see `add_elements`.
+    /// The enthalpy of solution is taken up like in `take_up_enthalpy`.
+    /// Returns the salts that dissolved, which is empty without water
+    pub fn dissolve(&mut self) -> Vec<Dissolution> {
+        let candidates = self.dissolution_candidates();
+
+        self.dissolve_candidates(candidates)
+    }
+
+    /// Let only the given salts dissolve, like `dissolve`
+    /// The other salts are taken to be in equilibrium with the solution already
+    pub fn dissolve_salts(&mut self, salts: &[Ion]) -> Vec<Dissolution> {
+        let candidates = self.dissolution_candidates_among(salts);
+
+        self.dissolve_candidates(candidates)
+    }
+
+    fn dissolve_candidates(
+        &mut self,
+        candidates: Vec<(ElemReaction<Ion>, Option<PK>)>,
+    ) -> Vec<Dissolution> {
+        let mut dissolutions = vec![];
+
+        if self.solution_volume().is_none() {
+            return dissolutions;
+        }
+
+        for (dissolution, pksp) in candidates {
+            let salt = dissolution.lhs.compounds[0].element.clone();
+            let available = self.moles_of(&salt);
+
+            let extent = self.dissolution_extent(&dissolution, pksp).min(available.0);
+            if extent <= 0.0 {
+                continue;
+            }
+
+            // Move the salt out of the solid into the solution,
+            // which never takes more than there is
+            self.contents.remove(&salt, Moles::from(extent));
+
+            self.add_undissolved(
+                &dissolution
+                    .rhs
+                    .compounds
+                    .iter()
+                    .map(|x| ContainerCompound {
+                        element: x.element.clone(),
+                        moles: Moles::from(extent * f64::from(x.amount)),
+                    })
+                    .collect::<Vec<_>>(),
+            );
+
+            let enthalpy = solution_enthalpy(&dissolution).map(|x| Energy::from(x.0 * extent));
+            let (energy_delta, heat) = self.take_up_enthalpy(enthalpy.unwrap_or(Energy::from(0.0)));
+
+            dissolutions.push(Dissolution {
+                is_saturated: extent < available.0,
+                dissolution,
+                pksp,
+                moles: Moles::from(extent),
+                enthalpy,
+                energy_delta,
+                heat,
+            });
+        }
+
+        dissolutions
+    }
+}
@@ -0,0 +1,261 @@
+use cell::GalvanicCell;
+use constants::{DEPLETED, FARADAY};
+use container::*;
+use data_sep::SEPMAP;
+use electron::is_electron;
+use ion::Ion;
+use reaction::{ElemReaction, ReactionSide};
+use redox::electrons_in;
+use trait_properties::Properties;
+use types::*;
+
+// Reference: Binas 6th edition, table 49
+/// Extra voltage needed to evolve a gas on a (platinum or graphite) electrode
+/// This is why brine gives chlorine instead of oxygen at the anode
+const OVERPOTENTIALS: &[(&str, SEPType)] = &[("O₂", 0.5), ("Cl₂", 0.1), ("H₂", 0.1)];
+
+#[derive(Debug, Clone, PartialEq)]
+/// A single step of an electrolysis, during which the same half-reactions take place
+pub struct ElectrolysisStep {
+    /// The cell formed by both electrodes
+    /// Its EMF is negative, as the reaction is driven by the applied voltage
+    pub cell: GalvanicCell,
+
+    /// The minimum applied voltage for this step, including overpotentials
+    pub minimum_voltage: SEP,
+
+    /// The amount of moles of electrons passed through the container
+    pub electrons: Moles,
+}
+
+impl ElectrolysisStep {
+    /// Get the amount of moles the total reaction of this step proceeded
+    pub fn extent(&self) -> Moles {
+        self.electrons.clone() / MolesType::from(self.cell.electrons())
+    }
+}
+
+/// Get the overpotential of the gases formed on a side of a half-reaction
+fn overpotential(side: &ReactionSide<Ion>) -> SEPType {
+    let mut overpotential = 0.0;
+
+    for compound in &side.compounds {
+        let symbol = compound.element.symbol();
+
+        for &(gas, value) in OVERPOTENTIALS {
+            if symbol == gas && value > overpotential {
+                overpotential = value;
+            }
+        }
+    }
+
+    overpotential
+}
+
+/// Get the amount of an element the electrode can use [ mol ]
+/// The amounts formed at the other electrode are left out, as the electrodes are divided
+fn usable_moles(
+    container: &Container<Ion>,
+    element: &Ion,
+    reserved: &[ContainerCompound<Ion>],
+) -> MolesType {
+    let reserved_moles = reserved
+        .iter()
+        .find(|x| &x.element == element)
+        .map_or(0.0, |x| x.moles.0);
+
+    container.moles_of(element).0 - reserved_moles
+}
+
+/// Check if all reactants on this side are available in the container
+fn is_available(
+    side: &ReactionSide<Ion>,
+    container: &Container<Ion>,
+    reserved: &[ContainerCompound<Ion>],
+) -> bool {
+    side.compounds.iter().all(|x| {
+        is_electron(&x.element) || usable_moles(container, &x.element, reserved) > DEPLETED
+    })
+}
+
+/// Add the products formed at an electrode to the amounts reserved for it
+fn reserve(reserved: &mut Vec<ContainerCompound<Ion>>, products: Vec<ContainerCompound<Ion>>) {
+    for product in products {
+        if is_electron(&product.element) {
+            continue;
+        }
+
+        if let Some(compound) = reserved.iter_mut().find(|x| x.element == product.element) {
+            compound.moles += product.moles;
+        } else {
+            reserved.push(product);
+        }
+    }
+}
+
+/// Select the half-reaction that takes place at an electrode, returned as a reduction
+/// together with its SEP corrected for overpotential
+fn select_half_reaction(
+    container: &Container<Ion>,
+    is_cathode: bool,
+    reserved: &[ContainerCompound<Ion>],
+) -> Option<(ElemReaction<Ion>, SEP)> {
+    let mut best: Option<(ElemReaction<Ion>, SEP)> = None;
+
+    for (reaction, &sep) in SEPMAP.iter() {
+        // The cathode reduces (reactants on the lhs), the anode oxidises (reactants on the rhs)
+        let (reactants, products) = if is_cathode {
+            (&reaction.lhs, &reaction.rhs)
+        } else {
+            (&reaction.rhs, &reaction.lhs)
+        };
+
+        if !is_available(reactants, container, reserved) {
+            continue;
+        }
+
+        let effective_sep = if is_cathode {
+            sep - SEP::from(overpotential(products))
+        } else {
+            sep + SEP::from(overpotential(products))
+        };
+
+        // The cathode reduces the strongest oxidator, the anode oxidises the strongest reductor
+        let is_better = match best {
+            Some((_, best_sep)) if is_cathode => effective_sep > best_sep,
+            Some((_, best_sep)) => effective_sep < best_sep,
+            None => true,
+        };
+
+        if is_better {
+            best = Some((reaction.clone(), effective_sep));
+        }
+    }
+
+    best
+}
+
+/// Get the half-reaction taking place at the cathode of a container under electrolysis
+pub fn cathode_reaction(container: &Container<Ion>) -> Option<ElemReaction<Ion>> {
+    select_half_reaction(container, true, &[]).map(|x| x.0)
+}
+
+/// Get the half-reaction taking place at the anode of a container under electrolysis
+pub fn anode_reaction(container: &Container<Ion>) -> Option<ElemReaction<Ion>> {
+    select_half_reaction(container, false, &[]).map(|x| x.0)
+}
+
+impl Container<Ion> {
+    /// Apply a current for a given time at a given voltage, according to Faraday's law
+    /// At every step, the cathode reduces the strongest oxidator and the anode oxidises
+    /// the strongest reductor. The electrodes are divided, so the products formed at one
+    /// electrode don't react at the other one.
+    /// The electrical work is taken from the available energy, and stops the electrolysis
+    /// when it runs out. What is not stored in the products heats the contents.
+    /// Returns the steps that took place, which is empty if the voltage is too low,
+    /// or fails if a step can't take place after all, leaving the container as it was
+    pub fn electrolyse(
+        &mut self,
+        current: Current,
+        time: Time,
+        voltage: SEP,
+    ) -> Result<Vec<ElectrolysisStep>, ReactionError<Ion>> {
+        // Earlier steps are undone if a later one fails
+        let before = self.clone();
+
+        let mut steps = vec![];
+        let mut electrons_left = current.0 * time.0 / FARADAY;
+
+        // The work needed to pass a mole of electrons
+        let work_per_electron = FARADAY * EnergyType::from(voltage.0);
+
+        let mut cathode_products: Vec<ContainerCompound<Ion>> = vec![];
+        let mut anode_products: Vec<ContainerCompound<Ion>> = vec![];
+
+        while electrons_left > DEPLETED {
+            let cathode = select_half_reaction(self, true, &anode_products);
+            let anode = select_half_reaction(self, false, &cathode_products);
+
+            let ((cathode, cathode_sep), (anode, anode_sep)) = match (cathode, anode) {
+                (Some(cathode), Some(anode)) => (cathode, anode),
+                _ => break,
+            };
+
+            let minimum_voltage = anode_sep - cathode_sep;
+            if voltage < minimum_voltage {
+                break;
+            }
+
+            let cell = GalvanicCell {
+                anode: anode.clone(),
+                cathode: cathode.clone(),
+            };
+            let reaction = cell.elem_reaction();
+
+            // The half-reactions per mole of electrons
+            let cathode_per_electron = 1.0 / MolesType::from(electrons_in(&cathode));
+            let anode_per_electron = 1.0 / MolesType::from(electrons_in(&anode));
+
+            // Limit the amount of electrons by the charge, the available energy
+            // and the amount of reactants each electrode can use
+            let mut electrons = electrons_left.min(self.available_energy.0 / work_per_electron);
+
+            let limits = scaled_compounds(&cathode.lhs, cathode_per_electron)
+                .into_iter()
+                .map(|x| (x, &anode_products))
+                .chain(
+                    scaled_compounds(&anode.rhs, anode_per_electron)
+                        .into_iter()
+                        .map(|x| (x, &cathode_products)),
+                );
+
+            for (compound, reserved) in limits {
+                if !is_electron(&compound.element) {
+                    electrons = electrons
+                        .min(usable_moles(self, &compound.element, reserved) / compound.moles.0);
+                }
+            }
+
+            if electrons <= DEPLETED {
+                break;
+            }
+
+            // Rounding errors are within the epsilon of the contents
+            let extent = electrons / MolesType::from(cell.electrons());
+            if let Err(error) = self.remove_elements(&scaled_compounds(&reaction.lhs, extent)) {
+                *self = before;
+                return Err(error);
+            }
+            self.add_undissolved(&scaled_compounds(&reaction.rhs, extent));
+
+            let work = Energy::from(electrons * work_per_electron);
+            self.available_energy -= work;
+
+            // ΔG = nF (E°anode - E°cathode) is stored in the products, so the overpotentials
+            // and the voltage above the minimum become heat
+            let emf = SEPMAP[&anode] - SEPMAP[&cathode];
+            let gibbs = Energy::from(electrons * FARADAY * f64::from(emf.0));
+            self.add_heat(work - gibbs);
+
+            // Keep track of the products, so they don't get used at the other electrode
+            reserve(
+                &mut cathode_products,
+                scaled_compounds(&cathode.rhs, electrons * cathode_per_electron),
+            );
+            reserve(
+                &mut anode_products,
+                scaled_compounds(&anode.lhs, electrons * anode_per_electron),
+            );
+
+            electrons_left -= electrons;
+
+            steps.push(ElectrolysisStep {
+                cell,
+                minimum_voltage,
+                electrons: Moles::from(electrons),
+            });
+        }
+
+        Ok(steps)
+    }
+}
@@ -1,6 +1,7 @@
 use atom::Atom;
 use ion::Ion;
 use molecule::*;
+use trait_element::Element;
 use types::*;
 
 lazy_static! {
@@ -30,3 +31,12 @@ lazy_static! {
         charge: Some(AtomCharge::from(-1))
     };
 }
+
+/// Check if an element is an electron (atom number 0)
+pub fn is_electron<E: Element>(element: &E) -> bool {
+    if let Some(molecule) = element.clone().get_molecule() {
+        molecule.compounds.len() == 1 && molecule.compounds[0].atom.number == AtomNumber::from(0)
+    } else {
+        false
+    }
+}
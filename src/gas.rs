@@ -0,0 +1,206 @@
+use constants::*;
+use container::Container;
+use data_entropy::get_entropy;
+use data_phases::{get_boiling_point, get_heat_capacity, get_phase};
+use data_sef::get_sef;
+use gibbs::written_charge;
+use reaction::{ElemReaction, ReactionSide};
+use trait_element::Element;
+use types::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The phase of a species
+pub enum Phase {
+    Solid,
+    Liquid,
+    Gas,
+
+    /// Dissolved in water
+    Aqueous,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// What is kept constant of the gas phase of a container
+pub enum GasMode {
+    /// A closed vessel of a given volume [ L ], in which the pressure changes
+    FixedVolume(f64),
+
+    /// A vessel with a piston at a given pressure, of which the volume changes
+    FixedPressure(Pressure),
+}
+
+impl Default for GasMode {
+    /// An open vessel at standard pressure
+    fn default() -> GasMode {
+        GasMode::FixedPressure(Pressure::from(STANDARD_PRESSURE))
+    }
+}
+
+/// Get the phase of an element at standard conditions
+/// Species without data are taken to be dissolved if charged, and solid otherwise
+pub fn phase_of<E: Element>(element: &E) -> Phase {
+    let ion = match element.clone().get_ion() {
+        Some(ion) => ion,
+        None => return Phase::Solid,
+    };
+
+    if let Some(phase) = get_phase(&ion) {
+        phase
+    } else if written_charge(&ion) != 0.0 {
+        Phase::Aqueous
+    } else {
+        Phase::Solid
+    }
+}
+
+/// Get the phase of an element at a temperature, at standard pressure
+/// Liquids and solids turn into a gas at their boiling point, if it is known
+pub fn phase_at<E: Element>(element: &E, temperature: Temperature) -> Phase {
+    let phase = phase_of(element);
+
+    if phase != Phase::Liquid && phase != Phase::Solid {
+        return phase;
+    }
+
+    let boiling_point = element
+        .clone()
+        .get_ion()
+        .and_then(|x| get_boiling_point(&x));
+
+    match boiling_point {
+        Some(boiling_point) if temperature.0 >= boiling_point => Phase::Gas,
+        _ => phase,
+    }
+}
+
+/// Get the molar heat capacity of an element [ J / (mol K) ]
+/// Species without data are estimated using Kopp's law: 3R for every atom
+pub fn heat_capacity_of<E: Element>(element: &E) -> f64 {
+    let ion = match element.clone().get_ion() {
+        Some(ion) => ion,
+        None => return 0.0,
+    };
+
+    if let Some(heat_capacity) = get_heat_capacity(&ion) {
+        return heat_capacity;
+    }
+
+    let atoms: f64 = ion
+        .molecule
+        .compounds
+        .iter()
+        .map(|x| f64::from(x.amount))
+        .sum();
+
+    3.0 * GAS_CONSTANT * atoms
+}
+
+/// Calculate the standard enthalpy of a reaction side, using the `SEFMAP` [ J ]
+/// Species without data (like elements) are taken to have an enthalpy of formation of 0
+fn side_enthalpy<E: Element>(side: &ReactionSide<E>) -> f64 {
+    side.compounds
+        .iter()
+        .filter_map(|x| {
+            let sef = get_sef(&x.element.clone().get_ion()?)?;
+
+            // [ kJ ] -> [ J ]
+            Some(f64::from(sef.0) * 1000.0 * f64::from(x.amount))
+        })
+        .sum()
+}
+
+/// Calculate the standard enthalpy change of a reaction [ J ]
+/// This is the heat that is absorbed (or released, if negative)
+pub fn reaction_enthalpy<E: Element>(reaction: &ElemReaction<E>) -> Energy {
+    Energy::from(side_enthalpy(&reaction.rhs) - side_enthalpy(&reaction.lhs))
+}
+
+/// Calculate the standard entropy of a reaction side, using the `ENTROPYMAP` [ J / K ]
+/// Returns None if the entropy of any of the species is unknown
+fn side_entropy<E: Element>(side: &ReactionSide<E>) -> Option<f64> {
+    side.compounds
+        .iter()
+        .map(|x| Some(get_entropy(&x.element.clone().get_ion()?)? * f64::from(x.amount)))
+        .sum()
+}
+
+/// Calculate the standard entropy change of a reaction [ J / K ]
+/// Returns None if the entropy of any of the species is unknown
+pub fn reaction_entropy<E: Element>(reaction: &ElemReaction<E>) -> Option<f64> {
+    Some(side_entropy(&reaction.rhs)? - side_entropy(&reaction.lhs)?)
+}
+
+impl<E: Element> Container<E> {
+    /// Get the total amount of gas in the container [ mol ]
+    pub fn gas_moles(&self) -> MolesType {
+        self.contents
+            .iter()
+            .filter(|x| phase_at(&x.element, self.temperature) == Phase::Gas)
+            // Starting from 0.0, as an empty sum of floats is -0.0
+            .fold(0.0, |total, x| total + x.moles.0)
+    }
+
+    /// Get the mole fraction of a gas in the gas phase (Dalton's law)
+    /// A gas that is used up (or was never added) has a mole fraction of 0
+    /// Returns None if the element is not a gas at the temperature of the container
+    pub fn mole_fraction(&self, element: &E) -> Option<f64> {
+        if phase_at(element, self.temperature) != Phase::Gas {
+            return None;
+        }
+
+        let moles = self.moles_of(element).0;
+        if moles <= 0.0 {
+            return Some(0.0);
+        }
+
+        Some(moles / self.gas_moles())
+    }
+
+    /// Get the volume of the gas phase, using the ideal gas law [ L ]
+    pub fn gas_volume(&self) -> f64 {
+        match self.gas_mode {
+            GasMode::FixedVolume(volume) => volume,
+            GasMode::FixedPressure(pressure) => {
+                // [ m³ ] -> [ L ]
+                self.gas_moles() * GAS_CONSTANT * self.temperature.0 / pressure.0 * 1000.0
+            }
+        }
+    }
+
+    /// Get the pressure of the gas phase, using the ideal gas law
+    pub fn pressure(&self) -> Pressure {
+        match self.gas_mode {
+            GasMode::FixedPressure(pressure) => pressure,
+            GasMode::FixedVolume(volume) => {
+                // [ L ] -> [ m³ ]
+                Pressure::from(
+                    self.gas_moles() * GAS_CONSTANT * self.temperature.0 / (volume / 1000.0),
+                )
+            }
+        }
+    }
+
+    /// Get the partial pressure of a gas (Dalton's law)
+    /// Returns None if the element is not a gas
+    pub fn partial_pressure(&self, element: &E) -> Option<Pressure> {
+        Some(self.pressure() * self.mole_fraction(element)?)
+    }
+
+    /// Get the heat capacity of all contents [ J / K ]
+    pub fn heat_capacity(&self) -> f64 {
+        self.contents
+            .iter()
+            .map(|x| heat_capacity_of(&x.element) * x.moles.0)
+            .sum()
+    }
+
+    /// Add heat to the contents (or remove it, if negative), changing the temperature
+    /// Nothing happens if the container is empty
+    pub fn add_heat(&mut self, heat: Energy) {
+        let heat_capacity = self.heat_capacity();
+
+        if heat_capacity > 0.0 {
+            self.temperature += Temperature::from(heat.0 / heat_capacity);
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use atom::Atom;
+use constants::*;
+use data_hydrolysis::HYDROLYSISMAP;
+use data_sep::*;
+use electron::is_electron;
+use ion::Ion;
+use reaction::ElemReaction;
+use trait_properties::Properties;
+use types::*;
+
+// Reference: Binas 6th edition, table 57
+/// Standard Gibbs energies of formation of the medium [ J / mol ]
+const MEDIUM_GIBBS: &[(&str, f64)] = &[
+    ("H;1", 0.0),
+    ("OH;-", -157_240.0),
+    ("H2O", -237_130.0),
+    ("H2", 0.0),
+    ("O2", 0.0),
+];
+
+/// Get the charge of a species as it is written in the data
+/// Species written without charge are neutral
+pub fn written_charge(ion: &Ion) -> f64 {
+    ion.charge.as_ref().map_or(0.0, |x| f64::from(x.0))
+}
+
+/// Get the amount of atoms with given number in a species
+fn count_atoms(ion: &Ion, number: &AtomNumber) -> f64 {
+    ion.molecule
+        .compounds
+        .iter()
+        .filter(|x| &x.atom.number == number)
+        .map(|x| f64::from(x.amount))
+        .sum()
+}
+
+/// Check if a species only consists of the element, oxygen and hydrogen
+fn is_made_of(ion: &Ion, element: &Atom) -> bool {
+    ion.molecule.compounds.iter().all(|x| {
+        x.atom.number == element.number
+            || x.atom.number == AtomNumber::from(1)
+            || x.atom.number == AtomNumber::from(8)
+    })
+}
+
+/// Get the standard Gibbs energy of a reaction, either from its SEP or its pK [ J / mol ]
+fn reaction_gibbs(reaction: &ElemReaction<Ion>, sep: Option<SEP>, pk: Option<PK>) -> f64 {
+    if let Some(sep) = sep {
+        // ΔG = -nFE
+        let electrons: f64 = reaction
+            .lhs
+            .compounds
+            .iter()
+            .filter(|x| is_electron(&x.element))
+            .map(|x| f64::from(x.amount))
+            .sum();
+
+        -electrons * FARADAY * f64::from(sep.0)
+    } else {
+        // ΔG = -RT ln(K) = RT ln(10) pK
+        GAS_CONSTANT * STANDARD_TEMPERATURE * 10f64.ln() * f64::from(pk.unwrap().0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A species containing an element, together with its standard Gibbs energy of formation
+pub struct SpeciesEnergy {
+    /// The species, with its charge written explicitly
+    pub species: Ion,
+
+    /// The element this is a species of
+    pub element: Atom,
+
+    /// The standard Gibbs energy of formation
+    pub gibbs_energy: Energy,
+}
+
+impl SpeciesEnergy {
+    /// Get the oxidation state of the element in this species,
+    /// taking hydrogen to be +1 and oxygen to be -2
+    pub fn oxidation_state(&self) -> f64 {
+        let k = count_atoms(&self.species, &self.element.number);
+        let o = count_atoms(&self.species, &AtomNumber::from(8));
+        let h = count_atoms(&self.species, &AtomNumber::from(1));
+
+        (written_charge(&self.species) - h + 2.0 * o) / k
+    }
+
+    /// Get the Gibbs energy needed to form this species out of the element and water,
+    /// kX + oH₂O -> S + bH⁺ + ce⁻, per atom of the element and divided by F [ V ]
+    /// It is returned as the linear function a + b * pH + c * E
+    /// Dissolved (charged) species have given activity, neutral species are taken to be pure
+    pub fn formation_function(&self, activity: f64) -> [f64; 3] {
+        let rt = GAS_CONSTANT * STANDARD_TEMPERATURE;
+        let water_gibbs = MEDIUM_GIBBS[2].1;
+
+        let k = count_atoms(&self.species, &self.element.number);
+        let o = count_atoms(&self.species, &AtomNumber::from(8));
+        let h = count_atoms(&self.species, &AtomNumber::from(1));
+        let z = written_charge(&self.species);
+
+        let b = 2.0 * o - h;
+        let c = z + b;
+
+        let dissolved = if z != 0.0 { rt * activity.ln() } else { 0.0 };
+
+        [
+            (self.gibbs_energy.0 + dissolved - o * water_gibbs) / (k * FARADAY),
+            -b * rt * 10f64.ln() / (k * FARADAY),
+            -c / k,
+        ]
+    }
+}
+
+/// Get the standard Gibbs energies of formation of all species of an element,
+/// by combining the half-reactions of the `SEPMAP` and, if requested, the hydrolyses of the
+/// `HYDROLYSISMAP`. Only species made of the element, oxygen and hydrogen are considered.
+/// The charges are taken as written in the data, so species written without one are neutral
+pub fn get_species_energies(element: &Atom, with_hydrolysis: bool) -> Vec<SpeciesEnergy> {
+    // Water and its ions can't be formed out of themselves
+    if element.number == AtomNumber::from(1) || element.number == AtomNumber::from(8) {
+        return vec![];
+    }
+
+    let mut known: Vec<(Ion, f64)> = MEDIUM_GIBBS
+        .iter()
+        .map(|&(symbol, gibbs)| (Ion::from_string(symbol).unwrap(), gibbs))
+        .collect();
+
+    // Collect all reactions between the element, water and electrons
+    let mut reactions: Vec<(ElemReaction<Ion>, f64)> = vec![];
+
+    for (reaction, &sep) in SEPMAP.iter() {
+        reactions.push((reaction.clone(), reaction_gibbs(reaction, Some(sep), None)));
+    }
+
+    if with_hydrolysis {
+        for (reaction, &pk) in HYDROLYSISMAP.iter() {
+            reactions.push((reaction.clone(), reaction_gibbs(reaction, None, Some(pk))));
+        }
+    }
+
+    reactions.retain(|(reaction, _)| {
+        let species: Vec<&Ion> = reaction
+            .lhs
+            .compounds
+            .iter()
+            .chain(reaction.rhs.compounds.iter())
+            .map(|x| &x.element)
+            .filter(|x| !is_electron(*x))
+            .collect();
+
+        species.iter().all(|x| is_made_of(x, element))
+            && species
+                .iter()
+                .any(|x| count_atoms(x, &element.number) > 0.0)
+    });
+
+    // Make sure the outcome doesn't depend on the order of the maps
+    reactions.sort_by_key(|(reaction, _)| reaction.lhs.symbol() + &reaction.rhs.symbol());
+
+    let mut species: Vec<Ion> = vec![];
+    for (reaction, _) in &reactions {
+        for rc in reaction
+            .lhs
+            .compounds
+            .iter()
+            .chain(reaction.rhs.compounds.iter())
+        {
+            if count_atoms(&rc.element, &element.number) > 0.0 && !species.contains(&rc.element) {
+                species.push(rc.element.clone());
+            }
+        }
+    }
+
+    // The element in its standard state is the reference
+    for ion in &species {
+        if written_charge(ion) == 0.0 && ion.molecule.compounds.len() == 1 {
+            known.push((ion.clone(), 0.0));
+        }
+    }
+
+    let gibbs_of = |known: &[(Ion, f64)], ion: &Ion| -> Option<f64> {
+        if is_electron(ion) {
+            return Some(0.0);
+        }
+
+        known.iter().find(|x| &x.0 == ion).map(|x| x.1)
+    };
+
+    // Solve every reaction with exactly one unknown species, until nothing changes anymore
+    let mut changed = true;
+    while changed {
+        changed = false;
+
+        for (reaction, reaction_gibbs) in &reactions {
+            let mut unknown = None;
+            let mut unknowns = 0;
+            let mut sum = 0.0;
+
+            for (rc, sign) in reaction
+                .lhs
+                .compounds
+                .iter()
+                .map(|x| (x, -1.0))
+                .chain(reaction.rhs.compounds.iter().map(|x| (x, 1.0)))
+            {
+                let amount = f64::from(rc.amount) * sign;
+
+                if let Some(gibbs) = gibbs_of(&known, &rc.element) {
+                    sum += amount * gibbs;
+                } else if unknown
+                    .as_ref()
+                    .is_none_or(|x: &(Ion, f64)| x.0 == rc.element)
+                {
+                    if unknown.is_none() {
+                        unknowns += 1;
+                    }
+
+                    let previous = unknown.map_or(0.0, |x| x.1);
+                    unknown = Some((rc.element.clone(), previous + amount));
+                } else {
+                    unknowns += 1;
+                }
+            }
+
+            if let (1, Some((ion, amount))) = (unknowns, unknown) {
+                // ΔG_r = Σ products - Σ reactants
+                known.push((ion, (reaction_gibbs - sum) / amount));
+                changed = true;
+            }
+        }
+    }
+
+    // Write the charge explicitly, so neutral species are displayed as such
+    known
+        .into_iter()
+        .filter(|x| species.contains(&x.0))
+        .map(|(ion, gibbs)| {
+            let charge = AtomCharge::from(written_charge(&ion) as AtomChargeType);
+
+            SpeciesEnergy {
+                species: Ion {
+                    molecule: ion.molecule,
+                    charge: Some(charge),
+                },
+                element: element.clone(),
+                gibbs_energy: Energy::from(gibbs),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,235 @@
+use activation::Activation;
+use container::*;
+use reaction::ElemReaction;
+use trait_element::Element;
+use trait_reaction::Reaction;
+use types::*;
+
+#[derive(Debug, Clone, PartialEq)]
+/// A reaction that took place in a container
+pub struct ReactionEvent<E: Element> {
+    /// The reaction that took place
+    pub reaction: ElemReaction<E>,
+
+    /// The activation barrier of the reaction, if it has one
+    pub activation: Option<Activation<E>>,
+
+    /// What happened
+    pub outcome: ReactionOutcome<E>,
+
+    /// The temperature before the reaction took place
+    pub temperature: Temperature,
+}
+
+#[derive(Debug, Clone)]
+/// A container which keeps a log of the reactions that took place in it,
+/// so they can be undone, redone and replayed
+pub struct History<E: Element> {
+    /// The container in its current state
+    container: Container<E>,
+
+    /// The container before any reaction took place
+    initial: Container<E>,
+
+    /// All logged reactions, including the undone ones
+    events: Vec<ReactionEvent<E>>,
+
+    /// The amount of events that are applied, the others can be redone
+    position: usize,
+}
+
+/// Apply a logged reaction to a container, see `Container::react`
+/// Fails if the container lacks the required elements or energy, or can't overcome
+/// the activation barrier, in which case the container is left untouched
+fn apply_event<E: Element>(
+    container: &mut Container<E>,
+    event: &ReactionEvent<E>,
+) -> Result<(), ReactionError<E>> {
+    container.apply(
+        &event.reaction,
+        event.activation.clone(),
+        event.outcome.extent,
+        Energy::from(0.0) - event.outcome.energy_delta,
+        event.outcome.consumed.clone(),
+        event.outcome.produced.clone(),
+    )?;
+
+    Ok(())
+}
+
+impl<E: Element> History<E> {
+    /// Start logging the reactions in a container
+    pub fn new(container: Container<E>) -> History<E> {
+        History {
+            initial: container.clone(),
+            container,
+            events: vec![],
+            position: 0,
+        }
+    }
+
+    /// Get the container in its current state
+    pub fn container(&self) -> &Container<E> {
+        &self.container
+    }
+
+    /// Get the container in its current state, to change it
+    /// The changes aren't logged, so undoing doesn't revert them
+    /// and they don't show up in `replay` or `states`
+    pub fn container_mut(&mut self) -> &mut Container<E> {
+        &mut self.container
+    }
+
+    /// Get the reactions that took place, without the undone ones
+    pub fn events(&self) -> &[ReactionEvent<E>] {
+        &self.events[..self.position]
+    }
+
+    /// Applies given `Reaction` to the container and logs it
+    /// See `Container::react`
+    pub fn react<R: Reaction<E>>(
+        &mut self,
+        reaction: &R,
+    ) -> Result<ReactionOutcome<E>, ReactionError<E>> {
+        self.react_extent(reaction, 1.0)
+    }
+
+    /// Applies given `Reaction` to the container a fractional amount of times and logs it
+    /// See `Container::react_extent`
+    pub fn react_extent<R: Reaction<E>>(
+        &mut self,
+        reaction: &R,
+        extent: MolesType,
+    ) -> Result<ReactionOutcome<E>, ReactionError<E>> {
+        let temperature = self.container.temperature;
+        let outcome = self.container.react_extent(reaction, extent)?;
+
+        Ok(self.log(reaction, outcome, temperature))
+    }
+
+    /// Applies given `Reaction` to the container until it can't go any further and logs it
+    /// See `Container::react_to_completion`
+    pub fn react_to_completion<R: Reaction<E>>(
+        &mut self,
+        reaction: &R,
+    ) -> Result<ReactionOutcome<E>, ReactionError<E>> {
+        let temperature = self.container.temperature;
+        let outcome = self.container.react_to_completion(reaction)?;
+
+        Ok(self.log(reaction, outcome, temperature))
+    }
+
+    /// Log a reaction, which makes redoing the undone ones impossible
+    fn log<R: Reaction<E>>(
+        &mut self,
+        reaction: &R,
+        outcome: ReactionOutcome<E>,
+        temperature: Temperature,
+    ) -> ReactionOutcome<E> {
+        self.events.truncate(self.position);
+        self.events.push(ReactionEvent {
+            reaction: reaction.elem_reaction(),
+            activation: reaction.activation(),
+            outcome: outcome.clone(),
+            temperature,
+        });
+        self.position += 1;
+
+        outcome
+    }
+
+    /// Undo the last reaction
+    /// Returns false if there is nothing to undo,
+    /// or fails if its products were removed from the container since,
+    /// in which case nothing changes
+    pub fn undo(&mut self) -> Result<bool, ReactionError<E>> {
+        if self.position == 0 {
+            return Ok(false);
+        }
+
+        let event = &self.events[self.position - 1];
+
+        self.container.remove_elements(&event.outcome.produced)?;
+        self.container.add_undissolved(&event.outcome.consumed);
+        self.container.available_energy -= event.outcome.energy_delta;
+        self.container.temperature = event.temperature;
+        self.position -= 1;
+
+        Ok(true)
+    }
+
+    /// Redo the last undone reaction
+    /// Returns false if there is nothing to redo,
+    /// or fails if the container changed since in a way the reaction can't take place anymore,
+    /// in which case nothing changes
+    pub fn redo(&mut self) -> Result<bool, ReactionError<E>> {
+        if self.position == self.events.len() {
+            return Ok(false);
+        }
+
+        apply_event(&mut self.container, &self.events[self.position])?;
+        self.position += 1;
+
+        Ok(true)
+    }
+
+    /// Apply all logged reactions (except the undone ones) to another container, in order
+    /// Stops at the first reaction that can't take place, and returns why
+    pub fn replay(&self, container: &mut Container<E>) -> Result<(), ReactionError<E>> {
+        for event in self.events() {
+            apply_event(container, event)?;
+        }
+
+        Ok(())
+    }
+
+    /// Get the state of the container before the first reaction and after every reaction
+    /// Fails if the reactions can't take place again from the initial container,
+    /// which happens if they used what was added with `container_mut`
+    pub fn states(&self) -> Result<Vec<Container<E>>, ReactionError<E>> {
+        let mut container = self.initial.clone();
+        let mut states = vec![container.clone()];
+
+        for event in self.events() {
+            apply_event(&mut container, event)?;
+            states.push(container.clone());
+        }
+
+        Ok(states)
+    }
+
+    /// Export the amount of moles of every element after every reaction as CSV
+    /// Fails if the states can't be recreated, see `states`
+    pub fn to_csv(&self) -> Result<String, ReactionError<E>> {
+        let states = self.states()?;
+
+        // Every element that was ever present, in order of appearance
+        let mut elements: Vec<E> = vec![];
+        for state in &states {
+            for compound in &state.contents {
+                if !elements.contains(&compound.element) {
+                    elements.push(compound.element.clone());
+                }
+            }
+        }
+
+        let mut csv = String::from("step");
+        for element in &elements {
+            csv += ",";
+            csv += &element.symbol();
+        }
+        csv += "\n";
+
+        for (step, state) in states.iter().enumerate() {
+            csv += &step.to_string();
+
+            for element in &elements {
+                csv += &format!(",{:.4}", state.moles_of(element));
+            }
+
+            csv += "\n";
+        }
+
+        Ok(csv)
+    }
+}
@@ -0,0 +1,462 @@
+use container::Container;
+use math::solve_polynomial;
+use reaction::ElemReaction;
+use trait_element::Element;
+
+/// Relative precision the extent is solved to numerically
+const TOLERANCE: f64 = 1e-12;
+
+/// Amount of halvings or doublings after which solving numerically gives up
+const MAX_ITERATIONS: usize = 2000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The unit of the amounts in an ICE table
+pub enum IceUnit {
+    /// Amounts [ mol ], if the container has no volume to react in
+    Moles,
+
+    /// Concentrations [ mol / L ]
+    Molar,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// How the extent of an ICE table was solved for
+pub enum IceMethod {
+    /// Exactly, as a linear equation
+    Linear,
+
+    /// Exactly, using the quadratic formula
+    Quadratic,
+
+    /// Exactly, using Cardano's formula
+    Cubic,
+
+    /// Exactly, using Ferrari's method
+    Quartic,
+
+    /// Numerically, using bisection
+    Numerical,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A column of an ICE table: a species taking part in the equilibrium
+pub struct IceColumn<E: Element> {
+    /// The species
+    pub element: E,
+
+    /// How much it changes per unit of extent: negative for reactants, positive for products
+    pub coefficient: i32,
+
+    /// The initial amount or concentration
+    pub initial: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// An Initial/Change/Equilibrium table of a single equilibrium, solved for the extent x
+pub struct IceTable<E: Element> {
+    /// The equilibrium
+    pub reaction: ElemReaction<E>,
+
+    /// Its equilibrium constant, in the unit of the table
+    pub equilibrium_constant: f64,
+
+    /// The unit of the amounts
+    pub unit: IceUnit,
+
+    /// Every species, except the solvent
+    pub columns: Vec<IceColumn<E>>,
+
+    /// K · Π reactants - Π products = 0, as a polynomial in x, constant term first
+    pub polynomial: Vec<f64>,
+
+    /// The range of x in which no amount becomes negative
+    pub bounds: (f64, f64),
+
+    /// How x was solved for
+    pub method: IceMethod,
+
+    /// The extent x at equilibrium
+    pub extent: f64,
+}
+
+/// Format a number readably, in scientific notation if it is very large or small
+/// with 4 significant digits
+pub fn format_number(value: f64) -> String {
+    if value == 0.0 {
+        // Don't show -0
+        format!("{:.4}", 0.0)
+    } else if 1e-3 <= value.abs() && value.abs() < 1e5 {
+        let decimals = (3.0 - value.abs().log10().floor()).max(0.0) as usize;
+
+        format!("{:.*}", decimals, value)
+    } else {
+        format!("{:.3e}", value)
+    }
+}
+
+/// Multiply two polynomials, given their coefficients from the constant term up
+fn multiply(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut product = vec![0.0; a.len() + b.len() - 1];
+
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            product[i + j] += x * y;
+        }
+    }
+
+    product
+}
+
+/// Evaluate a polynomial, given its coefficients from the constant term up
+fn evaluate(polynomial: &[f64], x: f64) -> f64 {
+    polynomial.iter().rev().fold(0.0, |sum, a| sum * x + a)
+}
+
+/// Format a multiple of x, like -2x
+fn format_x(coefficient: i32) -> String {
+    match coefficient {
+        1 => String::from("x"),
+        -1 => String::from("-x"),
+        _ => format!("{}x", coefficient),
+    }
+}
+
+impl<E: Element> IceColumn<E> {
+    /// Get the amount at an extent
+    pub fn at(&self, extent: f64) -> f64 {
+        self.initial + f64::from(self.coefficient) * extent
+    }
+
+    /// Get the change, symbolically in x
+    pub fn change(&self) -> String {
+        if self.coefficient > 0 {
+            format!("+{}", format_x(self.coefficient))
+        } else {
+            format_x(self.coefficient)
+        }
+    }
+
+    /// Get the amount at equilibrium, symbolically in x
+    pub fn equilibrium(&self) -> String {
+        if self.initial == 0.0 {
+            return format_x(self.coefficient);
+        }
+
+        let sign = if self.coefficient > 0 { "+" } else { "-" };
+
+        format!(
+            "{} {} {}",
+            format_number(self.initial),
+            sign,
+            format_x(self.coefficient.abs())
+        )
+    }
+}
+
+impl<E: Element> IceTable<E> {
+    /// Get the amount of a species at equilibrium, in the unit of the table
+    /// Returns None if it isn't in the table
+    pub fn equilibrium_of(&self, element: &E) -> Option<f64> {
+        self.columns
+            .iter()
+            .find(|x| &x.element == element)
+            .map(|x| x.at(self.extent))
+    }
+
+    /// Get the expression of the equilibrium constant, symbolically in x
+    pub fn expression(&self) -> String {
+        let factors = |is_product: bool| -> String {
+            let factors: Vec<String> = self
+                .columns
+                .iter()
+                .filter(|x| (x.coefficient > 0) == is_product)
+                .map(|x| {
+                    let power = x.coefficient.abs();
+
+                    if power == 1 {
+                        format!("({})", x.equilibrium())
+                    } else {
+                        format!("({})^{}", x.equilibrium(), power)
+                    }
+                })
+                .collect();
+
+            if factors.is_empty() {
+                String::from("1")
+            } else {
+                factors.join("")
+            }
+        };
+
+        format!(
+            "{} = {} / {}",
+            format_number(self.equilibrium_constant),
+            factors(true),
+            factors(false)
+        )
+    }
+
+    /// Get the equation that is solved for x, as a polynomial with a positive leading term
+    pub fn equation(&self) -> String {
+        let mut equation = String::new();
+        let sign = if *self.polynomial.last().unwrap() < 0.0 {
+            -1.0
+        } else {
+            1.0
+        };
+
+        for (power, coefficient) in self.polynomial.iter().enumerate().rev() {
+            let coefficient = sign * coefficient;
+
+            if coefficient == 0.0 {
+                continue;
+            }
+
+            let sign = if coefficient < 0.0 { "-" } else { "+" };
+            if equation.is_empty() {
+                if coefficient < 0.0 {
+                    equation += "-";
+                }
+            } else {
+                equation += &format!(" {} ", sign);
+            }
+
+            equation += &format_number(coefficient.abs());
+            match power {
+                0 => {}
+                1 => equation += "x",
+                _ => equation += &format!("x^{}", power),
+            }
+        }
+
+        if equation.is_empty() {
+            equation += "0";
+        }
+
+        equation + " = 0"
+    }
+
+    /// Get the steps from the table to the amounts at equilibrium, to show to the user
+    pub fn steps(&self) -> Vec<String> {
+        let mut steps = vec![self.expression(), self.equation()];
+
+        steps.push(match self.method {
+            IceMethod::Linear => String::from("This is linear in x, so it is solved directly"),
+            IceMethod::Quadratic => String::from("This is solved using the quadratic formula"),
+            IceMethod::Cubic => String::from("This is solved using Cardano's formula"),
+            IceMethod::Quartic => String::from("This is solved using Ferrari's method"),
+            IceMethod::Numerical => String::from("This is solved numerically, using bisection"),
+        });
+
+        steps.push(format!(
+            "x = {}, the only root between {} and {}",
+            format_number(self.extent),
+            format_number(self.bounds.0),
+            format_number(self.bounds.1)
+        ));
+
+        for column in &self.columns {
+            let amount = format_number(column.at(self.extent));
+
+            steps.push(match self.unit {
+                IceUnit::Molar => format!("[{}] = {} mol/L", column.element.symbol(), amount),
+                IceUnit::Moles => format!("n({}) = {} mol", column.element.symbol(), amount),
+            });
+        }
+
+        steps
+    }
+
+    /// Solve the polynomial for x, using its roots if it isn't of a higher degree than 4
+    /// Returns the root within the bounds, and how it was found,
+    /// or None if there is no bound to search it in
+    fn solve(&self) -> Option<(f64, IceMethod)> {
+        let scale = self
+            .columns
+            .iter()
+            .fold(0.0, |a: f64, b| a.max(b.initial.abs()))
+            .max(1.0);
+        let margin = TOLERANCE * scale;
+
+        let exact = solve_polynomial(&self.polynomial).and_then(|roots| {
+            roots
+                .into_iter()
+                .filter(|&x| self.bounds.0 - margin <= x && x <= self.bounds.1 + margin)
+                .map(|x| x.clamp(self.bounds.0, self.bounds.1))
+                .filter(|&x| evaluate(&self.polynomial, x).is_finite())
+                .min_by(|a, b| {
+                    evaluate(&self.polynomial, *a)
+                        .abs()
+                        .total_cmp(&evaluate(&self.polynomial, *b).abs())
+                })
+        });
+
+        let method = match self.polynomial.len() {
+            2 => IceMethod::Linear,
+            3 => IceMethod::Quadratic,
+            4 => IceMethod::Cubic,
+            5 => IceMethod::Quartic,
+            _ => IceMethod::Numerical,
+        };
+
+        match exact {
+            Some(extent) if method != IceMethod::Numerical => Some((extent, method)),
+            _ => Some((self.bisect(scale)?, IceMethod::Numerical)),
+        }
+    }
+
+    /// Find the root within the bounds using bisection
+    /// The polynomial decreases within the bounds, as the reactants are used up
+    /// and the products formed, so there is exactly one
+    /// Returns None if a missing bound isn't found within `MAX_ITERATIONS` doublings
+    fn bisect(&self, scale: f64) -> Option<f64> {
+        let f = |x: f64| evaluate(&self.polynomial, x);
+
+        let (mut low, mut high) = self.bounds;
+
+        // Without reactants or products there is no bound, so search for one
+        let mut step = scale;
+        for _ in 0..MAX_ITERATIONS {
+            if low.is_finite() {
+                break;
+            }
+            if f(-step) >= 0.0 {
+                low = -step;
+            }
+            step *= 2.0;
+        }
+
+        step = scale;
+        for _ in 0..MAX_ITERATIONS {
+            if high.is_finite() {
+                break;
+            }
+            if f(step) <= 0.0 {
+                high = step;
+            }
+            step *= 2.0;
+        }
+
+        if !low.is_finite() || !high.is_finite() {
+            return None;
+        }
+
+        for _ in 0..MAX_ITERATIONS {
+            let middle = (low + high) / 2.0;
+
+            if high - low <= TOLERANCE * scale * 1e-3 || middle == low || middle == high {
+                break;
+            }
+
+            if f(middle) > 0.0 {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+
+        Some((low + high) / 2.0)
+    }
+}
+
+impl<E: Element> Container<E> {
+    /// Build the ICE table of an equilibrium with an equilibrium constant, starting
+    /// from the contents of this container, and solve it for the extent
+    /// The table uses concentrations in the volume of the reactions, see `reaction_volume`,
+    /// or amounts if there is no volume. The solvent is left out of solutions.
+    /// Returns None if the equilibrium constant isn't positive,
+    /// or if the extent can't be found
+    pub fn ice_table(
+        &self,
+        reaction: &ElemReaction<E>,
+        equilibrium_constant: f64,
+    ) -> Option<IceTable<E>> {
+        if equilibrium_constant <= 0.0 || !equilibrium_constant.is_finite() {
+            return None;
+        }
+
+        let (unit, volume) = match self.reaction_volume() {
+            Some(volume) => (IceUnit::Molar, volume),
+            None => (IceUnit::Moles, 1.0),
+        };
+        let is_solution = self.solution_volume().is_some();
+
+        let mut columns: Vec<IceColumn<E>> = vec![];
+        for (side, sign) in &[(&reaction.lhs, -1), (&reaction.rhs, 1)] {
+            for compound in &side.compounds {
+                if is_solution && Container::is_solvent(&compound.element) {
+                    continue;
+                }
+
+                let change = sign * i32::from(compound.amount);
+
+                if let Some(column) = columns.iter_mut().find(|x| x.element == compound.element) {
+                    column.coefficient += change;
+                    continue;
+                }
+
+                columns.push(IceColumn {
+                    element: compound.element.clone(),
+                    coefficient: change,
+                    initial: self.moles_of(&compound.element).0 / volume,
+                });
+            }
+        }
+        columns.retain(|x| x.coefficient != 0);
+
+        // K · Π (initial - a x)^a - Π (initial + b x)^b
+        let mut reactants = vec![equilibrium_constant];
+        let mut products = vec![1.0];
+        let mut bounds = (-f64::INFINITY, f64::INFINITY);
+
+        for column in &columns {
+            let factor = [column.initial, f64::from(column.coefficient)];
+            let limit = -column.initial / f64::from(column.coefficient);
+
+            for _ in 0..column.coefficient.abs() {
+                if column.coefficient > 0 {
+                    products = multiply(&products, &factor);
+                } else {
+                    reactants = multiply(&reactants, &factor);
+                }
+            }
+
+            if column.coefficient > 0 {
+                bounds.0 = bounds.0.max(limit);
+            } else {
+                bounds.1 = bounds.1.min(limit);
+            }
+        }
+
+        let mut polynomial = vec![0.0; reactants.len().max(products.len())];
+        for (i, a) in reactants.iter().enumerate() {
+            polynomial[i] += a;
+        }
+        for (i, b) in products.iter().enumerate() {
+            polynomial[i] -= b;
+        }
+
+        // Remove terms that cancelled out, like x² when K = 1 in A + B ⇌ C + D
+        let largest = polynomial.iter().fold(0.0, |a: f64, b| a.max(b.abs()));
+        while polynomial.len() > 1 && polynomial.last().unwrap().abs() <= 1e-12 * largest {
+            polynomial.pop();
+        }
+
+        let mut table = IceTable {
+            reaction: reaction.clone(),
+            equilibrium_constant,
+            unit,
+            columns,
+            polynomial,
+            bounds,
+            method: IceMethod::Numerical,
+            extent: 0.0,
+        };
+
+        let (extent, method) = table.solve()?;
+        table.extent = extent;
+        table.method = method;
+
+        Some(table)
+    }
+}
@@ -1,3 +1,5 @@
+use container::{Container, ContainerCompound};
+use data_ions::*;
 use electron::ELECTRON;
 use molecule::Molecule;
 use namings::*;
@@ -92,14 +94,10 @@ impl Ion {
             None
         };
 
-        if let Some(molecule) = molecule {
-            Some(Ion {
-                molecule,
-                charge: charge_option,
-            })
-        } else {
-            None
-        }
+        molecule.map(|molecule| Ion {
+            molecule,
+            charge: charge_option,
+        })
     }
 
     /// Convert a `Molecule` into an `Ion`
@@ -119,6 +117,16 @@ impl Ion {
             return Some(charge);
         }
 
+        // Known polyatomic ions, like MnO4, come before ionic compounds, like Mn⁷⁺ and 4 O²⁻
+        if is_implied_polyatomic_ion(&self.molecule) {
+            return get_polyatomic_ion(&self.molecule).unwrap().0.get_charge();
+        }
+
+        // Ionic compounds are neutral
+        if self.molecule.ionic_parts().is_some() {
+            return Some(charge);
+        }
+
         for molecule_compound in &self.molecule.compounds {
             if let Some(atom_charge) = molecule_compound.atom.charge_by_group() {
                 let mol_charge = AtomCharge::from(
@@ -155,6 +163,16 @@ impl Element for Ion {
     fn get_ion(self) -> Option<Ion> {
         Some(self)
     }
+
+    fn dissolve_in(container: &mut Container<Ion>, added: &[ContainerCompound<Ion>]) {
+        // Added water can dissolve any salt, otherwise only the added salts can dissolve
+        if added.iter().any(|x| Container::is_solvent(&x.element)) {
+            container.dissolve();
+        } else {
+            let salts: Vec<Ion> = added.iter().map(|x| x.element.clone()).collect();
+            container.dissolve_salts(&salts);
+        }
+    }
 }
 
 impl Properties for Ion {
@@ -173,6 +191,12 @@ impl Properties for Ion {
     }
 
     fn name(&self) -> String {
+        if let Some(&(ref ion, name)) = get_polyatomic_ion(&self.molecule) {
+            if ion.get_charge() == self.get_charge() {
+                return String::from(name);
+            }
+        }
+
         let mut name = String::new();
 
         name += &self.molecule.name();
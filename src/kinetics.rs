@@ -0,0 +1,668 @@
+use constants::GAS_CONSTANT;
+use container::*;
+use gas::reaction_enthalpy;
+use math::solve_linear;
+use reaction::{ElemReaction, ReactionSide};
+use trait_element::Element;
+use types::*;
+
+/// Relative error allowed in every step of the integration
+const RELATIVE_TOLERANCE: f64 = 1e-6;
+
+/// Absolute error allowed in every step of the integration [ mol / L ]
+const ABSOLUTE_TOLERANCE: f64 = 1e-12;
+
+/// Amount of steps after which the integration gives up
+const MAX_STEPS: usize = 1_000_000;
+
+// Reference: Dormand, J. R.; Prince, P. J. (1980), "A family of embedded Runge-Kutta formulae"
+/// Runge-Kutta matrix of the Dormand-Prince method
+/// The nodes aren't needed, as the rates don't depend on time
+const DP_A: [[f64; 6]; 7] = [
+    [0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [1.0 / 5.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [3.0 / 40.0, 9.0 / 40.0, 0.0, 0.0, 0.0, 0.0],
+    [44.0 / 45.0, -56.0 / 15.0, 32.0 / 9.0, 0.0, 0.0, 0.0],
+    [
+        19372.0 / 6561.0,
+        -25360.0 / 2187.0,
+        64448.0 / 6561.0,
+        -212.0 / 729.0,
+        0.0,
+        0.0,
+    ],
+    [
+        9017.0 / 3168.0,
+        -355.0 / 33.0,
+        46732.0 / 5247.0,
+        49.0 / 176.0,
+        -5103.0 / 18656.0,
+        0.0,
+    ],
+    [
+        35.0 / 384.0,
+        0.0,
+        500.0 / 1113.0,
+        125.0 / 192.0,
+        -2187.0 / 6784.0,
+        11.0 / 84.0,
+    ],
+];
+
+/// Weights of the fifth order solution of the Dormand-Prince method
+const DP_B: [f64; 7] = [
+    35.0 / 384.0,
+    0.0,
+    500.0 / 1113.0,
+    125.0 / 192.0,
+    -2187.0 / 6784.0,
+    11.0 / 84.0,
+    0.0,
+];
+
+/// Weights of the embedded fourth order solution of the Dormand-Prince method
+const DP_B_EMBEDDED: [f64; 7] = [
+    5179.0 / 57600.0,
+    0.0,
+    7571.0 / 16695.0,
+    393.0 / 640.0,
+    -92097.0 / 339_200.0,
+    187.0 / 2100.0,
+    1.0 / 40.0,
+];
+
+// Reference: Verwer, J. G. et al. (1999), "A second-order Rosenbrock method applied to
+// photochemical dispersion problems"
+/// Parameter of the ROS2 method, which makes it L-stable
+const ROS2_GAMMA: f64 = 1.0 + 0.5 * ::std::f64::consts::SQRT_2;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// The Arrhenius parameters of a rate constant: k = A · e^(-Ea / RT)
+pub struct Arrhenius {
+    /// The pre-exponential factor A, in the units of the rate constant
+    pub pre_exponential: f64,
+
+    /// The activation energy Ea [ J / mol ]
+    pub activation_energy: Energy,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A reaction with a rate law: r = k · Π [X]^order
+pub struct KineticReaction<E: Element> {
+    /// The reaction taking place
+    pub reaction: ElemReaction<E>,
+
+    /// The rate constant of the forward reaction
+    pub forward: Arrhenius,
+
+    /// The rate constant of the reverse reaction, if it is reversible
+    pub reverse: Option<Arrhenius>,
+
+    /// The orders of the forward rate law in every reactant
+    pub forward_orders: Vec<(E, f64)>,
+
+    /// The orders of the reverse rate law in every product
+    pub reverse_orders: Vec<(E, f64)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The method used to integrate the rate laws over time
+pub enum Integrator {
+    /// The adaptive fifth order Runge-Kutta method of Dormand and Prince,
+    /// which is fast for reactions of similar rates
+    RungeKutta,
+
+    /// The adaptive second order Rosenbrock method ROS2, which stays stable
+    /// for stiff networks of very fast and very slow reactions
+    Stiff,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The concentrations at a moment during an integration
+pub struct KineticsPoint {
+    /// The time since the start
+    pub time: Time,
+
+    /// The concentration of every species [ mol / L ]
+    pub concentrations: Vec<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// How the concentrations changed over time
+pub struct Trajectory<E: Element> {
+    /// The species taking part in the reactions, in order of appearance
+    pub species: Vec<E>,
+
+    /// The concentrations at the start and after every step
+    pub points: Vec<KineticsPoint>,
+}
+
+/// The orders of a rate law, by species index
+type Orders = Vec<(usize, f64)>;
+
+/// The reactions of a network, with everything needed to evaluate the rates quickly
+struct Network {
+    /// The net change of every species for every reaction
+    changes: Vec<Vec<f64>>,
+
+    /// The forward and reverse rate constants of every reaction
+    rate_constants: Vec<(f64, f64)>,
+
+    /// The forward and reverse orders of every reaction, by species index
+    orders: Vec<(Orders, Orders)>,
+}
+
+/// Get the orders of an elementary reaction: the coefficients of its side
+/// The solvent doesn't appear in rate laws
+fn elementary_orders<E: Element>(side: &ReactionSide<E>) -> Vec<(E, f64)> {
+    let mut orders: Vec<(E, f64)> = vec![];
+
+    for compound in &side.compounds {
+        if Container::<E>::is_solvent(&compound.element) {
+            continue;
+        }
+
+        if let Some(order) = orders.iter_mut().find(|x| x.0 == compound.element) {
+            order.1 += f64::from(compound.amount);
+            continue;
+        }
+
+        orders.push((compound.element.clone(), f64::from(compound.amount)));
+    }
+
+    orders
+}
+
+/// Get the product of all concentrations to the power of their orders
+fn concentration_product(orders: &[(usize, f64)], state: &[f64]) -> f64 {
+    orders
+        .iter()
+        .map(|&(species, order)| state[species].max(0.0).powf(order))
+        .product()
+}
+
+impl Arrhenius {
+    /// Create Arrhenius parameters
+    pub fn new(pre_exponential: f64, activation_energy: Energy) -> Arrhenius {
+        Arrhenius {
+            pre_exponential,
+            activation_energy,
+        }
+    }
+
+    /// Create a rate constant that doesn't depend on the temperature
+    pub fn constant(rate_constant: f64) -> Arrhenius {
+        Arrhenius::new(rate_constant, Energy::from(0.0))
+    }
+
+    /// Get the rate constant at a temperature
+    pub fn rate_constant(&self, temperature: Temperature) -> f64 {
+        self.pre_exponential * (-self.activation_energy.0 / (GAS_CONSTANT * temperature.0)).exp()
+    }
+}
+
+impl<E: Element> KineticReaction<E> {
+    /// Create an irreversible reaction, with orders equal to its coefficients
+    pub fn new(reaction: ElemReaction<E>, forward: Arrhenius) -> KineticReaction<E> {
+        KineticReaction {
+            forward_orders: elementary_orders(&reaction.lhs),
+            reverse_orders: elementary_orders(&reaction.rhs),
+            reaction,
+            forward,
+            reverse: None,
+        }
+    }
+
+    /// Make the reaction reversible, with given rate constant of the reverse reaction
+    pub fn with_reverse(mut self, reverse: Arrhenius) -> KineticReaction<E> {
+        self.reverse = Some(reverse);
+        self
+    }
+
+    /// Set the order of the forward rate law in an element, which doesn't need to be a reactant
+    pub fn with_order(mut self, element: &E, order: f64) -> KineticReaction<E> {
+        self.forward_orders.retain(|x| &x.0 != element);
+        self.forward_orders.push((element.clone(), order));
+        self
+    }
+
+    /// Set the order of the reverse rate law in an element, which doesn't need to be a product
+    pub fn with_reverse_order(mut self, element: &E, order: f64) -> KineticReaction<E> {
+        self.reverse_orders.retain(|x| &x.0 != element);
+        self.reverse_orders.push((element.clone(), order));
+        self
+    }
+
+    /// Get the forward and reverse rate at a temperature [ mol / (L s) ]
+    /// given the concentration of every element [ mol / L ]
+    pub fn rates<F: Fn(&E) -> f64>(
+        &self,
+        concentration: F,
+        temperature: Temperature,
+    ) -> (f64, f64) {
+        let product = |orders: &[(E, f64)]| -> f64 {
+            orders
+                .iter()
+                .map(|x| concentration(&x.0).max(0.0).powf(x.1))
+                .product()
+        };
+
+        let forward = self.forward.rate_constant(temperature) * product(&self.forward_orders);
+        let reverse = match self.reverse {
+            Some(reverse) => reverse.rate_constant(temperature) * product(&self.reverse_orders),
+            None => 0.0,
+        };
+
+        (forward, reverse)
+    }
+
+    /// Get the net rate at a temperature [ mol / (L s) ]
+    /// See `rates`
+    pub fn net_rate<F: Fn(&E) -> f64>(&self, concentration: F, temperature: Temperature) -> f64 {
+        let (forward, reverse) = self.rates(concentration, temperature);
+
+        forward - reverse
+    }
+}
+
+/// Get all species taking part in the reactions, in order of appearance
+pub fn network_species<E: Element>(reactions: &[KineticReaction<E>]) -> Vec<E> {
+    let mut species: Vec<E> = vec![];
+
+    for reaction in reactions {
+        let sides = [&reaction.reaction.lhs, &reaction.reaction.rhs];
+        let elements = sides
+            .iter()
+            .flat_map(|side| side.compounds.iter().map(|x| &x.element))
+            .chain(reaction.forward_orders.iter().map(|x| &x.0))
+            .chain(reaction.reverse_orders.iter().map(|x| &x.0));
+
+        for element in elements {
+            if !species.contains(element) {
+                species.push(element.clone());
+            }
+        }
+    }
+
+    species
+}
+
+/// Get the net change of every species when a reaction takes place once
+pub fn network_changes<E: Element>(reaction: &ElemReaction<E>, species: &[E]) -> Vec<f64> {
+    let mut changes = vec![0.0; species.len()];
+
+    for (side, sign) in &[(&reaction.lhs, -1.0), (&reaction.rhs, 1.0)] {
+        for compound in &side.compounds {
+            if let Some(index) = species.iter().position(|x| x == &compound.element) {
+                changes[index] += sign * f64::from(compound.amount);
+            }
+        }
+    }
+
+    changes
+}
+
+impl Network {
+    fn new<E: Element>(
+        reactions: &[KineticReaction<E>],
+        species: &[E],
+        temperature: Temperature,
+    ) -> Network {
+        let index = |orders: &[(E, f64)]| -> Orders {
+            orders
+                .iter()
+                .map(|x| (species.iter().position(|y| y == &x.0).unwrap(), x.1))
+                .collect()
+        };
+
+        Network {
+            changes: reactions
+                .iter()
+                .map(|x| network_changes(&x.reaction, species))
+                .collect(),
+            rate_constants: reactions
+                .iter()
+                .map(|x| {
+                    (
+                        x.forward.rate_constant(temperature),
+                        x.reverse.map_or(0.0, |y| y.rate_constant(temperature)),
+                    )
+                })
+                .collect(),
+            orders: reactions
+                .iter()
+                .map(|x| (index(&x.forward_orders), index(&x.reverse_orders)))
+                .collect(),
+        }
+    }
+
+    /// Get the time derivative of the state
+    /// The state holds the concentration of every species, followed by the extent
+    /// of every reaction per volume [ mol / L ]
+    fn derivative(&self, state: &[f64]) -> Vec<f64> {
+        let species = state.len() - self.changes.len();
+        let mut derivative = vec![0.0; state.len()];
+
+        for (reaction, changes) in self.changes.iter().enumerate() {
+            let (forward, reverse) = self.rate_constants[reaction];
+            let (forward_orders, reverse_orders) = &self.orders[reaction];
+
+            let rate = forward * concentration_product(forward_orders, state)
+                - reverse * concentration_product(reverse_orders, state);
+
+            for (i, change) in changes.iter().enumerate() {
+                derivative[i] += change * rate;
+            }
+            derivative[species + reaction] = rate;
+        }
+
+        derivative
+    }
+
+    /// Get the Jacobian of the derivative, using finite differences
+    fn jacobian(&self, state: &[f64], derivative: &[f64]) -> Vec<Vec<f64>> {
+        let mut jacobian = vec![vec![0.0; state.len()]; state.len()];
+
+        for column in 0..state.len() {
+            let delta = f64::EPSILON.sqrt() * state[column].abs().max(1e-8);
+
+            let mut shifted = state.to_vec();
+            shifted[column] += delta;
+            let shifted_derivative = self.derivative(&shifted);
+
+            for (row, values) in jacobian.iter_mut().enumerate() {
+                values[column] = (shifted_derivative[row] - derivative[row]) / delta;
+            }
+        }
+
+        jacobian
+    }
+
+    /// Take a Dormand-Prince step
+    /// Returns the new state and the error estimate
+    fn runge_kutta_step(&self, state: &[f64], step: f64) -> (Vec<f64>, Vec<f64>) {
+        let mut stages: Vec<Vec<f64>> = Vec::with_capacity(DP_A.len());
+
+        for row in &DP_A {
+            let mut stage_state = state.to_vec();
+
+            for (stage, &weight) in stages.iter().zip(row.iter()) {
+                for (value, derivative) in stage_state.iter_mut().zip(stage.iter()) {
+                    *value += step * weight * derivative;
+                }
+            }
+
+            stages.push(self.derivative(&stage_state));
+        }
+
+        let mut next = state.to_vec();
+        let mut error = vec![0.0; state.len()];
+
+        for (i, stage) in stages.iter().enumerate() {
+            for (j, derivative) in stage.iter().enumerate() {
+                next[j] += step * DP_B[i] * derivative;
+                error[j] += step * (DP_B[i] - DP_B_EMBEDDED[i]) * derivative;
+            }
+        }
+
+        (next, error)
+    }
+
+    /// Take a ROS2 step
+    /// Returns the new state and the error estimate, or None if the step is too large
+    fn stiff_step(&self, state: &[f64], step: f64) -> Option<(Vec<f64>, Vec<f64>)> {
+        let derivative = self.derivative(state);
+        let jacobian = self.jacobian(state, &derivative);
+
+        // (I - γhJ)
+        let matrix: Vec<Vec<f64>> = jacobian
+            .iter()
+            .enumerate()
+            .map(|(row, values)| {
+                values
+                    .iter()
+                    .enumerate()
+                    .map(|(column, value)| {
+                        let identity = if row == column { 1.0 } else { 0.0 };
+                        identity - ROS2_GAMMA * step * value
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let first = solve_linear(matrix.clone(), derivative)?;
+
+        let intermediate: Vec<f64> = state
+            .iter()
+            .zip(first.iter())
+            .map(|(value, slope)| value + step * slope)
+            .collect();
+        let rhs = self
+            .derivative(&intermediate)
+            .iter()
+            .zip(first.iter())
+            .map(|(value, slope)| value - 2.0 * slope)
+            .collect();
+
+        let second = solve_linear(matrix, rhs)?;
+
+        let next = state
+            .iter()
+            .enumerate()
+            .map(|(i, value)| value + step * (1.5 * first[i] + 0.5 * second[i]))
+            .collect();
+
+        // The difference with the linearly implicit Euler method
+        let error = first
+            .iter()
+            .zip(second.iter())
+            .map(|(a, b)| 0.5 * step * (a + b))
+            .collect();
+
+        Some((next, error))
+    }
+}
+
+/// Get the size of the error relative to the tolerances, which is acceptable if at most 1
+fn error_norm(state: &[f64], next: &[f64], error: &[f64]) -> f64 {
+    let sum: f64 = error
+        .iter()
+        .enumerate()
+        .map(|(i, error)| {
+            let scale = ABSOLUTE_TOLERANCE + RELATIVE_TOLERANCE * state[i].abs().max(next[i].abs());
+
+            (error / scale).powi(2)
+        })
+        .sum();
+
+    (sum / error.len() as f64).sqrt()
+}
+
+impl<E: Element> Container<E> {
+    /// Get the volume the reactions take place in: the solution, or else a closed vessel [ L ]
+    /// Returns None if neither is available
+    pub fn reaction_volume(&self) -> Option<f64> {
+        if let Some(volume) = self.solution_volume() {
+            return Some(volume);
+        }
+
+        let volume = self.gas_volume();
+        if volume > 0.0 {
+            Some(volume)
+        } else {
+            None
+        }
+    }
+
+    /// Let a network of reactions take place at their rates for a duration
+    /// The volume and the temperature are kept constant during the reactions,
+    /// after which the heat released by them changes the temperature.
+    /// Returns how the concentrations changed over time,
+    /// or None if the container has no volume or the integration failed
+    pub fn integrate(
+        &mut self,
+        reactions: &[KineticReaction<E>],
+        duration: Time,
+        integrator: Integrator,
+    ) -> Option<Trajectory<E>> {
+        let volume = self.reaction_volume()?;
+        let species = network_species(reactions);
+        let network = Network::new(reactions, &species, self.temperature);
+
+        let mut state: Vec<f64> = species
+            .iter()
+            .map(|x| self.moles_of(x).0 / volume)
+            .chain(reactions.iter().map(|_| 0.0))
+            .collect();
+
+        let concentrations = |state: &[f64]| state[..species.len()].to_vec();
+
+        let mut time = 0.0;
+        let mut step = duration.0 * 1e-6;
+        let mut points = vec![KineticsPoint {
+            time: Time::from(0.0),
+            concentrations: concentrations(&state),
+        }];
+
+        let order = match integrator {
+            Integrator::RungeKutta => 5.0,
+            Integrator::Stiff => 2.0,
+        };
+
+        for _ in 0..MAX_STEPS {
+            if time >= duration.0 {
+                break;
+            }
+
+            step = step.min(duration.0 - time);
+
+            let result = match integrator {
+                Integrator::RungeKutta => Some(network.runge_kutta_step(&state, step)),
+                Integrator::Stiff => network.stiff_step(&state, step),
+            };
+
+            let norm = match result {
+                Some((ref next, ref error)) => error_norm(&state, next, error),
+                None => f64::INFINITY,
+            };
+
+            if norm <= 1.0 {
+                state = result.unwrap().0;
+                time += step;
+
+                // Amounts can't become negative, overshooting is within the tolerances
+                for value in state.iter_mut().take(species.len()) {
+                    *value = value.max(0.0);
+                }
+
+                points.push(KineticsPoint {
+                    time: Time::from(time),
+                    concentrations: concentrations(&state),
+                });
+            }
+
+            // Grow or shrink the step to get the error just within the tolerances
+            let factor = if norm.is_finite() {
+                0.9 * norm.max(1e-10).powf(-1.0 / order)
+            } else {
+                0.2
+            };
+            step *= factor.clamp(0.2, 5.0);
+
+            if !step.is_finite() || step <= 0.0 || time + step == time {
+                return None;
+            }
+        }
+
+        if time < duration.0 {
+            return None;
+        }
+
+        // Put the final amounts in the container
+        for (i, element) in species.iter().enumerate() {
+            let moles = state[i] * volume;
+            let current = self.moles_of(element).0;
+
+            if moles > current {
+                self.contents.add(element, Moles::from(moles - current));
+            } else {
+                self.contents.remove(element, Moles::from(current - moles));
+            }
+        }
+
+        for (i, reaction) in reactions.iter().enumerate() {
+            let extent = state[species.len() + i] * volume;
+            self.add_heat(Energy::from(
+                -reaction_enthalpy(&reaction.reaction).0 * extent,
+            ));
+        }
+
+        Some(Trajectory { species, points })
+    }
+}
+
+impl<E: Element> Trajectory<E> {
+    /// Get the concentration of an element over time [ mol / L ]
+    /// Returns None if the element didn't take part in the reactions
+    pub fn concentrations_of(&self, element: &E) -> Option<Vec<(Time, f64)>> {
+        let index = self.species.iter().position(|x| x == element)?;
+
+        Some(
+            self.points
+                .iter()
+                .map(|x| (x.time, x.concentrations[index]))
+                .collect(),
+        )
+    }
+
+    /// Get the concentration of an element at the end [ mol / L ]
+    /// Returns None if the element didn't take part in the reactions
+    pub fn final_concentration(&self, element: &E) -> Option<f64> {
+        self.concentrations_of(element)?.last().map(|x| x.1)
+    }
+
+    /// Get the time it took for the concentration of an element to halve
+    /// Returns None if it didn't halve, or if the element didn't take part in the reactions
+    pub fn half_life(&self, element: &E) -> Option<Time> {
+        let concentrations = self.concentrations_of(element)?;
+        let half = concentrations.first()?.1 / 2.0;
+
+        for pair in concentrations.windows(2) {
+            let ((start, before), (end, after)) = (pair[0], pair[1]);
+
+            if before > half && after <= half {
+                // Interpolate exponentially between both points, which is exact for first order
+                let fraction = if after > 0.0 {
+                    (before / half).ln() / (before / after).ln()
+                } else {
+                    (before - half) / (before - after)
+                };
+                return Some(start + (end - start) * fraction);
+            }
+        }
+
+        None
+    }
+
+    /// Export the concentrations over time as CSV
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("time");
+        for element in &self.species {
+            csv += ",";
+            csv += &element.symbol();
+        }
+        csv += "\n";
+
+        for point in &self.points {
+            csv += &format!("{:.6}", point.time);
+
+            for concentration in &point.concentrations {
+                csv += &format!(",{:.6}", concentration);
+            }
+
+            csv += "\n";
+        }
+
+        csv
+    }
+}
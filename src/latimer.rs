@@ -0,0 +1,210 @@
+use atom::Atom;
+use gibbs::*;
+use ion::Ion;
+use trait_properties::Properties;
+use types::*;
+
+/// Oxidation states closer than this are considered equal
+const EPSILON: f64 = 1e-6;
+
+#[derive(Debug, Clone, PartialEq)]
+/// A step in a Latimer diagram: the reduction of a species to the next lower oxidation state
+pub struct LatimerStep {
+    /// The oxidised species
+    pub from: Ion,
+
+    /// The reduced species
+    pub to: Ion,
+
+    /// The reduction potential of this step, in acid (pH = 0)
+    pub potential: SEP,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A point in a Frost diagram
+pub struct FrostPoint {
+    /// The species
+    pub species: Ion,
+
+    /// The oxidation state of the element in this species
+    pub oxidation_state: f64,
+
+    /// The volt-equivalent nE° of the species relative to the element [ V ]
+    pub volt_equivalent: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The Latimer diagram of an element in acid, generated from the `SEPMAP`
+pub struct LatimerDiagram {
+    /// The element this diagram is about
+    pub element: Atom,
+
+    /// The most stable species of every oxidation state, from high to low oxidation state
+    pub species: Vec<SpeciesEnergy>,
+}
+
+/// Get the volt-equivalent nE° of a species in acid (pH = 0) with unit activity [ V ]
+fn volt_equivalent(species: &SpeciesEnergy) -> f64 {
+    species.formation_function(1.0)[0]
+}
+
+impl LatimerDiagram {
+    /// Generate the diagram of an element
+    /// Returns None if less than two oxidation states of the element are known
+    pub fn new(element: &Atom) -> Option<LatimerDiagram> {
+        let mut species: Vec<SpeciesEnergy> = vec![];
+
+        for candidate in get_species_energies(element, false) {
+            let state = candidate.oxidation_state();
+
+            // Keep the most stable species of every oxidation state
+            if let Some(position) = species
+                .iter()
+                .position(|x| (x.oxidation_state() - state).abs() < EPSILON)
+            {
+                if volt_equivalent(&candidate) < volt_equivalent(&species[position]) {
+                    species[position] = candidate;
+                }
+            } else {
+                species.push(candidate);
+            }
+        }
+
+        if species.len() < 2 {
+            return None;
+        }
+
+        species.sort_by(|a, b| {
+            b.oxidation_state()
+                .partial_cmp(&a.oxidation_state())
+                .unwrap()
+        });
+
+        Some(LatimerDiagram {
+            element: element.clone(),
+            species,
+        })
+    }
+
+    /// Find a species in the diagram
+    fn find(&self, ion: &Ion) -> Option<&SpeciesEnergy> {
+        self.species.iter().find(|x| &x.species == ion)
+    }
+
+    /// Get the reduction potential between any two species of the diagram
+    /// Non-adjacent steps are combined through their Gibbs energies: E = Σ nE / Σ n
+    pub fn potential(&self, from: &Ion, to: &Ion) -> Option<SEP> {
+        let from = self.find(from)?;
+        let to = self.find(to)?;
+
+        let electrons = from.oxidation_state() - to.oxidation_state();
+        if electrons.abs() < EPSILON {
+            return None;
+        }
+
+        let potential = (volt_equivalent(from) - volt_equivalent(to)) / electrons;
+
+        Some(SEP::from(potential as SEPType))
+    }
+
+    /// Get the steps between adjacent oxidation states
+    pub fn steps(&self) -> Vec<LatimerStep> {
+        self.species
+            .windows(2)
+            .map(|pair| LatimerStep {
+                from: pair[0].species.clone(),
+                to: pair[1].species.clone(),
+                potential: self.potential(&pair[0].species, &pair[1].species).unwrap(),
+            })
+            .collect()
+    }
+
+    /// Get the Frost diagram: the volt-equivalent of every species against its
+    /// oxidation state, from low to high oxidation state
+    pub fn frost_diagram(&self) -> Vec<FrostPoint> {
+        self.species
+            .iter()
+            .rev()
+            .map(|x| FrostPoint {
+                species: x.species.clone(),
+                oxidation_state: x.oxidation_state(),
+                volt_equivalent: volt_equivalent(x),
+            })
+            .collect()
+    }
+
+    /// Check if a species is unstable to disproportionation,
+    /// which is when it lies above the line between a lower and a higher oxidation state
+    /// in the Frost diagram
+    pub fn is_disproportionating(&self, ion: &Ion) -> bool {
+        let species = match self.find(ion) {
+            Some(species) => species,
+            None => return false,
+        };
+
+        let state = species.oxidation_state();
+        let energy = volt_equivalent(species);
+
+        for higher in self.species.iter().filter(|x| x.oxidation_state() > state) {
+            for lower in self.species.iter().filter(|x| x.oxidation_state() < state) {
+                let fraction = (state - lower.oxidation_state())
+                    / (higher.oxidation_state() - lower.oxidation_state());
+                let line = volt_equivalent(lower)
+                    + fraction * (volt_equivalent(higher) - volt_equivalent(lower));
+
+                if energy > line + EPSILON {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Get all species that are unstable to disproportionation
+    pub fn disproportionating(&self) -> Vec<Ion> {
+        self.species
+            .iter()
+            .map(|x| x.species.clone())
+            .filter(|x| self.is_disproportionating(x))
+            .collect()
+    }
+}
+
+impl Properties for LatimerDiagram {
+    fn symbol(&self) -> String {
+        let mut symbol = String::new();
+
+        symbol += &self.species[0].species.symbol();
+
+        for step in self.steps() {
+            symbol += &format!(" ─{:.2}→ ", step.potential);
+            symbol += &step.to.symbol();
+        }
+
+        symbol
+    }
+
+    fn name(&self) -> String {
+        let mut name = String::new();
+
+        name += &self.species[0].species.name();
+
+        for step in self.steps() {
+            name += &format!(" ─{:.2}→ ", step.potential);
+            name += &step.to.name();
+        }
+
+        name
+    }
+
+    fn mass(&self) -> AtomMass {
+        // Diagrams don't have a mass
+        AtomMass::from(0.0)
+    }
+
+    fn is_diatomic(&self) -> bool {
+        // Diagrams can't be diatomic
+        false
+    }
+}
@@ -0,0 +1,296 @@
+use constants::WATER_MOLAR_VOLUME;
+use container::*;
+use gas::{reaction_enthalpy, GasMode};
+use ion::Ion;
+use reaction::ElemReaction;
+use simulation::*;
+use trait_properties::Properties;
+use types::*;
+
+/// Amount of reactions after which re-equilibrating gives up
+const MAX_STEPS: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq)]
+/// A change to a container at equilibrium
+pub enum Disturbance {
+    /// Add elements
+    Add(Vec<ContainerCompound<Ion>>),
+
+    /// Remove elements
+    Remove(Vec<ContainerCompound<Ion>>),
+
+    /// Change the volume [ L ]: of the solution by adding or evaporating water,
+    /// or else of the (then closed) vessel
+    Volume(f64),
+
+    /// Change the pressure of the gas phase, using a piston
+    Pressure(Pressure),
+
+    /// Heat or cool the contents
+    Temperature(Temperature),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The direction an equilibrium shifts in
+pub enum ShiftDirection {
+    /// To the right-hand side
+    Forward,
+
+    /// To the left-hand side
+    Backward,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// How an equilibrium responded to a disturbance
+pub struct EquilibriumShift {
+    /// The equilibrium, as registered (or as it first took place)
+    pub reaction: ElemReaction<Ion>,
+
+    /// The direction it shifted in
+    pub direction: ShiftDirection,
+
+    /// How far it shifted [ mol ]
+    pub extent: MolesType,
+}
+
+#[derive(Debug, Clone)]
+/// The outcome of a Le Chatelier experiment
+pub struct Perturbation {
+    /// What was changed
+    pub disturbance: Disturbance,
+
+    /// The container right after the disturbance, before it re-equilibrated
+    pub disturbed: Container<Ion>,
+
+    /// The reactions that took place to re-equilibrate
+    pub steps: Vec<SimulationStep>,
+
+    /// How every equilibrium shifted
+    pub shifts: Vec<EquilibriumShift>,
+
+    /// The net change of every element while re-equilibrating [ mol ]
+    pub changes: Vec<(Ion, MolesType)>,
+
+    /// Why the equilibria shifted the way they did, to show to the user
+    pub reasoning: Vec<String>,
+}
+
+/// Check if two reactions have the same sides, possibly swapped
+/// Returns the sign of the second one relative to the first one
+fn orientation(reaction: &ElemReaction<Ion>, other: &ElemReaction<Ion>) -> Option<f64> {
+    if reaction.lhs == other.lhs && reaction.rhs == other.rhs {
+        Some(1.0)
+    } else if reaction.lhs == other.rhs && reaction.rhs == other.lhs {
+        Some(-1.0)
+    } else {
+        None
+    }
+}
+
+/// Get the symbols of some elements, as a list
+fn symbols(compounds: &[ContainerCompound<Ion>]) -> String {
+    compounds
+        .iter()
+        .map(|x| x.element.symbol())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+impl Disturbance {
+    /// Explain which direction this disturbance favours
+    pub fn principle(&self, container: &Container<Ion>) -> String {
+        match *self {
+            Disturbance::Add(ref compounds) => format!(
+                "Adding {} favours the reactions that use it up again",
+                symbols(compounds)
+            ),
+
+            Disturbance::Remove(ref compounds) => format!(
+                "Removing {} favours the reactions that form it again",
+                symbols(compounds)
+            ),
+
+            Disturbance::Volume(volume) => {
+                let current = container
+                    .solution_volume()
+                    .unwrap_or_else(|| container.gas_volume());
+
+                if volume > current {
+                    String::from(
+                        "Increasing the volume lowers all concentrations, \
+                         which favours the side with the most particles",
+                    )
+                } else {
+                    String::from(
+                        "Decreasing the volume raises all concentrations, \
+                         which favours the side with the fewest particles",
+                    )
+                }
+            }
+
+            Disturbance::Pressure(pressure) => {
+                if pressure > container.pressure() {
+                    String::from(
+                        "Increasing the pressure favours the side with the fewest gas particles",
+                    )
+                } else {
+                    String::from(
+                        "Decreasing the pressure favours the side with the most gas particles",
+                    )
+                }
+            }
+
+            Disturbance::Temperature(temperature) => {
+                if temperature > container.temperature {
+                    String::from("Heating favours the endothermic reactions, which absorb heat")
+                } else {
+                    String::from("Cooling favours the exothermic reactions, which release heat")
+                }
+            }
+        }
+    }
+}
+
+impl Container<Ion> {
+    /// Let the reactions of the registry take place until equilibrium
+    /// Returns the steps that took place
+    pub fn equilibrate(&mut self, registry: &ReactionRegistry) -> Vec<SimulationStep> {
+        self.simulate(registry, MAX_STEPS)
+    }
+
+    /// Apply a disturbance to this container, without re-equilibrating
+    /// Fails if elements are removed that aren't there, in which case nothing changes
+    pub fn apply_disturbance(
+        &mut self,
+        disturbance: &Disturbance,
+    ) -> Result<(), ReactionError<Ion>> {
+        match *disturbance {
+            Disturbance::Add(ref compounds) => self.add_elements(compounds),
+
+            Disturbance::Remove(ref compounds) => self.remove_elements(compounds)?,
+
+            Disturbance::Volume(volume) => {
+                if let Some(current) = self.solution_volume() {
+                    let water = ContainerCompound {
+                        element: Ion::from_string("H2O").unwrap(),
+                        moles: Moles::from((volume - current).abs() / WATER_MOLAR_VOLUME),
+                    };
+
+                    if volume > current {
+                        self.add_elements(&[water]);
+                    } else {
+                        self.remove_elements(&[water])?;
+                    }
+                } else {
+                    self.gas_mode = GasMode::FixedVolume(volume);
+                }
+            }
+
+            Disturbance::Pressure(pressure) => self.gas_mode = GasMode::FixedPressure(pressure),
+
+            Disturbance::Temperature(temperature) => self.temperature = temperature,
+        }
+
+        Ok(())
+    }
+
+    /// Apply a disturbance to this container at equilibrium, and let it re-equilibrate
+    /// using the reactions of the registry (Le Chatelier's principle)
+    /// Returns how the equilibria shifted and why,
+    /// or fails if elements are removed that aren't there, in which case nothing changes
+    pub fn disturb(
+        &mut self,
+        disturbance: Disturbance,
+        registry: &ReactionRegistry,
+    ) -> Result<Perturbation, ReactionError<Ion>> {
+        let principle = disturbance.principle(self);
+
+        self.apply_disturbance(&disturbance)?;
+        let disturbed = self.clone();
+        let steps = self.equilibrate(registry);
+
+        // Add up the steps of every equilibrium, in the direction it was registered in
+        let mut shifts: Vec<(ElemReaction<Ion>, MolesType)> = vec![];
+
+        for step in &steps {
+            let extent = step.outcome.extent;
+
+            if let Some((shift, sign)) = shifts
+                .iter_mut()
+                .find_map(|x| orientation(&x.0, &step.reaction).map(|sign| (x, sign)))
+            {
+                shift.1 += sign * extent;
+                continue;
+            }
+
+            match registry
+                .reactions
+                .iter()
+                .find_map(|x| orientation(x, &step.reaction).map(|sign| (x, sign)))
+            {
+                Some((registered, sign)) => shifts.push((registered.clone(), sign * extent)),
+                None => shifts.push((step.reaction.clone(), extent)),
+            }
+        }
+
+        let shifts: Vec<EquilibriumShift> = shifts
+            .into_iter()
+            .filter(|x| x.1.abs() > self.contents.epsilon())
+            .map(|(reaction, extent)| EquilibriumShift {
+                reaction,
+                direction: if extent > 0.0 {
+                    ShiftDirection::Forward
+                } else {
+                    ShiftDirection::Backward
+                },
+                extent: extent.abs(),
+            })
+            .collect();
+
+        let mut changes: Vec<(Ion, MolesType)> = vec![];
+        for compound in disturbed.contents.iter().chain(self.contents.iter()) {
+            if changes.iter().any(|x| x.0 == compound.element) {
+                continue;
+            }
+
+            let change =
+                self.moles_of(&compound.element).0 - disturbed.moles_of(&compound.element).0;
+            if change.abs() > self.contents.epsilon() {
+                changes.push((compound.element.clone(), change));
+            }
+        }
+
+        let mut reasoning = vec![principle];
+        if shifts.is_empty() {
+            reasoning.push(String::from("None of the equilibria shifted noticeably"));
+        }
+
+        for shift in &shifts {
+            let (side, reaction) = match shift.direction {
+                ShiftDirection::Forward => ("right", shift.reaction.clone()),
+                ShiftDirection::Backward => ("left", shift.reaction.clone().swap()),
+            };
+
+            reasoning.push(format!(
+                "{} shifted to the {} by {:.3e} mol: {} → {} (ΔH = {:.1} kJ/mol)",
+                shift.reaction.lhs.symbol()
+                    + shift.reaction.reaction_sign()
+                    + &shift.reaction.rhs.symbol(),
+                side,
+                shift.extent,
+                reaction.lhs.symbol(),
+                reaction.rhs.symbol(),
+                reaction_enthalpy(&reaction).0 / 1000.0
+            ));
+        }
+
+        Ok(Perturbation {
+            disturbance,
+            disturbed,
+            steps,
+            shifts,
+            changes,
+            reasoning,
+        })
+    }
+}
@@ -53,39 +53,87 @@ macro_rules! atom_from_string {
     };
 }
 
+mod acid_base;
+mod activation;
 mod atom;
+mod buffer;
+mod cell;
+mod constants;
 mod container;
+mod dissolution;
+mod electrolysis;
 mod electron;
+mod gas;
+mod gibbs;
+mod history;
+mod ice_table;
 mod ion;
+mod kinetics;
+mod latimer;
+mod le_chatelier;
 mod math;
+mod mechanism;
 mod molecule;
 mod namings;
+mod pourbaix;
+mod precipitation;
 mod reaction;
 mod redox;
+mod simulation;
+mod stochastic;
+mod titration;
 mod trait_element;
 mod trait_properties;
 mod trait_reaction;
 mod types;
 
+pub use acid_base::*;
+pub use activation::*;
 pub use atom::*;
+pub use buffer::*;
+pub use cell::*;
+pub use constants::*;
 pub use container::*;
+pub use dissolution::*;
+pub use electrolysis::*;
 pub use electron::*;
+pub use gas::*;
+pub use gibbs::*;
+pub use history::*;
+pub use ice_table::*;
 pub use ion::*;
+pub use kinetics::*;
+pub use latimer::*;
+pub use le_chatelier::*;
 pub use math::*;
+pub use mechanism::*;
 pub use molecule::*;
 pub use namings::*;
+pub use pourbaix::*;
+pub use precipitation::*;
 pub use reaction::*;
 pub use redox::*;
+pub use simulation::*;
+pub use stochastic::*;
+pub use titration::*;
 pub use trait_element::*;
 pub use trait_properties::*;
 pub use trait_reaction::*;
 pub use types::*;
 
+// The atom masses are generated with 5 decimals, more than an f32 holds
+#[allow(clippy::excessive_precision)]
 pub mod data_atoms;
+pub mod data_entropy;
+pub mod data_hydrolysis;
 pub mod data_ions;
+pub mod data_ksp;
 pub mod data_molecules;
+pub mod data_phases;
+pub mod data_pka;
 pub mod data_sef;
 pub mod data_sep;
+pub mod data_solubility;
 
 pub mod display_impls;
 
@@ -279,17 +327,22 @@ fn keep_explicit_charges() {
 }
 
 #[test]
+#[allow(unused_must_use, clippy::useless_vec)]
 fn container_add_and_remove_elements() {
     use data_atoms::*;
     use data_molecules::*;
 
     let mut container = Container {
-        contents: vec![ContainerCompound {
+        contents: Contents::from(vec![ContainerCompound {
             element: WATER.clone(),
             moles: Moles::from(6.0),
-        }],
+        }]),
+
+        available_energy: Energy::from(1e7), // Should be enough
+
+        temperature: Temperature::from(STANDARD_TEMPERATURE),
 
-        available_energy: Energy::from(1e5), // Should be enough
+        gas_mode: GasMode::default(),
     };
 
     let reaction = ElemReaction {
@@ -323,19 +376,19 @@ fn container_add_and_remove_elements() {
     assert_eq!(1, container.contents.len());
 
     // 4 moles water, 2 moles hydrogen, 1 mole oxygen
-    assert!(container.react(&reaction));
+    assert!(container.react(&reaction).is_ok());
     assert_eq!(3, container.contents.len());
 
     // 2 moles water, 4 moles hydrogen, 2 moles oxygen
-    assert!(container.react(&reaction));
+    assert!(container.react(&reaction).is_ok());
     assert_eq!(3, container.contents.len());
 
     // 6 moles hydrogen, 3 moles oxygen
-    assert!(container.react(&reaction));
+    assert!(container.react(&reaction).is_ok());
     assert_eq!(2, container.contents.len());
 
     // No water left, so should fail
-    assert!(!container.react(&reaction));
+    assert!(container.react(&reaction).is_err());
 
     // Remove 6 moles of hydrogen and 3 moles of oxygen (all contents)
     container.remove_elements(&vec![
@@ -354,6 +407,7 @@ fn container_add_and_remove_elements() {
 }
 
 #[test]
+#[allow(unused_must_use)]
 fn check_display() {
     use container::*;
     use data_atoms::*;
@@ -382,8 +436,10 @@ fn check_display() {
     let containercompound = rc_to_cc(reactioncompound.clone());
 
     let container = Container {
-        contents: vec![containercompound.clone()],
+        contents: Contents::from(vec![containercompound.clone()]),
         available_energy: Energy::from(0.0),
+        temperature: Temperature::from(STANDARD_TEMPERATURE),
+        gas_mode: GasMode::default(),
     };
 
     format!("{}", HYDROGEN); // Atom
@@ -406,7 +462,7 @@ fn container_reaction_cost() {
     let oxygen = molecule_from_atom!(OXYGEN);
 
     let mut container = Container {
-        contents: vec![
+        contents: Contents::from(vec![
             ContainerCompound {
                 moles: Moles::from(100.0),
                 element: hydrogen.clone(),
@@ -415,9 +471,13 @@ fn container_reaction_cost() {
                 moles: Moles::from(200.0),
                 element: oxygen.clone(),
             },
-        ],
+        ]),
 
         available_energy: Energy::from(10000.0),
+
+        temperature: Temperature::from(STANDARD_TEMPERATURE),
+
+        gas_mode: GasMode::default(),
     };
 
     let reaction = ElemReaction {
@@ -451,18 +511,34 @@ fn container_reaction_cost() {
     }
 
     assert_eq!(Energy::from(10000.0), container.available_energy);
-    assert!(energy_range!(-600.0, reaction.energy_cost(), -500.0));
+    assert!(energy_range!(
+        -600_000.0,
+        reaction.energy_cost(),
+        -500_000.0
+    ));
 
     // Repeadably try this reaction
 
-    assert!(container.react(&reaction));
-    assert!(energy_range!(10500.0, container.available_energy, 10600.0));
-
-    assert!(container.react(&reaction));
-    assert!(energy_range!(11000.0, container.available_energy, 11200.0));
-
-    assert!(container.react(&reaction));
-    assert!(energy_range!(11500.0, container.available_energy, 11800.0));
+    assert!(container.react(&reaction).is_ok());
+    assert!(energy_range!(
+        510_000.0,
+        container.available_energy,
+        610_000.0
+    ));
+
+    assert!(container.react(&reaction).is_ok());
+    assert!(energy_range!(
+        1_010_000.0,
+        container.available_energy,
+        1_210_000.0
+    ));
+
+    assert!(container.react(&reaction).is_ok());
+    assert!(energy_range!(
+        1_510_000.0,
+        container.available_energy,
+        1_810_000.0
+    ));
 }
 
 #[test]
@@ -521,6 +597,7 @@ fn diatomic_check() {
 }
 
 #[test]
+#[allow(clippy::assertions_on_constants)]
 fn atoms_database_check() {
     use data_atoms::*;
 
@@ -671,7 +748,7 @@ fn reaction_check() {
 
     // Test display
     assert_eq!(
-        "C + O₂ → CO₂    [-1135.930 J]",
+        "C + O₂ → CO₂    [-1135929.993 J]",
         format!("{}", good_reaction)
     );
     assert_eq!("H₂ ⇌ 2H    [0.000 J]", format!("{}", equilibrium_reaction));
@@ -759,3 +836,1719 @@ fn molecule_mass_calculation() {
     assert_eq!(AtomMass::from(342.297), SUGAR.mass());
     assert_eq!(AtomMass::from(44.009), CO2.mass());
 }
+
+#[test]
+fn galvanic_cell_daniell() {
+    let cell = GalvanicCell::from_string("Zn|Zn²⁺||Cu²⁺|Cu").unwrap();
+
+    assert_eq!(
+        cell,
+        GalvanicCell::from_string("Zn|Zn;2+||Cu;2+|Cu").unwrap()
+    );
+    assert_eq!("Zn|Zn²⁺||Cu²⁺|Cu", cell.symbol());
+
+    // 0.337 - -0.7618 = 1.0988 V
+    assert!((cell.emf().unwrap().0 - 1.0988).abs() < 1e-4);
+    assert_eq!(2, cell.electrons());
+    assert!(cell.is_spontaneous());
+
+    // ΔG = -2 * 96485 * 1.0988 ≈ -212 kJ
+    let gibbs = cell.gibbs_energy().unwrap();
+    assert!(Energy::from(-212_100.0) < gibbs);
+    assert!(gibbs < Energy::from(-211_900.0));
+    assert!(cell.equilibrium_constant().unwrap() > 1e37);
+
+    // The cathode is the half-reaction with the highest SEP
+    let swapped = GalvanicCell::from_half_reactions(&cell.cathode, &cell.anode).unwrap();
+    assert_eq!(cell, swapped);
+}
+
+#[test]
+fn galvanic_cell_inert_electrodes() {
+    let cell = GalvanicCell::from_string("Pt|H₂,H⁺||Fe³⁺,Fe²⁺|Pt").unwrap();
+
+    assert_eq!("Pt|H₂,H⁺||Fe³⁺,Fe²⁺|Pt", cell.symbol());
+    assert_eq!(2, cell.electrons());
+    assert!((cell.emf().unwrap().0 - 0.77).abs() < 1e-4);
+
+    // The redox reaction of the cell gets its energy from the electrochemistry
+    assert_eq!(
+        cell.gibbs_energy(),
+        Some(cell.redox_reaction().energy_cost())
+    );
+
+    // Without a known SEP, there is no potential to report
+    let unknown = GalvanicCell {
+        anode: ElemReaction::<Ion>::ion_from_string("Xe;2+ + 2e <> Xe").unwrap(),
+        cathode: cell.cathode.clone(),
+    };
+    assert_eq!(None, unknown.anode_potential());
+    assert_eq!(None, unknown.emf());
+    assert_eq!(None, unknown.gibbs_energy());
+    assert!(!unknown.is_spontaneous());
+}
+
+#[test]
+fn electrolysis_copper_sulfate() {
+    let mut container =
+        Container::<Ion>::ion_from_string("Cu;2+ + SO4;2- + 55H2O [100000 J]").unwrap();
+
+    // 0.2 mol electrons
+    let steps = container
+        .electrolyse(
+            Current::from(2.0),
+            Time::from(0.2 * FARADAY / 2.0),
+            SEP::from(3.0),
+        )
+        .unwrap();
+
+    // Copper is deposited on the cathode, oxygen evolves at the anode
+    assert_eq!(1, steps.len());
+    assert_eq!(
+        ElemReaction::<Ion>::ion_from_string("Cu;2 + 2e <> Cu").unwrap(),
+        steps[0].cell.cathode
+    );
+
+    // 1.229 V + 0.5 V overpotential for oxygen - 0.337 V
+    assert!((steps[0].minimum_voltage.0 - 1.392).abs() < 1e-4);
+
+    assert!((container.moles_of(&ion_from_string!("Cu")).0 - 0.1).abs() < 1e-9);
+    assert!((container.moles_of(&ion_from_string!("O2")).0 - 0.05).abs() < 1e-9);
+    assert!((container.moles_of(&ion_from_string!("Cu;2+")).0 - 0.9).abs() < 1e-9);
+
+    // The electrical work is taken from the available energy,
+    // and what isn't stored in the products heats the solution
+    let work = 0.2 * FARADAY * 3.0;
+    assert!((container.available_energy.0 - (100_000.0 - work)).abs() < 1e-6);
+    assert!(container.temperature > Temperature::from(STANDARD_TEMPERATURE));
+
+    // Just above the minimum voltage, the overpotential still becomes heat
+    let mut container =
+        Container::<Ion>::ion_from_string("Cu;2+ + SO4;2- + 55H2O [100000 J]").unwrap();
+    container
+        .electrolyse(
+            Current::from(2.0),
+            Time::from(0.2 * FARADAY / 2.0),
+            SEP::from(1.4),
+        )
+        .unwrap();
+    assert!(container.temperature > Temperature::from(STANDARD_TEMPERATURE + 0.1));
+
+    // Not enough voltage, so nothing happens
+    let mut container =
+        Container::<Ion>::ion_from_string("Cu;2+ + SO4;2- + 55H2O [100000 J]").unwrap();
+    let steps = container
+        .electrolyse(Current::from(2.0), Time::from(100.0), SEP::from(1.0))
+        .unwrap();
+
+    assert!(steps.is_empty());
+    assert_eq!(
+        Moles::from(0.0),
+        container.moles_of(&ion_from_string!("Cu"))
+    );
+
+    // Neither without energy
+    let mut container = Container::<Ion>::ion_from_string("Cu;2+ + SO4;2- + 55H2O [0 J]").unwrap();
+    let steps = container
+        .electrolyse(Current::from(2.0), Time::from(100.0), SEP::from(3.0))
+        .unwrap();
+
+    assert!(steps.is_empty());
+    assert_eq!(
+        Moles::from(0.0),
+        container.moles_of(&ion_from_string!("Cu"))
+    );
+}
+
+#[test]
+fn electrolysis_chlor_alkali() {
+    let mut brine = Container::<Ion>::ion_from_string("Na;+ + Cl;- + 55H2O [1000000 J]").unwrap();
+
+    // 2 mol electrons, while there is only 1 mol chloride
+    let steps = brine
+        .electrolyse(
+            Current::from(10.0),
+            Time::from(2.0 * FARADAY / 10.0),
+            SEP::from(4.0),
+        )
+        .unwrap();
+
+    // First chlorine, then oxygen at the anode; hydrogen at the cathode
+    assert_eq!(2, steps.len());
+    assert!((steps[0].electrons.0 - 1.0).abs() < 1e-9);
+    assert!((brine.moles_of(&ion_from_string!("Cl2")).0 - 0.5).abs() < 1e-9);
+    assert!((brine.moles_of(&ion_from_string!("H2")).0 - 1.0).abs() < 1e-9);
+    assert!((brine.moles_of(&ion_from_string!("O2")).0 - 0.25).abs() < 1e-9);
+    assert!(brine.moles_of(&ion_from_string!("Cl;-")) < Moles::from(1e-9));
+
+    // Hydroxide that was already there is oxidised at the anode,
+    // but not the hydroxide formed at the cathode
+    let mut lye = Container::<Ion>::ion_from_string("Na;+ + OH;- + 55H2O [2000000 J]").unwrap();
+    lye.electrolyse(
+        Current::from(10.0),
+        Time::from(3.0 * FARADAY / 10.0),
+        SEP::from(4.0),
+    )
+    .unwrap();
+
+    assert!((lye.moles_of(&ion_from_string!("OH;-")).0 - 3.0).abs() < 1e-9);
+}
+
+#[test]
+fn redox_candidates_are_ranked() {
+    let container = Container::<Ion>::ion_from_string("10 Fe + 2 O2 + 20 H2O [0 J]").unwrap();
+    let candidates = get_redox_candidates(&container);
+
+    // Sorted by driving force
+    for pair in candidates.windows(2) {
+        assert!(pair[0].emf >= pair[1].emf);
+    }
+
+    // Only balanced reactions, without mixing acid and base
+    for candidate in &candidates {
+        assert!(candidate.reaction.is_valid());
+        assert_eq!(
+            candidate.reaction.lhs.total_charge(),
+            candidate.reaction.rhs.total_charge()
+        );
+        assert!(candidate.missing.len() <= 1);
+    }
+
+    // The best feasible one oxidises iron to iron(II) using oxygen and water
+    let best = candidates.iter().find(|x| x.is_feasible()).unwrap();
+    assert_eq!(
+        ElemReaction::<Ion>::ion_from_string("2Fe + O2 + 2H2O <> 2Fe;2+ + 4OH;-").unwrap(),
+        best.reaction
+    );
+    assert!((best.emf.0 - 0.84).abs() < 1e-4);
+    assert_eq!(Some(ion_from_string!("O2")), best.limiting);
+    assert_eq!(Moles::from(2.0), best.extent);
+    assert_eq!(Some(best.redox.clone()), get_redox_reaction(&container));
+
+    // In acid it would go faster, but there is no acid
+    let acidic = candidates.iter().find(|x| !x.is_feasible()).unwrap();
+    assert_eq!(vec![ion_from_string!("H;+")], acidic.missing);
+    assert!(acidic.emf > best.emf);
+}
+
+#[test]
+fn redox_reaction_cancels_medium() {
+    // The H⁺ produced by hydrogen is used up by oxygen
+    let container = Container::<Ion>::ion_from_string("2 H2 + O2 [0 J]").unwrap();
+    let redox = get_redox_reaction(&container).unwrap();
+
+    assert_eq!(
+        ElemReaction::<Ion>::ion_from_string("2H2 + O2 <> 2H2O").unwrap(),
+        redox.elem_reaction()
+    );
+}
+
+#[test]
+fn pourbaix_iron() {
+    use data_atoms::IRON;
+
+    let diagram = PourbaixDiagram::new(&IRON, 1e-6).unwrap();
+
+    let species: Vec<String> = diagram.regions.iter().map(|x| x.species.symbol()).collect();
+    assert_eq!(
+        vec!["Fe", "Fe(OH)₂", "Fe(OH)₃", "FeO₄²⁻", "Fe²⁺", "Fe³⁺"],
+        species
+    );
+
+    // Iron dissolves below Fe²⁺ + 2e <> Fe, corrected for the activity
+    let fe2_boundary = diagram
+        .boundaries
+        .iter()
+        .find(|x| x.between == (ion_from_string!("Fe;0"), ion_from_string!("Fe;2+")))
+        .unwrap();
+    assert!((fe2_boundary.from.1 - (-0.44 - 0.0592 / 2.0 * 6.0)).abs() < 1e-3);
+
+    // Iron in neutral water with oxygen rusts
+    let oxygen_line = &diagram.water_lines()[0];
+    let potential = oxygen_line.from.1 + (oxygen_line.to.1 - oxygen_line.from.1) * 0.5;
+    assert_eq!(
+        Some(&ion_from_string!("Fe(OH)3;0")),
+        diagram.predominant_species(7.0, potential)
+    );
+    assert_eq!(
+        Some(&ion_from_string!("Fe;0")),
+        diagram.predominant_species(7.0, -1.0)
+    );
+
+    assert!(diagram.to_csv().starts_with("species,pH,E\nFe,"));
+    assert!(diagram.to_svg().contains("<polygon"));
+}
+
+#[test]
+fn latimer_manganese() {
+    use data_atoms::MANGANESE;
+
+    let diagram = LatimerDiagram::new(&MANGANESE).unwrap();
+
+    let species: Vec<String> = diagram.species.iter().map(|x| x.species.symbol()).collect();
+    assert_eq!(
+        vec!["MnO₄⁻", "HMnO₄⁻", "MnO₂", "Mn³⁺", "Mn²⁺", "Mn"],
+        species
+    );
+
+    // Adjacent steps match the SEPMAP
+    let steps = diagram.steps();
+    assert!((steps[3].potential.0 - 1.51).abs() < 1e-4);
+    assert!((steps[4].potential.0 - -1.185).abs() < 1e-4);
+
+    // MnO₂ -> Mn²⁺ combines 0.95 V (1 e) and 1.51 V (1 e)
+    let potential = diagram
+        .potential(&ion_from_string!("MnO2;0"), &ion_from_string!("Mn;2+"))
+        .unwrap();
+    assert!((potential.0 - 1.23).abs() < 1e-4);
+
+    // Frost diagram: nE of Mn²⁺ is 2 * -1.185
+    let frost = diagram.frost_diagram();
+    assert_eq!(ion_from_string!("Mn;0"), frost[0].species);
+    assert!((frost[1].volt_equivalent - -2.37).abs() < 1e-4);
+
+    assert_eq!(
+        vec![ion_from_string!("HMnO4;-"), ion_from_string!("Mn;3+")],
+        diagram.disproportionating()
+    );
+}
+
+#[test]
+fn acid_base_ph() {
+    // Pure water is neutral
+    let water = Container::<Ion>::ion_from_string("555 H2O [0 J]").unwrap();
+    assert!((water.ph().unwrap() - 7.0).abs() < 1e-6);
+
+    // 0.1 M hydrochloric acid
+    let acid = Container::<Ion>::ion_from_string("HCl + 555 H2O [0 J]").unwrap();
+    assert!((acid.ph().unwrap() - 1.0).abs() < 1e-3);
+    assert!((acid.poh().unwrap() - 13.0).abs() < 1e-3);
+
+    // 0.1 M acetic acid
+    let vinegar = Container::<Ion>::ion_from_string("CH3COOH + 555 H2O [0 J]").unwrap();
+    assert!((vinegar.ph().unwrap() - 2.88).abs() < 1e-2);
+
+    // Without water there is no pH
+    let dry = Container::<Ion>::ion_from_string("HCl [0 J]").unwrap();
+    assert_eq!(None, dry.ph());
+}
+
+#[test]
+fn acid_base_phosphate_speciation() {
+    // Equal amounts of H₂PO₄⁻ and HPO₄²⁻ buffer at pH = pKa2
+    let buffer =
+        Container::<Ion>::ion_from_string("H2PO4;- + HPO4;2- + 3Na;+ + 555 H2O [0 J]").unwrap();
+    let equilibrium = buffer.acid_base_equilibrium().unwrap();
+
+    assert!((equilibrium.ph - 7.2).abs() < 1e-2);
+    assert!((equilibrium.concentration_of(&ion_from_string!("H2PO4;-")) - 0.1).abs() < 1e-3);
+    assert!(equilibrium.concentration_of(&ion_from_string!("PO4;3-")) < 1e-5);
+
+    let phosphate = get_acid_base_systems()
+        .into_iter()
+        .find(|x| x.species[0] == ion_from_string!("H3PO4"))
+        .unwrap();
+    assert_eq!(4, phosphate.species.len());
+}
+
+#[test]
+fn precipitation_with_ksp() {
+    let mut container =
+        Container::<Ion>::ion_from_string("Ag;+ + Cl;- + Na;+ + NO3;- + 555 H2O [0 J]").unwrap();
+    let precipitates = container.precipitate();
+
+    // Only silver chloride precipitates, until [Ag⁺] = [Cl⁻] = √Ksp
+    assert_eq!(1, precipitates.len());
+    assert_eq!(ion_from_string!("AgCl;0"), precipitates[0].salt());
+
+    let dissolved = container.moles_of(&ion_from_string!("Ag;+")).0;
+    let volume = container.solution_volume().unwrap();
+    assert!((dissolved / volume - 10f64.powf(-9.75 / 2.0)).abs() < 1e-8);
+    assert!((precipitates[0].moles.0 + dissolved - 1.0).abs() < 1e-9);
+
+    // The sodium nitrate stays dissolved
+    assert_eq!(
+        Moles::from(1.0),
+        container.moles_of(&ion_from_string!("Na;+"))
+    );
+    assert!(container.get_precipitates().is_empty());
+}
+
+#[test]
+fn precipitation_with_solubility_rules() {
+    assert_eq!(
+        Some(true),
+        is_soluble(&ion_from_string!("K;+"), &ion_from_string!("PO4;3-"))
+    );
+    assert_eq!(
+        Some(false),
+        is_soluble(&ion_from_string!("Pb;2+"), &ion_from_string!("Cl;-"))
+    );
+
+    // Without Ksp data, the rules make lead(II) carbonate precipitate completely
+    let mut container =
+        Container::<Ion>::ion_from_string("2Pb;2+ + CO3;2- + 2NO3;- + 555 H2O [0 J]").unwrap();
+    let precipitates = container.precipitate();
+
+    assert_eq!(1, precipitates.len());
+    assert_eq!(ion_from_string!("PbCO3;0"), precipitates[0].salt());
+    assert_eq!(None, precipitates[0].pksp);
+    assert_eq!(Moles::from(1.0), precipitates[0].moles);
+    assert_eq!(
+        Moles::from(1.0),
+        container.moles_of(&ion_from_string!("Pb;2+"))
+    );
+}
+
+#[test]
+fn titration_acid_base() {
+    // 0.1 M hydrochloric acid with 0.1 M sodium hydroxide
+    let analyte = Container::<Ion>::ion_from_string("HCl + 555 H2O [0 J]").unwrap();
+    let burette = Container::<Ion>::ion_from_string("4Na;+ + 4OH;- + 2220 H2O [0 J]").unwrap();
+    let titration = Titration::acid_base(&analyte, &burette, 250.0, 20000.0).unwrap();
+
+    assert_eq!(81, titration.points.len());
+    assert!((titration.points[0].value - 1.0).abs() < 1e-3);
+    assert!(titration.points[80].value > 12.0);
+
+    let equivalence_points = titration.equivalence_points();
+    assert_eq!(1, equivalence_points.len());
+    // The equivalent amount of titrant is in 555 H₂O of the burette [ mL ]
+    let equivalence_point = 555.0 * WATER_MOLAR_VOLUME * 1000.0;
+    assert!((equivalence_points[0] - equivalence_point).abs() < 1e-3);
+
+    assert!(titration.to_csv().starts_with("volume,pH\n0.0000,0.9999\n"));
+}
+
+#[test]
+fn titration_permanganometry() {
+    let analyte =
+        Container::<Ion>::ion_from_string("5Fe;2+ + 20H;+ + 15SO4;2- + 555 H2O [0 J]").unwrap();
+    let burette = Container::<Ion>::ion_from_string("4K;+ + 4MnO4;- + 2220 H2O [0 J]").unwrap();
+    let redox = RedoxReaction {
+        oxidator: ElemReaction::<Ion>::ion_from_string("MnO4;- + 8H;1 + 5e <> Mn;2 + 4H2O")
+            .unwrap(),
+        reductor: ElemReaction::<Ion>::ion_from_string("Fe;2 <> Fe;3 + e").unwrap(),
+    };
+    let titration = Titration::redox(&analyte, &burette, &redox, 250.0, 20000.0).unwrap();
+
+    // Before any permanganate is added, the potential is undefined
+    assert_eq!(250.0, titration.points[0].volume);
+
+    // Halfway, [Fe³⁺] = [Fe²⁺], so E = E°(Fe³⁺/Fe²⁺)
+    let halfway = titration
+        .points
+        .iter()
+        .find(|x| x.volume == 5000.0)
+        .unwrap();
+    assert!((halfway.value - 0.77).abs() < 1e-3);
+
+    let equivalence_points = titration.equivalence_points();
+    assert_eq!(1, equivalence_points.len());
+    // The equivalent amount of titrant is in 555 H₂O of the burette [ mL ]
+    let equivalence_point = 555.0 * WATER_MOLAR_VOLUME * 1000.0;
+    assert!((equivalence_points[0] - equivalence_point).abs() < 1e-3);
+
+    // All iron(II) is oxidised
+    assert!(titration.analyte.moles_of(&ion_from_string!("Fe;2+")).0 < 1e-9);
+    assert!(titration.to_csv().starts_with("volume,E\n"));
+}
+
+#[test]
+fn buffer_design() {
+    let acid = ion_from_string!("CH3COOH");
+    let recipe = BufferRecipe::design(&acid, 5.0, 0.05, 1.0).unwrap();
+
+    assert_eq!(ion_from_string!("CH3COO;-"), recipe.base);
+    assert!(
+        (henderson_hasselbalch(recipe.pka, &recipe.acid_moles, &recipe.base_moles) - 5.0).abs()
+            < 1e-6
+    );
+
+    // Acetic acid and sodium acetate
+    let grams = recipe.grams();
+    assert_eq!(2, grams.len());
+    assert!((grams[0].1 .0 - 2.054).abs() < 1e-2);
+    assert!((grams[1].1 .0 - 4.877).abs() < 1e-2);
+
+    let buffer = recipe.container();
+    assert!((buffer.ph().unwrap() - 5.0).abs() < 1e-3);
+
+    // The pH hardly changes when adding a strong acid or base
+    let acid = ContainerCompound {
+        element: ion_from_string!("HCl"),
+        moles: Moles::from(0.01),
+    };
+    let base = vec![
+        ContainerCompound {
+            element: ion_from_string!("Na;+"),
+            moles: Moles::from(0.01),
+        },
+        ContainerCompound {
+            element: ion_from_string!("OH;-"),
+            moles: Moles::from(0.01),
+        },
+    ];
+    assert!((buffer.ph_change(&[acid]).unwrap() + 0.191).abs() < 1e-3);
+    assert!((buffer.ph_change(&base).unwrap() - 0.218).abs() < 1e-2);
+
+    // Acetic acid can't buffer at pH 7
+    assert!(BufferRecipe::design(&ion_from_string!("CH3COOH"), 7.0, 0.05, 1.0).is_none());
+}
+
+#[test]
+fn container_volumes() {
+    let hydrochloric_acid = ion_from_string!("HCl");
+    let mut container = Container::<Ion>::ion_from_string("HCl + 555 H2O [0 J]").unwrap();
+
+    assert!((container.solution_volume().unwrap() - 9.998).abs() < 1e-3);
+    assert!((container.concentration_of(&hydrochloric_acid).unwrap() - 0.1).abs() < 1e-4);
+
+    // Diluting only works to larger volumes
+    assert!(!container.dilute(5.0));
+    assert!(container.dilute(20.0));
+    assert!((container.solution_volume().unwrap() - 20.0).abs() < 1e-9);
+    assert!((container.concentration_of(&hydrochloric_acid).unwrap() - 0.05).abs() < 1e-9);
+
+    // An aliquot has the same concentration
+    let aliquot = container.aliquot(5.0).unwrap();
+    assert!((aliquot.solution_volume().unwrap() - 5.0).abs() < 1e-9);
+    assert!((aliquot.concentration_of(&hydrochloric_acid).unwrap() - 0.05).abs() < 1e-9);
+    assert!((container.moles_of(&hydrochloric_acid).0 - 0.75).abs() < 1e-9);
+    assert!(container.aliquot(100.0).is_none());
+
+    container.merge(aliquot);
+    assert!((container.moles_of(&hydrochloric_acid).0 - 1.0).abs() < 1e-9);
+    assert!((container.solution_volume().unwrap() - 20.0).abs() < 1e-9);
+
+    let empty = Container::<Ion>::ion_from_string("HCl [0 J]").unwrap();
+    assert!(empty.concentrations().is_none());
+}
+
+#[test]
+fn gas_phase() {
+    let mut container = Container::<Ion>::ion_from_string("2000 H2 + 1000 O2 [10000 J]").unwrap();
+    let hydrogen = ion_from_string!("H2");
+
+    // At standard conditions, a mole of ideal gas takes up 24.79 L
+    assert!((container.gas_volume() - 3000.0 * 24.7896).abs() < 1.0);
+    assert!((container.mole_fraction(&hydrogen).unwrap() - 2.0 / 3.0).abs() < 1e-9);
+    assert!(
+        (container.partial_pressure(&hydrogen).unwrap().0 - 2.0 / 3.0 * STANDARD_PRESSURE).abs()
+            < 1e-6
+    );
+    assert!(container.mole_fraction(&ion_from_string!("H2O")).is_none());
+
+    // A used up gas no longer adds to the pressure
+    let mut burnt = Container::<Ion>::ion_from_string("2H2 + O2 [0 J]").unwrap();
+    burnt
+        .react(&ElemReaction::<Ion>::ion_from_string("2H2 + O2 > 2H2O").unwrap())
+        .unwrap();
+    assert_eq!(burnt.partial_pressure(&hydrogen), Some(Pressure::from(0.0)));
+
+    container.gas_mode = GasMode::FixedVolume(container.gas_volume());
+    let reaction = ElemReaction::<Ion>::ion_from_string("2H2 + O2 > 2H2O").unwrap();
+    assert!(container.react(&reaction).is_ok());
+
+    // 571.6 kJ heats 1998 H₂, 999 O₂ and 2 H₂O
+    let heat_capacity = 1998.0 * 28.836 + 999.0 * 29.378 + 2.0 * 75.375;
+    let temperature = STANDARD_TEMPERATURE + 571_600.0 / heat_capacity;
+    assert!((container.temperature.0 - temperature).abs() < 1e-6);
+    assert!(
+        (container.pressure().0
+            - STANDARD_PRESSURE * 2997.0 / 3000.0 * temperature / STANDARD_TEMPERATURE)
+            .abs()
+            < 1e-3
+    );
+
+    // Above its boiling point, water is a gas as well
+    let mut steam = Container::<Ion>::ion_from_string("2 H2O [0 J]").unwrap();
+    steam.gas_mode = GasMode::FixedVolume(100.0);
+    assert_eq!(Pressure::from(0.0), steam.pressure());
+
+    steam.temperature = Temperature::from(400.0);
+    assert_eq!(Some(1.0), steam.mole_fraction(&ion_from_string!("H2O")));
+    assert!((steam.pressure().0 - 2.0 * GAS_CONSTANT * 400.0 / 0.1).abs() < 1e-6);
+
+    // Mixing evens out the temperature
+    let mut cold = Container::<Ion>::ion_from_string("1000 H2O [0 J]").unwrap();
+    let mut hot = cold.clone();
+    hot.temperature = Temperature::from(358.15);
+    cold.merge(hot);
+    assert!((cold.temperature.0 - 328.15).abs() < 1e-9);
+}
+
+#[test]
+fn container_react_outcome() {
+    let mut container = Container::<Ion>::ion_from_string("H2 + 2 O2 + 2 H2O [0 J]").unwrap();
+    let before = container.clone();
+
+    // Not enough hydrogen
+    let combustion = ElemReaction::<Ion>::ion_from_string("2H2 + O2 > 2H2O").unwrap();
+    let error = container.react(&combustion).unwrap_err();
+    assert_eq!(
+        ReactionError::NotEnoughElements {
+            shortfalls: vec![ContainerCompound {
+                element: ion_from_string!("H2"),
+                moles: Moles::from(1.0),
+            }],
+        },
+        error
+    );
+    assert_eq!("Not enough elements: 1 mol H₂ short", error.to_string());
+
+    // Splitting water costs energy
+    let electrolysis = ElemReaction::<Ion>::ion_from_string("2H2O > 2H2 + O2").unwrap();
+    match container.react(&electrolysis).unwrap_err() {
+        ReactionError::NotEnoughEnergy {
+            required,
+            shortfall,
+        } => {
+            assert_eq!(required, shortfall);
+            assert!((required.0 - 571_600.0).abs() < 1e-1);
+        }
+        error => panic!("Unexpected error: {}", error),
+    }
+
+    // Nothing changed
+    assert_eq!(before.contents, container.contents);
+    assert_eq!(before.available_energy, container.available_energy);
+    assert_eq!(before.temperature, container.temperature);
+
+    container.available_energy = Energy::from(1e6);
+    let outcome = container.react(&electrolysis).unwrap();
+    assert_eq!(
+        vec![ion_from_string!("H2O")],
+        outcome
+            .consumed
+            .iter()
+            .map(|x| x.element.clone())
+            .collect::<Vec<Ion>>()
+    );
+    assert_eq!(Moles::from(2.0), outcome.consumed[0].moles);
+    assert_eq!(2, outcome.produced.len());
+    assert!((outcome.energy_delta.0 + 571_600.0).abs() < 1e-1);
+    assert_eq!(
+        Moles::from(3.0),
+        container.moles_of(&ion_from_string!("H2"))
+    );
+}
+
+#[test]
+fn container_react_extent() {
+    let mut container = Container::<Ion>::ion_from_string("5 H2 + 2 O2 [0 J]").unwrap();
+    let combustion = ElemReaction::<Ion>::ion_from_string("2H2 + O2 > 2H2O").unwrap();
+
+    let outcome = container.react_extent(&combustion, 0.5).unwrap();
+    assert_eq!(0.5, outcome.extent);
+    assert_eq!(None, outcome.limiting_reagent);
+    assert_eq!(
+        Moles::from(1.0),
+        outcome.theoretical_yield(&ion_from_string!("H2O"))
+    );
+    assert_eq!(
+        Moles::from(4.0),
+        container.moles_of(&ion_from_string!("H2"))
+    );
+
+    // Oxygen runs out first
+    let outcome = container.react_to_completion(&combustion).unwrap();
+    assert_eq!(1.5, outcome.extent);
+    assert_eq!(Some(ion_from_string!("O2")), outcome.limiting_reagent);
+    assert_eq!(
+        Moles::from(3.0),
+        outcome.theoretical_yield(&ion_from_string!("H2O"))
+    );
+    assert_eq!(
+        vec![ContainerCompound {
+            element: ion_from_string!("H2"),
+            moles: Moles::from(1.0),
+        }],
+        outcome.leftover
+    );
+    assert_eq!(1.0, outcome.leftover[0].moles.0);
+    assert_eq!(
+        Moles::from(0.0),
+        container.moles_of(&ion_from_string!("O2"))
+    );
+
+    // Nothing left to react with
+    assert!(container.react_to_completion(&combustion).is_err());
+
+    // Splitting water is limited by the available energy
+    let electrolysis = ElemReaction::<Ion>::ion_from_string("2H2O > 2H2 + O2").unwrap();
+    container.available_energy = Energy::from(electrolysis.energy_cost().0 / 4.0);
+    let outcome = container.react_to_completion(&electrolysis).unwrap();
+    assert!((outcome.extent - 0.25).abs() < 1e-6);
+    assert_eq!(None, outcome.limiting_reagent);
+    assert!(container.available_energy.0.abs() < 1e-9);
+}
+
+#[test]
+fn container_depletion() {
+    let hydrogen = ion_from_string!("H2");
+    let mut container = Container::<Ion>::ion_from_string("H2 + H2 + O2 [0 J]").unwrap();
+
+    // Duplicates are merged
+    assert_eq!(2, container.contents.len());
+    assert_eq!(Moles::from(2.0), container.moles_of(&hydrogen));
+
+    // Removing a third three times leaves no residue
+    for _ in 0..3 {
+        assert!(container
+            .remove_elements(&[ContainerCompound {
+                element: hydrogen.clone(),
+                moles: Moles::from(2.0 / 3.0),
+            }])
+            .is_ok());
+    }
+    assert!(container.contents.get(&hydrogen).is_none());
+    assert_eq!(1, container.contents.len());
+
+    // A larger epsilon removes small amounts
+    container.add_elements(&[ContainerCompound {
+        element: hydrogen.clone(),
+        moles: Moles::from(0.001),
+    }]);
+    assert_eq!(2, container.contents.len());
+    container.contents.set_epsilon(0.01);
+    assert_eq!(
+        vec![ion_from_string!("O2")],
+        container
+            .contents
+            .iter()
+            .map(|x| x.element.clone())
+            .collect::<Vec<Ion>>()
+    );
+    assert!(container.contains(&ContainerCompound {
+        element: ion_from_string!("O2"),
+        moles: Moles::from(1.005),
+    }));
+}
+
+#[test]
+fn container_history() {
+    let container = Container::<Ion>::ion_from_string("4 H2 + 2 O2 [0 J]").unwrap();
+    let combustion = ElemReaction::<Ion>::ion_from_string("2H2 + O2 > 2H2O").unwrap();
+    let water = ion_from_string!("H2O");
+
+    let mut history = History::new(container.clone());
+    assert!(history.react(&combustion).is_ok());
+    assert!(history.react_extent(&combustion, 0.5).is_ok());
+    assert_eq!(2, history.events().len());
+    assert_eq!(0.5, history.events()[1].outcome.extent);
+    assert!((history.events()[0].outcome.heat.0 - 571_600.0).abs() < 1e-1);
+
+    let burnt = history.container().clone();
+
+    // Undoing restores the previous state
+    assert_eq!(Ok(true), history.undo());
+    assert_eq!(Ok(true), history.undo());
+    assert_eq!(Ok(false), history.undo());
+    assert_eq!(
+        Moles::from(4.0),
+        history.container().moles_of(&ion_from_string!("H2"))
+    );
+    assert_eq!(Moles::from(0.0), history.container().moles_of(&water));
+    assert_eq!(container.temperature, history.container().temperature);
+
+    // ... and redoing the state after
+    assert_eq!(Ok(true), history.redo());
+    assert_eq!(Ok(true), history.redo());
+    assert_eq!(Ok(false), history.redo());
+    assert_eq!(burnt.contents, history.container().contents);
+    assert_eq!(burnt.temperature, history.container().temperature);
+
+    // A new reaction makes redoing impossible
+    assert_eq!(Ok(true), history.undo());
+    assert!(history.react_to_completion(&combustion).is_ok());
+    assert_eq!(Ok(false), history.redo());
+    assert_eq!(Moles::from(4.0), history.container().moles_of(&water));
+
+    // Replaying gives the same result
+    let mut replayed = container.clone();
+    assert!(history.replay(&mut replayed).is_ok());
+    assert_eq!(
+        history.container().moles_of(&water),
+        replayed.moles_of(&water)
+    );
+    assert_eq!(history.container().temperature, replayed.temperature);
+
+    let mut empty = Container::<Ion>::ion_from_string("O2 [0 J]").unwrap();
+    assert!(history.replay(&mut empty).is_err());
+
+    assert_eq!(
+        "step,H₂,O₂,H₂O\n\
+         0,4.0000,2.0000,0.0000\n\
+         1,2.0000,1.0000,2.0000\n\
+         2,0.0000,0.0000,4.0000\n",
+        history.to_csv().unwrap()
+    );
+
+    // Changes that aren't logged can make undoing and recreating the states impossible
+    history
+        .container_mut()
+        .merge(Container::<Ion>::ion_from_string("2 H2 + O2 [0 J]").unwrap());
+    history.react(&combustion).unwrap();
+    assert!(matches!(
+        history.states(),
+        Err(ReactionError::NotEnoughElements { .. })
+    ));
+
+    history
+        .container_mut()
+        .remove_elements(&[ContainerCompound {
+            element: water.clone(),
+            moles: Moles::from(6.0),
+        }])
+        .unwrap();
+    assert!(history.undo().is_err());
+    assert_eq!(3, history.events().len());
+
+    // Replaying has to overcome the activation barrier again
+    let explosion = ActivatedReaction::new(combustion, Activation::new(Energy::from(150_000.0)));
+    let mut sparked =
+        History::new(Container::<Ion>::ion_from_string("2 H2 + O2 [150000 J]").unwrap());
+    assert!(sparked.react(&explosion).is_ok());
+
+    let mut unsparked = Container::<Ion>::ion_from_string("2 H2 + O2 [0 J]").unwrap();
+    assert_eq!(
+        Err(ReactionError::NotActivated {
+            barrier: Energy::from(150_000.0)
+        }),
+        sparked.replay(&mut unsparked)
+    );
+}
+
+#[test]
+fn container_simulation() {
+    let registry = ReactionRegistry::new();
+    let protons = ion_from_string!("H;1");
+
+    // A strong acid neutralises a strong base
+    let mut container =
+        Container::<Ion>::ion_from_string("HCl + OH;- + Na;+ + 555 H2O [0 J]").unwrap();
+    let steps = container.simulate(&registry, 100);
+    assert!(steps.len() < 100);
+    assert!(steps.iter().all(|x| x.kind == ReactionKind::AcidBase));
+    assert!((-container.concentration_of(&protons).unwrap().log10() - 7.0).abs() < 1e-3);
+
+    // Nothing happens in equilibrium
+    assert!(container.simulate(&registry, 100).is_empty());
+
+    // The same pH as the acid-base equilibrium
+    let mut vinegar = Container::<Ion>::ion_from_string("CH3COOH + 555 H2O [0 J]").unwrap();
+    let ph = vinegar.ph().unwrap();
+    vinegar.simulate(&registry, 100);
+    assert!((-vinegar.concentration_of(&protons).unwrap().log10() - ph).abs() < 1e-3);
+
+    // Silver chloride precipitates
+    let mut container =
+        Container::<Ion>::ion_from_string("Ag;+ + Cl;- + Na;+ + NO3;- + 555 H2O [0 J]").unwrap();
+    let steps = container.simulate(&registry, 100);
+    assert!(steps.len() < 100);
+    assert!(steps
+        .iter()
+        .any(|x| x.kind == ReactionKind::Precipitation && x.outcome.extent > 0.99));
+    assert!((container.moles_of(&ion_from_string!("AgCl;0")).0 - 1.0).abs() < 1e-3);
+
+    // Zinc reduces copper ions
+    let mut container = Container::<Ion>::ion_from_string("Zn + Cu;2+ [0 J]").unwrap();
+    let candidates = container.get_simulation_candidates(&registry);
+    assert!(candidates
+        .windows(2)
+        .all(|x| x[0].driving_force <= x[1].driving_force));
+
+    let steps = container.simulate(&registry, 100);
+    assert_eq!(1, steps.len());
+    assert_eq!(ReactionKind::Redox, steps[0].kind);
+    assert_eq!(
+        Moles::from(1.0),
+        container.moles_of(&ion_from_string!("Cu"))
+    );
+    assert!(steps[0].outcome.heat > Energy::from(0.0));
+
+    // Only the registered reactions
+    let mut registry = ReactionRegistry {
+        redox: false,
+        acid_base: false,
+        precipitation: false,
+        reactions: vec![],
+    };
+    registry.register(ElemReaction::<Ion>::ion_from_string("2H2 + O2 > 2H2O").unwrap());
+
+    // The water takes up the heat, so the reaction goes to completion
+    let mut container = Container::<Ion>::ion_from_string("2 H2 + 2 O2 + 1000 H2O [0 J]").unwrap();
+    let steps = container.simulate(&registry, 100);
+    assert_eq!(1, steps.len());
+    assert_eq!(
+        Some(ion_from_string!("H2")),
+        steps[0].outcome.limiting_reagent
+    );
+    assert!((container.moles_of(&ion_from_string!("H2O")).0 - 1002.0).abs() < 1e-9);
+
+    // Without it, the heat shifts the equilibrium back before all hydrogen is burnt
+    let mut container = Container::<Ion>::ion_from_string("2 H2 + 2 O2 [0 J]").unwrap();
+    container.simulate(&registry, 100);
+    assert!(container.moles_of(&ion_from_string!("H2")) > Moles::from(0.0));
+    assert!(container.temperature > Temperature::from(1000.0));
+
+    // Reactions without entropy data are left out
+    registry.reactions = vec![ElemReaction::<Ion>::ion_from_string("Xe + F2 > XeF2").unwrap()];
+    let container = Container::<Ion>::ion_from_string("Xe + F2 [0 J]").unwrap();
+    assert!(container.get_simulation_candidates(&registry).is_empty());
+
+    // Water ionises itself, with a finite driving force
+    let mut registry = ReactionRegistry::new();
+    registry.redox = false;
+
+    let container = Container::<Ion>::ion_from_string("Na;+ + NO3;- + 555 H2O [0 J]").unwrap();
+    let candidates = container.get_simulation_candidates(&registry);
+    assert_eq!(1, candidates.len());
+    assert!(candidates[0].reaction.is_valid());
+    assert!((candidates[0].driving_force.0 - 79_912.0).abs() < 1.0);
+
+    // Neutralising conserves atoms, so only one water is formed
+    let mut container = Container::<Ion>::ion_from_string("H;+ + OH;- + 555 H2O [0 J]").unwrap();
+    container.simulate(&registry, 100);
+
+    let water = container.moles_of(&ion_from_string!("H2O")).0;
+    let protons = container.moles_of(&ion_from_string!("H;+")).0;
+    let hydroxide = container.moles_of(&ion_from_string!("OH;-")).0;
+    assert!((water + protons - 556.0).abs() < 1e-9);
+    assert!((water + hydroxide - 556.0).abs() < 1e-9);
+    assert!(protons < 1e-5);
+}
+
+#[test]
+fn container_kinetics() {
+    let n2o5 = ion_from_string!("N2O5");
+    let water_volume = 555.0 * WATER_MOLAR_VOLUME;
+
+    // First order decay, of which the half-life doesn't depend on the concentration
+    let decay = [KineticReaction::new(
+        ElemReaction::<Ion>::ion_from_string("2N2O5 > 4NO2;0 + O2").unwrap(),
+        Arrhenius::constant(0.05),
+    )
+    .with_order(&n2o5, 1.0)];
+
+    for &integrator in &[Integrator::RungeKutta, Integrator::Stiff] {
+        let mut container = Container::<Ion>::ion_from_string("2 N2O5 + 555 H2O [0 J]").unwrap();
+        let trajectory = container
+            .integrate(&decay, Time::from(20.0), integrator)
+            .unwrap();
+
+        let half_life = trajectory.half_life(&n2o5).unwrap();
+        assert!((half_life.0 - 2.0_f64.ln() / 0.1).abs() < 1e-2);
+
+        // Every point follows the analytic decay c₀ · e^(-2kt)
+        let concentrations = trajectory.concentrations_of(&n2o5).unwrap();
+        let start = 2.0 / water_volume;
+        assert!(concentrations.len() > 2);
+        for (time, concentration) in concentrations {
+            let analytic = start * (-0.1 * time.0).exp();
+            assert!((concentration - analytic).abs() < 1e-5 * start);
+        }
+
+        let expected = 2.0 * (-0.1 * 20.0_f64).exp();
+        assert!((container.moles_of(&n2o5).0 - expected).abs() < 1e-5);
+        assert!(
+            (container.moles_of(&ion_from_string!("O2")).0 - (2.0 - expected) / 2.0).abs() < 1e-5
+        );
+        assert_eq!(Time::from(20.0), trajectory.points.last().unwrap().time);
+    }
+
+    // Consecutive reactions A → B → C
+    let methanol = ion_from_string!("CH4O");
+    let formaldehyde = ion_from_string!("CH2O");
+    let reactions = [
+        KineticReaction::new(
+            ElemReaction::<Ion>::ion_from_string("CH4O > CH2O + H2").unwrap(),
+            Arrhenius::constant(1.0),
+        ),
+        KineticReaction::new(
+            ElemReaction::<Ion>::ion_from_string("CH2O > CO + H2").unwrap(),
+            Arrhenius::constant(0.5),
+        ),
+    ];
+
+    let mut container = Container::<Ion>::ion_from_string("CH4O + 555 H2O [0 J]").unwrap();
+    let trajectory = container
+        .integrate(&reactions, Time::from(4.0), Integrator::RungeKutta)
+        .unwrap();
+
+    let start = 1.0 / water_volume;
+    let expected = start * 1.0 / (0.5 - 1.0) * ((-4.0_f64).exp() - (-2.0_f64).exp());
+    assert!((trajectory.final_concentration(&formaldehyde).unwrap() - expected).abs() < 1e-6);
+
+    let total = trajectory.final_concentration(&methanol).unwrap()
+        + trajectory.final_concentration(&formaldehyde).unwrap()
+        + trajectory
+            .final_concentration(&ion_from_string!("CO"))
+            .unwrap();
+    assert!((total - start).abs() < 1e-9);
+
+    // A fast reversible reaction reaches equilibrium, even when it's stiff
+    let n2o4 = ion_from_string!("N2O4");
+    let no2 = ion_from_string!("NO2");
+    let equilibrium = KineticReaction::new(
+        ElemReaction::<Ion>::ion_from_string("N2O4 <> 2NO2").unwrap(),
+        Arrhenius::constant(1e4),
+    )
+    .with_reverse(Arrhenius::constant(2e5));
+
+    let mut container = Container::<Ion>::ion_from_string("N2O4 + 555 H2O [0 J]").unwrap();
+    let trajectory = container
+        .integrate(&[equilibrium], Time::from(1000.0), Integrator::Stiff)
+        .unwrap();
+
+    let ratio = trajectory.final_concentration(&no2).unwrap().powi(2)
+        / trajectory.final_concentration(&n2o4).unwrap();
+    assert!((ratio - 0.05).abs() < 1e-6);
+    assert!(trajectory.points.len() < 10_000);
+
+    // The rate constant grows with the temperature
+    let arrhenius = Arrhenius::new(1e10, Energy::from(50_000.0));
+    let ratio = arrhenius.rate_constant(Temperature::from(310.0))
+        / arrhenius.rate_constant(Temperature::from(300.0));
+    let expected = (50_000.0 / GAS_CONSTANT * (1.0 / 300.0 - 1.0 / 310.0)).exp();
+    assert!((ratio - expected).abs() < 1e-9);
+
+    // Reactions need a volume to take place in
+    let mut container = Container::<Ion>::ion_from_string("N2O5 [0 J]").unwrap();
+    assert!(container
+        .integrate(&decay, Time::from(1.0), Integrator::RungeKutta)
+        .is_none());
+}
+
+#[test]
+fn container_gillespie() {
+    let methanol = ion_from_string!("CH4O");
+    let decay = [KineticReaction::new(
+        ElemReaction::<Ion>::ion_from_string("CH4O > CH2O + H2").unwrap(),
+        Arrhenius::constant(0.1),
+    )];
+
+    let container = Container::<Ion>::ion_from_string("100 CH4O [0 J]").unwrap();
+    let mut settings = StochasticSettings::new(1e-18, 42);
+    settings.runs = 500;
+    settings.samples = 10;
+
+    let ensemble = container.gillespie(&decay, Time::from(20.0), &settings);
+    assert_eq!(500, ensemble.runs.len());
+    assert_eq!(11, ensemble.runs[0].len());
+
+    // Every molecule decays independently, so the count is binomially distributed
+    let (time, mean) = ensemble.mean_of(&methanol).unwrap()[5];
+    let (_, variance) = ensemble.variance_of(&methanol).unwrap()[5];
+    let survival = (-0.1 * time.0).exp();
+
+    assert_eq!(Time::from(10.0), time);
+    assert!((mean - 100.0 * survival).abs() < 1.5);
+    assert!((variance - 100.0 * survival * (1.0 - survival)).abs() < 6.0);
+
+    // The same seed gives the same runs
+    assert_eq!(
+        ensemble,
+        container.gillespie(&decay, Time::from(20.0), &settings)
+    );
+
+    settings.seed = 43;
+    assert_ne!(
+        ensemble,
+        container.gillespie(&decay, Time::from(20.0), &settings)
+    );
+
+    // On average, it agrees with the deterministic rate law at every sample
+    settings.seed = 42;
+    let ensemble = container.gillespie(&decay, Time::from(20.0), &settings);
+
+    for (time, mean) in ensemble.mean_of(&methanol).unwrap() {
+        let mut deterministic =
+            Container::<Ion>::ion_from_string("100 CH4O + 555 H2O [0 J]").unwrap();
+        if time > Time::from(0.0) {
+            deterministic
+                .integrate(&decay, time, Integrator::RungeKutta)
+                .unwrap();
+        }
+
+        assert!((mean - deterministic.moles_of(&methanol).0).abs() < 1.0);
+    }
+
+    // Two molecules are needed to dimerise, so one is always left over
+    let no2 = ion_from_string!("NO2;0");
+    let dimerisation = [KineticReaction::new(
+        ElemReaction::<Ion>::ion_from_string("2NO2;0 > N2O4;0").unwrap(),
+        Arrhenius::constant(1e9),
+    )];
+
+    let container = Container::<Ion>::ion_from_string("3 NO2;0 [0 J]").unwrap();
+    let ensemble = container.gillespie(&dimerisation, Time::from(1.0), &settings);
+
+    for run in &ensemble.runs {
+        assert_eq!(vec![1, 1], run.last().unwrap().counts);
+    }
+    assert_eq!(
+        Some(0.0),
+        ensemble.variance_of(&no2).unwrap().last().map(|x| x.1)
+    );
+    assert!(ensemble
+        .to_csv()
+        .starts_with("time,NO₂ mean,NO₂ variance,N₂O₄ mean"));
+}
+
+#[test]
+fn mechanism() {
+    let no = ion_from_string!("NO;0");
+    let o2 = ion_from_string!("O2");
+    let no2 = ion_from_string!("NO2;0");
+    let n2o2 = ion_from_string!("N2O2;0");
+
+    // A fast pre-equilibrium, followed by a slow step
+    let mechanism = Mechanism::new(vec![
+        KineticReaction::new(
+            ElemReaction::<Ion>::ion_from_string("2NO;0 <> N2O2;0").unwrap(),
+            Arrhenius::constant(1e3),
+        )
+        .with_reverse(Arrhenius::constant(1e5)),
+        KineticReaction::new(
+            ElemReaction::<Ion>::ion_from_string("N2O2;0 + O2 > 2NO2;0").unwrap(),
+            Arrhenius::constant(10.0),
+        ),
+    ]);
+
+    let overall = ElemReaction::<Ion>::ion_from_string("2NO;0 + O2 > 2NO2;0").unwrap();
+    assert!(mechanism.adds_up_to(&overall));
+    assert!(
+        !mechanism.adds_up_to(&ElemReaction::<Ion>::ion_from_string("NO;0 + O2 > NO2;0").unwrap())
+    );
+    assert_eq!(overall, mechanism.overall_reaction());
+    assert_eq!(vec![n2o2.clone()], mechanism.intermediates());
+    assert!(mechanism.catalysts().is_empty());
+
+    let temperature = Temperature::from(298.15);
+    assert_eq!(Some(1), mechanism.rate_determining_step(temperature));
+
+    // rate = k₂ · K₁ · [NO]² · [O₂]
+    let rate_law = mechanism.rate_determining_rate_law(1).unwrap();
+    assert!((rate_law.forward.rate_constant(temperature) - 0.1).abs() < 1e-12);
+    assert_eq!(2, rate_law.forward_orders.len());
+    assert!(rate_law.forward_orders.contains(&(no.clone(), 2.0)));
+    assert!(rate_law.forward_orders.contains(&(o2.clone(), 1.0)));
+
+    // An intermediate formed together with a product makes it inhibit the reaction:
+    // rate = k₂ · K₁ · [O₃]² / [O₂], with Ea = Ea₂ + Ea₁ - Ea₋₁
+    let decomposition = Mechanism::new(vec![
+        KineticReaction::new(
+            ElemReaction::<Ion>::ion_from_string("O3 <> O2 + O;0").unwrap(),
+            Arrhenius::new(1e3, Energy::from(50_000.0)),
+        )
+        .with_reverse(Arrhenius::new(1e6, Energy::from(10_000.0))),
+        KineticReaction::new(
+            ElemReaction::<Ion>::ion_from_string("O;0 + O3 > 2O2").unwrap(),
+            Arrhenius::new(10.0, Energy::from(20_000.0)),
+        ),
+    ]);
+
+    let ozone_law = decomposition.rate_determining_rate_law(1).unwrap();
+    assert_eq!(
+        ElemReaction::<Ion>::ion_from_string("2O3 > 3O2").unwrap(),
+        ozone_law.reaction
+    );
+    assert!((ozone_law.forward.pre_exponential - 1e-2).abs() < 1e-15);
+    assert_eq!(Energy::from(60_000.0), ozone_law.forward.activation_energy);
+    assert_eq!(2, ozone_law.forward_orders.len());
+    assert!(ozone_law
+        .forward_orders
+        .contains(&(ion_from_string!("O3"), 2.0)));
+    assert!(ozone_law.forward_orders.contains(&(o2.clone(), -1.0)));
+
+    // Without a pre-equilibrium, the intermediate can't be eliminated
+    let mut irreversible = mechanism.clone();
+    irreversible.steps[0].reverse = None;
+    assert!(irreversible.rate_determining_rate_law(1).is_none());
+
+    // The steady-state approximation: [N₂O₂] = k₁[NO]² / (k₋₁ + k₂[O₂])
+    let concentration = |x: &Ion| if *x == no { 0.2 } else { 0.1 };
+    let (rate, intermediates) = mechanism
+        .steady_state_rate(concentration, temperature)
+        .unwrap();
+    let steady_state = 1e3 * 0.2 * 0.2 / (1e5 + 10.0 * 0.1);
+
+    assert_eq!(n2o2, intermediates[0].0);
+    assert!((intermediates[0].1 - steady_state).abs() < 1e-15);
+    assert!((rate - 10.0 * 0.1 * steady_state).abs() < 1e-12);
+
+    // Both approximations agree with integrating the steps, as the second one is slow
+    let volume = 555.0 * WATER_MOLAR_VOLUME;
+    let mut stepwise = Container::<Ion>::ion_from_string("2 NO;0 + O2 + 555 H2O [0 J]").unwrap();
+    let mut approximated = stepwise.clone();
+
+    stepwise
+        .integrate(&mechanism.steps, Time::from(100.0), Integrator::Stiff)
+        .unwrap();
+    approximated
+        .integrate(&[rate_law], Time::from(100.0), Integrator::RungeKutta)
+        .unwrap();
+
+    let formed = approximated.moles_of(&no2).0 / volume;
+    assert!(formed > 0.01);
+    assert!((stepwise.moles_of(&no2).0 / volume - formed).abs() < formed * 1e-2);
+
+    // Chlorine radicals are regenerated, so they catalyse the breakdown of ozone
+    let ozone = Mechanism::new(vec![
+        KineticReaction::new(
+            ElemReaction::<Ion>::ion_from_string("Cl;0 + O3 > ClO;0 + O2").unwrap(),
+            Arrhenius::new(2.3e10, Energy::from(1_600.0)),
+        ),
+        KineticReaction::new(
+            ElemReaction::<Ion>::ion_from_string("ClO;0 + O;0 > Cl;0 + O2").unwrap(),
+            Arrhenius::constant(2.3e10),
+        ),
+    ]);
+
+    assert!(ozone.adds_up_to(&ElemReaction::<Ion>::ion_from_string("O3 + O;0 > 2O2").unwrap()));
+    assert_eq!(vec![ion_from_string!("Cl;0")], ozone.catalysts());
+    assert_eq!(vec![ion_from_string!("ClO;0")], ozone.intermediates());
+    assert_eq!(Some(0), ozone.rate_determining_step(temperature));
+}
+
+#[test]
+fn container_activation() {
+    let combustion = ElemReaction::<Ion>::ion_from_string("2H2 + O2 > 2H2O").unwrap();
+    let platinum = ion_from_string!("Pt");
+    let water = ion_from_string!("H2O");
+
+    let reaction = ActivatedReaction::new(
+        combustion.clone(),
+        Activation::new(Energy::from(150_000.0))
+            .with_catalyst(platinum.clone(), Energy::from(40_000.0))
+            .with_inhibitor(ion_from_string!("CO"), Energy::from(50_000.0)),
+    );
+
+    // Without a spark, nothing happens
+    let mut container = Container::<Ion>::ion_from_string("2 H2 + O2 [0 J]").unwrap();
+    assert_eq!(
+        Err(ReactionError::NotActivated {
+            barrier: Energy::from(150_000.0)
+        }),
+        container.react(&reaction)
+    );
+    assert_eq!(Moles::from(0.0), container.moles_of(&water));
+
+    // ... which an ordinary reaction doesn't need
+    assert!(container.clone().react(&combustion).is_ok());
+
+    // A spark supplies the barrier, which isn't used up
+    let mut sparked = Container::<Ion>::ion_from_string("2 H2 + O2 [150000 J]").unwrap();
+    let outcome = sparked.react(&reaction).unwrap();
+    assert_eq!(Moles::from(2.0), sparked.moles_of(&water));
+    assert!(outcome.energy_delta > Energy::from(0.0));
+    assert!(outcome.heat > Energy::from(0.0));
+
+    // So does heating it enough
+    let mut heated = container.clone();
+    heated.temperature = Temperature::from(1000.0);
+    assert!(heated.react(&reaction).is_ok());
+
+    // A catalyst lowers the barrier, and is still there afterwards
+    let mut catalysed = Container::<Ion>::ion_from_string("2 H2 + O2 + Pt [0 J]").unwrap();
+    assert_eq!(
+        Energy::from(40_000.0),
+        reaction.activation.barrier(&catalysed)
+    );
+    assert!(catalysed.react(&reaction).is_ok());
+    assert_eq!(Moles::from(1.0), catalysed.moles_of(&platinum));
+
+    // ... unless it is poisoned
+    let mut poisoned = Container::<Ion>::ion_from_string("2 H2 + O2 + Pt + CO [0 J]").unwrap();
+    assert_eq!(
+        Energy::from(90_000.0),
+        reaction.activation.barrier(&poisoned)
+    );
+    assert!(poisoned.react_to_completion(&reaction).is_err());
+
+    // Only catalysts that are present count, and the best of them is used,
+    // while every inhibitor present adds to the barrier, with or without a catalyst
+    let activation = Activation::new(Energy::from(150_000.0))
+        .with_catalyst(platinum.clone(), Energy::from(40_000.0))
+        .with_catalyst(ion_from_string!("Ag"), Energy::from(60_000.0))
+        .with_catalyst(ion_from_string!("Ni"), Energy::from(200_000.0))
+        .with_inhibitor(ion_from_string!("CO"), Energy::from(50_000.0))
+        .with_inhibitor(ion_from_string!("H2S"), Energy::from(30_000.0));
+    let barrier =
+        |contents: &str| activation.barrier(&Container::<Ion>::ion_from_string(contents).unwrap());
+
+    assert_eq!(Energy::from(150_000.0), barrier("2 H2 + O2 [0 J]"));
+    assert_eq!(Energy::from(150_000.0), barrier("2 H2 + O2 + Ni [0 J]"));
+    assert_eq!(Energy::from(60_000.0), barrier("2 H2 + O2 + Ag + Ni [0 J]"));
+    assert_eq!(Energy::from(40_000.0), barrier("2 H2 + O2 + Ag + Pt [0 J]"));
+    assert_eq!(
+        Energy::from(140_000.0),
+        barrier("2 H2 + O2 + Ag + CO + H2S [0 J]")
+    );
+    assert_eq!(Energy::from(180_000.0), barrier("2 H2 + O2 + H2S [0 J]"));
+}
+
+#[test]
+fn container_le_chatelier() {
+    let haber = ElemReaction::<Ion>::ion_from_string("N2 + 3H2 <> 2NH3").unwrap();
+
+    let mut registry = ReactionRegistry::new();
+    registry.redox = false;
+    registry.acid_base = false;
+    registry.precipitation = false;
+    registry.register(haber.clone());
+
+    let mut container = Container::<Ion>::ion_from_string("N2 + 3 H2 [200000 J]").unwrap();
+    assert!(!container.equilibrate(&registry).is_empty());
+    assert!(container.equilibrate(&registry).is_empty());
+
+    let direction = |container: &mut Container<Ion>, disturbance| {
+        let perturbation = container.disturb(disturbance, &registry).unwrap();
+
+        assert_eq!(1, perturbation.shifts.len());
+        assert_eq!(haber, perturbation.shifts[0].reaction);
+        assert_eq!(2, perturbation.reasoning.len());
+
+        perturbation.shifts[0].direction
+    };
+
+    // Ammonia has the fewest gas particles, and is formed exothermically
+    assert_eq!(
+        ShiftDirection::Forward,
+        direction(&mut container, Disturbance::Pressure(Pressure::from(1e6)))
+    );
+    assert_eq!(
+        ShiftDirection::Backward,
+        direction(
+            &mut container,
+            Disturbance::Temperature(Temperature::from(700.0))
+        )
+    );
+    assert_eq!(
+        ShiftDirection::Forward,
+        direction(
+            &mut container,
+            Disturbance::Temperature(Temperature::from(STANDARD_TEMPERATURE))
+        )
+    );
+    assert_eq!(
+        ShiftDirection::Forward,
+        direction(
+            &mut container,
+            Disturbance::Add(vec![
+                ContainerCompound::<Ion>::ion_from_string("N2").unwrap()
+            ])
+        )
+    );
+
+    let before = container.moles_of(&ion_from_string!("NH3"));
+    let perturbation = container
+        .disturb(
+            Disturbance::Remove(vec![ContainerCompound {
+                element: ion_from_string!("NH3"),
+                moles: before.clone() / 2.0,
+            }]),
+            &registry,
+        )
+        .unwrap();
+
+    assert_eq!(ShiftDirection::Forward, perturbation.shifts[0].direction);
+    assert_eq!(
+        before / 2.0,
+        perturbation.disturbed.moles_of(&ion_from_string!("NH3"))
+    );
+    assert!(perturbation
+        .changes
+        .iter()
+        .any(|x| x.0 == ion_from_string!("NH3") && x.1 > 0.0));
+    assert!(perturbation.reasoning[0].starts_with("Removing NH₃"));
+
+    // Expanding the closed vessel lowers the pressure
+    let volume = container.gas_volume();
+    assert_eq!(
+        ShiftDirection::Backward,
+        direction(&mut container, Disturbance::Volume(volume * 10.0))
+    );
+
+    // Each disturbance undone shifts it back the other way
+    assert_eq!(
+        ShiftDirection::Forward,
+        direction(&mut container, Disturbance::Volume(volume))
+    );
+    assert_eq!(
+        ShiftDirection::Backward,
+        direction(&mut container, Disturbance::Pressure(Pressure::from(1e4)))
+    );
+    assert_eq!(
+        ShiftDirection::Backward,
+        direction(
+            &mut container,
+            Disturbance::Add(vec![
+                ContainerCompound::<Ion>::ion_from_string("NH3").unwrap()
+            ])
+        )
+    );
+
+    let hydrogen = container.moles_of(&ion_from_string!("H2"));
+    assert_eq!(
+        ShiftDirection::Backward,
+        direction(
+            &mut container,
+            Disturbance::Remove(vec![ContainerCompound {
+                element: ion_from_string!("H2"),
+                moles: hydrogen / 2.0,
+            }])
+        )
+    );
+
+    // Diluting a weak acid lets more of it dissociate
+    let mut registry = ReactionRegistry::new();
+    registry.redox = false;
+    registry.precipitation = false;
+
+    let mut solution = Container::<Ion>::ion_from_string("CH3COOH + 555 H2O [0 J]").unwrap();
+    solution.equilibrate(&registry);
+    let volume = solution.solution_volume().unwrap();
+    let perturbation = solution
+        .disturb(Disturbance::Volume(volume * 10.0), &registry)
+        .unwrap();
+
+    assert!(perturbation
+        .changes
+        .iter()
+        .any(|x| x.0 == ion_from_string!("CH3COO;-") && x.1 > 0.0));
+
+    // Nothing is removed if it isn't there
+    let removed = container.disturb(
+        Disturbance::Remove(vec![
+            ContainerCompound::<Ion>::ion_from_string("O2").unwrap()
+        ]),
+        &registry,
+    );
+    assert!(removed.is_err());
+}
+
+#[test]
+fn container_ice_table() {
+    let mut roots = solve_polynomial(&[24.0, -50.0, 35.0, -10.0, 1.0]).unwrap();
+    roots.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    for (root, expected) in roots.iter().zip([1.0, 2.0, 3.0, 4.0].iter()) {
+        assert!((root - expected).abs() < 1e-9);
+    }
+
+    let k_from_table = |table: &IceTable<Ion>| -> f64 {
+        table
+            .columns
+            .iter()
+            .fold(1.0, |k, x| k * x.at(table.extent).powi(x.coefficient))
+    };
+
+    // A weak acid, in 0.1 mol/L
+    let acid = ElemReaction::<Ion>::ion_from_string("CH3COOH <> CH3COO;- + H;+").unwrap();
+    let solution = Container::<Ion>::ion_from_string("CH3COOH + 555 H2O [0 J]").unwrap();
+    let concentration = solution
+        .concentration_of(&ion_from_string!("CH3COOH"))
+        .unwrap();
+
+    let table = solution.ice_table(&acid, 1.8e-5).unwrap();
+    assert_eq!(table.unit, IceUnit::Molar);
+    assert_eq!(table.method, IceMethod::Quadratic);
+    assert_eq!(table.columns.len(), 3);
+    assert!((table.columns[0].initial - concentration).abs() < 1e-12);
+    assert!((k_from_table(&table) / 1.8e-5 - 1.0).abs() < 1e-9);
+    assert!((table.extent - (1.8e-5 * concentration).sqrt()).abs() < 1e-4);
+    assert!(format!("{}", table).contains("0.1000 - x"));
+
+    let exact = (-1.8e-5 + (1.8e-5_f64.powi(2) + 4.0 * 1.8e-5 * concentration).sqrt()) / 2.0;
+    assert!((table.extent - exact).abs() < 1e-12);
+    assert!(table.steps()[2].contains("quadratic formula"));
+
+    // The x² terms cancel out when K = 4, leaving a linear equation:
+    // 4 (1 - x)² = (2x)², so half of it reacts
+    let chlorination = ElemReaction::<Ion>::ion_from_string("H2 + Cl2 <> 2HCl").unwrap();
+    let vessel = Container::<Ion>::ion_from_string("H2 + Cl2 [0 J]").unwrap();
+
+    let table = vessel.ice_table(&chlorination, 4.0).unwrap();
+    assert_eq!(table.method, IceMethod::Linear);
+    assert!((table.extent - table.columns[0].initial / 2.0).abs() < 1e-12);
+    assert!(table.steps()[2].contains("linear"));
+
+    // Ammonia in a vessel, which gives a quartic
+    let haber = ElemReaction::<Ion>::ion_from_string("N2 + 3H2 <> 2NH3").unwrap();
+    let vessel = Container::<Ion>::ion_from_string("N2 + 3 H2 [0 J]").unwrap();
+
+    let table = vessel.ice_table(&haber, 100.0).unwrap();
+    assert_eq!(table.method, IceMethod::Quartic);
+    assert!(table.extent > 0.0 && table.extent < table.bounds.1);
+    assert!((k_from_table(&table) / 100.0 - 1.0).abs() < 1e-6);
+    assert!(table.steps()[2].contains("Ferrari"));
+
+    // Going backwards, with a cubic
+    let decomposition = ElemReaction::<Ion>::ion_from_string("2 NO2;0 <> 2 NO;0 + O2").unwrap();
+    let vessel = Container::<Ion>::ion_from_string("2 NO2;0 + O2 [0 J]").unwrap();
+
+    let table = vessel.ice_table(&decomposition, 1e-3).unwrap();
+    assert_eq!(table.method, IceMethod::Cubic);
+    assert!(table.extent > 0.0 && table.extent < table.bounds.1);
+    assert!((k_from_table(&table) / 1e-3 - 1.0).abs() < 1e-6);
+    assert!(table.steps()[2].contains("Cardano"));
+
+    // Higher degrees are solved numerically
+    let oxidation = ElemReaction::<Ion>::ion_from_string("4 NH3 + 5 O2 <> 4 NO;0 + 6 H2O").unwrap();
+    let vessel = Container::<Ion>::ion_from_string("4 NH3 + 5 O2 + 1 NO;0 [0 J]").unwrap();
+
+    let table = vessel.ice_table(&oxidation, 1e3).unwrap();
+    assert_eq!(table.method, IceMethod::Numerical);
+    assert!(table.extent > table.bounds.0 && table.extent < table.bounds.1);
+    assert!((k_from_table(&table) / 1e3 - 1.0).abs() < 1e-6);
+    assert!(table.steps()[2].contains("bisection"));
+
+    assert!(vessel.ice_table(&oxidation, 0.0).is_none());
+}
+
+#[test]
+fn molecule_ionic_parts() {
+    use data_ions::*;
+
+    let salt = molecule_from_string!("Fe2(SO4)3");
+    assert_eq!(salt, molecule_from_string!("Fe2S3O12"));
+
+    let parts = salt.ionic_parts().unwrap();
+    assert_eq!(parts.cation, ion_from_string!("Fe;3+"));
+    assert_eq!(parts.cations, 2);
+    assert_eq!(parts.anion, SULPHATE.clone());
+    assert_eq!(parts.anions, 3);
+    assert_eq!("iron(III) sulfate", salt.name());
+
+    let parts = molecule_from_string!("(NH4)3PO4").ionic_parts().unwrap();
+    assert_eq!(parts.cation, AMMONIUM.clone());
+    assert_eq!(parts.anion, PHOSPHATE.clone());
+
+    // The cation can be written after the anion
+    let parts = molecule_from_string!("CH3COONa").ionic_parts().unwrap();
+    assert_eq!(parts.cation, ion_from_string!("Na;+"));
+    assert_eq!(parts.anion, ACETATE.clone());
+
+    assert_eq!(
+        "potassium permanganate",
+        molecule_from_string!("KMnO4").name()
+    );
+    assert_eq!(
+        "potassium dichromate",
+        molecule_from_string!("K2Cr2O7").name()
+    );
+    assert_eq!(
+        "mercury(I) chloride",
+        molecule_from_string!("Hg2Cl2").name()
+    );
+    assert_eq!("manganese(IV) oxide", molecule_from_string!("MnO2").name());
+    assert_eq!("sodium peroxide", molecule_from_string!("Na2O2").name());
+    assert_eq!("calcium carbonate", molecule_from_string!("CaCO3").name());
+
+    // Covalent molecules aren't ionic
+    assert!(molecule_from_string!("H2SO4").ionic_parts().is_none());
+    assert!(molecule_from_string!("CO2").ionic_parts().is_none());
+
+    // The charge follows from the decomposition
+    assert_eq!(
+        AtomCharge::from(0),
+        ion_from_string!("Fe2(SO4)3").get_charge().unwrap()
+    );
+    assert_eq!(
+        AtomCharge::from(-2),
+        ion_from_string!("CO3").get_charge().unwrap()
+    );
+    assert_eq!(
+        AtomCharge::from(-1),
+        ion_from_string!("MnO4").get_charge().unwrap()
+    );
+    assert_eq!("carbonate", ion_from_string!("CO3;2-").name());
+
+    // Groups have to be balanced, and their amounts have to fit
+    assert!(Molecule::from_string("Ca3(PO4").is_none());
+    assert!(Molecule::from_string("Ca3PO4)2").is_none());
+    assert!(Molecule::from_string("(H9)99").is_none());
+    assert!(Molecule::from_string("(H2)300").is_none());
+}
+
+#[test]
+fn ionic_formula_from_ions() {
+    use data_ions::*;
+
+    let parts = IonicParts::from_ions(&ion_from_string!("Fe;3+"), &SULPHATE).unwrap();
+    assert_eq!((parts.cations, parts.anions), (2, 3));
+    assert_eq!("Fe₂(SO₄)₃", parts.symbol());
+    assert_eq!("iron(III) sulfate", parts.name());
+    assert_eq!(molecule_from_string!("Fe2(SO4)3"), parts.molecule());
+    assert_eq!("Fe₂(SO₄)₃", parts.molecule().symbol());
+
+    let parts = IonicParts::from_ions(&ion_from_string!("Ca;2+"), &CARBONATE).unwrap();
+    assert_eq!("CaCO₃", parts.symbol());
+
+    let parts = IonicParts::from_ions(&AMMONIUM, &PHOSPHATE).unwrap();
+    assert_eq!("(NH₄)₃PO₄", parts.symbol());
+    assert_eq!("ammonium phosphate", parts.name());
+
+    let parts =
+        IonicParts::from_ions(&ion_from_string!("Al;3+"), &ion_from_string!("O;2-")).unwrap();
+    assert_eq!("Al₂O₃", parts.symbol());
+    assert_eq!("aluminium oxide", parts.name());
+
+    assert!(IonicParts::from_ions(&SULPHATE, &SULPHATE).is_none());
+
+    // Dissolving splits the compound into its ions again
+    let salt = molecule_from_string!("Fe2(SO4)3").ionic_parts().unwrap();
+    let ions = salt.dissociate(&Moles::from(2.0));
+
+    assert_eq!(ions[0].element, ion_from_string!("Fe;3+"));
+    assert_eq!(ions[0].moles, Moles::from(4.0));
+    assert_eq!(ions[1].element, SULPHATE.clone());
+    assert_eq!(ions[1].moles, Moles::from(6.0));
+
+    assert!(molecule_from_string!("H2O").ionic_parts().is_none());
+}
+
+#[test]
+fn container_dissolution() {
+    // Table salt dissolves completely, cooling the water a bit
+    let mut container = Container::<Ion>::ion_from_string("1 NaCl + 555 H2O [0 J]").unwrap();
+    let temperature = container.temperature;
+
+    let dissolutions = container.dissolve();
+    assert_eq!(1, dissolutions.len());
+    assert!(!dissolutions[0].is_saturated);
+    assert!(dissolutions[0].enthalpy.unwrap() > Energy::from(0.0));
+    assert_eq!(
+        dissolutions[0].enthalpy.unwrap(),
+        Energy::from(0.0) - dissolutions[0].heat
+    );
+
+    assert_eq!(
+        Moles::from(0.0),
+        container.moles_of(&ion_from_string!("NaCl"))
+    );
+    assert_eq!(
+        Moles::from(1.0),
+        container.moles_of(&ion_from_string!("Na;+"))
+    );
+    assert_eq!(
+        Moles::from(1.0),
+        container.moles_of(&ion_from_string!("Cl;-"))
+    );
+    assert!(container.temperature < temperature);
+
+    // Too much salt saturates the solution at about 6.15 mol / L
+    let mut container = Container::<Ion>::ion_from_string("100 NaCl + 555 H2O [0 J]").unwrap();
+    let dissolutions = container.dissolve();
+
+    assert!(dissolutions[0].is_saturated);
+    let concentration = container
+        .concentration_of(&ion_from_string!("Na;+"))
+        .unwrap();
+    assert!((concentration - 6.15).abs() < 1e-3);
+
+    // Silver chloride barely dissolves, and without water nothing dissolves at all
+    let mut container = Container::<Ion>::ion_from_string("1 AgCl + 555 H2O [0 J]").unwrap();
+    let dissolutions = container.dissolve();
+
+    assert!(dissolutions[0].is_saturated);
+    assert!(dissolutions[0].moles < Moles::from(1e-3));
+    assert!(Container::<Ion>::ion_from_string("1 NaCl [0 J]")
+        .unwrap()
+        .dissolve()
+        .is_empty());
+
+    // Soluble salts without data dissolve completely, insoluble ones barely dissolve
+    let mut container =
+        Container::<Ion>::ion_from_string("1 KF + 1 CaCO3 + 555 H2O [0 J]").unwrap();
+    container.dissolve();
+
+    assert_eq!(
+        Moles::from(0.0),
+        container.moles_of(&ion_from_string!("KF"))
+    );
+    assert!(container.moles_of(&ion_from_string!("CaCO3")) > Moles::from(0.99));
+
+    // A simulation dissolves salts as well
+    let mut container = Container::<Ion>::ion_from_string("1 NaCl + 555 H2O [0 J]").unwrap();
+    container.simulate(&ReactionRegistry::default(), 100);
+
+    assert!(container.moles_of(&ion_from_string!("Na;+")) > Moles::from(0.99));
+
+    // Ammonium nitrate dissolves endothermically, cooling the water down,
+    // while calcium chloride heats it up
+    let mut container = Container::<Ion>::ion_from_string("1 NH4NO3 + 555 H2O [0 J]").unwrap();
+    let dissolutions = container.dissolve();
+
+    assert!(dissolutions[0].enthalpy.unwrap() > Energy::from(0.0));
+    assert!(dissolutions[0].heat < Energy::from(0.0));
+    assert_eq!(Energy::from(0.0), dissolutions[0].energy_delta);
+    assert!(container.temperature < Temperature::from(STANDARD_TEMPERATURE));
+
+    let mut container = Container::<Ion>::ion_from_string("1 CaCl2 + 555 H2O [0 J]").unwrap();
+    let dissolutions = container.dissolve();
+
+    assert!(dissolutions[0].enthalpy.unwrap() < Energy::from(0.0));
+    assert!(dissolutions[0].heat > Energy::from(0.0));
+    assert!(container.temperature > Temperature::from(STANDARD_TEMPERATURE));
+
+    // Potassium nitrate saturates at 3.79 mol / L, whatever the amount of salt
+    for &salt in &["50 KNO3", "100 KNO3"] {
+        let mut container =
+            Container::<Ion>::ion_from_string(&format!("{} + 56 H2O [0 J]", salt)).unwrap();
+        let dissolutions = container.dissolve();
+
+        assert!(dissolutions[0].is_saturated);
+        assert!(container.moles_of(&ion_from_string!("KNO3")) > Moles::from(0.0));
+        let concentration = container
+            .concentration_of(&ion_from_string!("K;+"))
+            .unwrap();
+        assert!((concentration - 3.79).abs() < 1e-3);
+    }
+
+    // The heat of solution is booked like the energy of a reaction,
+    // with the available energy paying for it as far as it goes
+    let mut container = Container::<Ion>::ion_from_string("1 NaCl + 555 H2O [10000 J]").unwrap();
+    let dissolutions = container.dissolve();
+    let enthalpy = dissolutions[0].enthalpy.unwrap();
+
+    assert_eq!(Energy::from(0.0) - enthalpy, dissolutions[0].heat);
+    assert_eq!(Energy::from(0.0) - enthalpy, dissolutions[0].energy_delta);
+    assert_eq!(Energy::from(10000.0) - enthalpy, container.available_energy);
+    assert!(container.temperature < Temperature::from(STANDARD_TEMPERATURE));
+
+    let mut container = Container::<Ion>::ion_from_string("1 NaCl + 555 H2O [1000 J]").unwrap();
+    let dissolutions = container.dissolve();
+
+    assert_eq!(Energy::from(-1000.0), dissolutions[0].energy_delta);
+    assert_eq!(Energy::from(0.0), container.available_energy);
+
+    // Salt added to water dissolves by itself, but not without water
+    let mut container = Container::<Ion>::ion_from_string("555 H2O [0 J]").unwrap();
+    container.add_elements(&[ContainerCompound::<Ion>::ion_from_string("1 NaCl").unwrap()]);
+
+    assert_eq!(
+        Moles::from(0.0),
+        container.moles_of(&ion_from_string!("NaCl"))
+    );
+    assert_eq!(
+        Moles::from(1.0),
+        container.moles_of(&ion_from_string!("Na;+"))
+    );
+
+    let mut container = Container::<Ion>::ion_from_string("1 KCl [0 J]").unwrap();
+    container.merge(Container::<Ion>::ion_from_string("1 NaCl [0 J]").unwrap());
+    assert_eq!(
+        Moles::from(1.0),
+        container.moles_of(&ion_from_string!("NaCl"))
+    );
+
+    container.merge(Container::<Ion>::ion_from_string("555 H2O [0 J]").unwrap());
+    assert_eq!(
+        Moles::from(0.0),
+        container.moles_of(&ion_from_string!("NaCl"))
+    );
+    assert!(container.temperature < Temperature::from(STANDARD_TEMPERATURE));
+}
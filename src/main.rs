@@ -41,8 +41,8 @@ fn main() {
     println!("10 moles of {} weigh {} gram(s).", symbol, weight);
 
     // Throw a bunch of molecules together in a container with a bit of energy
-    let mut container = Container {
-        contents: vec![
+    let container = Container {
+        contents: Contents::from(vec![
             ContainerCompound {
                 element: ion_from_molecule!(carbonmonoxide.clone()),
                 moles: Moles::from(10000000000.0),
@@ -55,9 +55,13 @@ fn main() {
                 element: ion_from_atom!(OXYGEN.clone()),
                 moles: Moles::from(10000000000.0),
             },
-        ],
+        ]),
 
         available_energy: Energy::from(100_000f64), // in Joules
+
+        temperature: Temperature::from(STANDARD_TEMPERATURE),
+
+        gas_mode: GasMode::default(),
     };
 
     // Specify the reaction that will occur
@@ -99,15 +103,23 @@ fn main() {
     // Print the contents of the container at the start
     println!("Contents: {}", container);
 
+    // Keep a log of what happens in the container
+    let mut history = History::new(container);
+
     // Run the reaction 10 times
     for i in 0..10 {
         // Run the reaction on the container
-        container.react(&reaction);
+        if let Err(error) = history.react(&reaction) {
+            println!("####    {}", error);
+        }
 
         // Show what's left
-        println!("[{:>2}] Contents: {}", i + 1, container.to_string());
+        println!("[{:>2}] Contents: {}", i + 1, history.container());
     }
 
+    // Export the amounts after every step, ready to be plotted
+    println!("{}", history.to_csv().unwrap());
+
     // Redox reactions are also possible
     let redox = RedoxReaction {
         oxidator: ElemReaction::<Ion>::ion_from_string("F2 + 2e <> 2F;1-").unwrap(),
@@ -125,6 +137,16 @@ fn main() {
     println!("oxidator: {}", get_sep(&redox.oxidator).unwrap());
     println!("reductor: {}", get_sep(&redox.reductor).unwrap());
 
+    // The same redox reaction can be seen as an electrochemical cell
+    let cell = GalvanicCell::from_redox(&redox).unwrap();
+    println!("cell: {}", cell);
+    println!(
+        "E_cell = {} V, n = {}",
+        cell.emf().unwrap(),
+        cell.electrons()
+    );
+    println!("ΔG = {:.0} J", cell.gibbs_energy().unwrap());
+
     // Print the SEF value
     println!(
         "SEF(AlCl3) = {} kJ/mol",
@@ -136,16 +158,39 @@ fn main() {
 
     let mut water_container =
         Container::<Ion>::ion_from_string("2000 H2; + 1000 O2; [10000 J]").unwrap();
+
+    // Close the vessel, so the pressure can build up
+    water_container.gas_mode = GasMode::FixedVolume(water_container.gas_volume());
     println!("pre: {}", water_container);
+    println!(
+        "T = {:.1} K, p = {:.0} Pa, V = {:.0} L",
+        water_container.temperature,
+        water_container.pressure(),
+        water_container.gas_volume()
+    );
 
     let redox_boom = get_redox_reaction(&water_container).unwrap();
     println!("reaction: {}", redox_boom.elem_reaction().symbol());
 
-    for _ in 0..100 {
-        water_container.react(&redox_boom);
+    // Burn the limiting reagent completely
+    match water_container.react_to_completion(&redox_boom) {
+        Ok(outcome) => println!(
+            "extent: {:.0} mol, yield: {:.0} mol H₂O",
+            outcome.extent,
+            outcome.theoretical_yield(&ion_from_string!("H2O"))
+        ),
+        Err(error) => println!("####    {}", error),
     }
 
     println!("post: {}", water_container);
+    println!(
+        "T = {:.1} K, p = {:.0} Pa, p(H₂) = {:.0} Pa",
+        water_container.temperature,
+        water_container.pressure(),
+        water_container
+            .partial_pressure(&ion_from_string!("H2"))
+            .unwrap()
+    );
     println!("\n\n\n");
 
     // Automatic redox reactions
@@ -153,7 +198,7 @@ fn main() {
 
     // Get the possible redox reactions from a container
     let mut redox_container = Container {
-        contents: vec![
+        contents: Contents::from(vec![
             ContainerCompound {
                 element: ion_from_string!("Fe"),
                 moles: Moles::from(100.0),
@@ -166,11 +211,38 @@ fn main() {
                 element: ion_from_string!("H2O"),
                 moles: Moles::from(200.0),
             },
-        ],
+        ]),
 
         available_energy: Energy::from(100_000f64),
+
+        temperature: Temperature::from(STANDARD_TEMPERATURE),
+
+        gas_mode: GasMode::default(),
     };
 
+    // List every pairing, including the ones that are missing a co-reactant
+    for candidate in get_redox_candidates(&redox_container) {
+        if candidate.is_feasible() {
+            println!("{:.3} V: {}", candidate.emf, candidate.reaction.symbol());
+        } else {
+            println!(
+                "{:.3} V: {} (missing {})",
+                candidate.emf,
+                candidate.reaction.symbol(),
+                ReactionSide {
+                    compounds: candidate
+                        .missing
+                        .iter()
+                        .map(|x| ReactionCompound {
+                            element: x.clone(),
+                            amount: 1,
+                        })
+                        .collect(),
+                }
+            );
+        }
+    }
+
     let redox_reaction = get_redox_reaction(&redox_container);
 
     if let Some(redox) = redox_reaction {
@@ -180,31 +252,300 @@ fn main() {
         println!("Redox reaction: \n{}", redox.symbol());
         println!("Total reaction: {}", redox.elem_reaction().symbol());
 
-        for _ in 0..100 {
-            redox_container.react(&redox);
+        // Stop as soon as one of the reactants runs out
+        let mut times = 0;
+        while times < 100 {
+            if let Err(error) = redox_container.react(&redox) {
+                println!("####    {}", error);
+                break;
+            }
+
+            times += 1;
         }
 
         println!("\n");
-        println!("After 100 times:");
+        println!("After {} times:", times);
         println!("Container: {}", redox_container);
 
-        let rust = ElemReaction::<Ion>::ion_from_string("Fe;2+ + 2OH;-  >  FeO2H2;0").unwrap();
-
+        // The iron(II) hydroxide that is formed is insoluble
         println!("\n");
         println!("Container: {}", &redox_container);
-        println!("\tcan have the following reaction:");
-        println!("Salt reaction: \n{}", rust.symbol());
-
-        let fe2 = ContainerCompound::<Ion>::ion_from_string("Fe;2+").unwrap();
+        println!("\tprecipitates:");
 
-        while redox_container.contains(&fe2) {
-            redox_container.react(&rust);
+        for precipitate in redox_container.precipitate() {
+            println!("{} {}", precipitate.moles, precipitate.salt().symbol());
         }
 
         println!("\n");
-        println!("After all {} is gone:", fe2.symbol());
+        println!("After precipitation:");
         println!("Container: {}", redox_container);
 
+        // The Pourbaix diagram of iron shows why: in water with oxygen (on the O₂/H₂O line)
+        // at pH 7, rust is the most stable form of iron
+        let pourbaix = PourbaixDiagram::new(&IRON, 1e-6).unwrap();
+        let oxygen_line = &pourbaix.water_lines()[0];
+        let potential = oxygen_line.from.1 + (oxygen_line.to.1 - oxygen_line.from.1) * 0.5;
+
+        println!("\n");
+        println!("Pourbaix diagram of {}:", IRON.name);
+        for region in &pourbaix.regions {
+            println!("\t{}", region.species.symbol());
+        }
+        if let Some(species) = pourbaix.predominant_species(7.0, potential) {
+            println!(
+                "At pH 7 and {:.3} V, iron becomes {}",
+                potential,
+                species.symbol()
+            );
+        }
+
         println!("\n\n\n");
     }
+
+    // Electrolysis of brine (chlor-alkali process)
+    // The power supply has to deliver the 144 kJ of electrical work
+    let mut brine = Container::<Ion>::ion_from_string("Na;+ + Cl;- + 55H2O [200000 J]").unwrap();
+    println!("pre: {}", brine);
+
+    // Apply 10 A for an hour at 4 V
+    let steps = brine
+        .electrolyse(Current::from(10.0), Time::from(3600.0), SEP::from(4.0))
+        .unwrap();
+
+    for step in steps {
+        println!("anode: {}", step.cell.anode.symbol());
+        println!("cathode: {}", step.cell.cathode.symbol());
+        println!("minimum voltage: {} V", step.minimum_voltage);
+    }
+
+    println!("post: {}", brine);
+
+    // Hydrochloric acid knows it is acidic
+    let acid = Container::<Ion>::ion_from_string("HCl + 555 H2O [0 J]").unwrap();
+    println!("\n{}", acid);
+    println!(
+        "pH = {:.2}, pOH = {:.2}",
+        acid.ph().unwrap(),
+        acid.poh().unwrap()
+    );
+
+    // ... and can be titrated with sodium hydroxide
+    let burette = Container::<Ion>::ion_from_string("4Na;+ + 4OH;- + 2220 H2O [0 J]").unwrap();
+    let titration = Titration::acid_base(&acid, &burette, 250.0, 20000.0).unwrap();
+    for volume in titration.equivalence_points() {
+        println!("Equivalence point at {} mL", volume);
+    }
+
+    // An acetate buffer of pH 5 with a capacity of 0.05 M per pH unit
+    let recipe =
+        BufferRecipe::design(&Ion::from_string("CH3COOH").unwrap(), 5.0, 0.05, 1.0).unwrap();
+    for (compound, grams) in recipe.grams() {
+        println!("Weigh out {:.3} g of {}", grams, compound.symbol());
+    }
+
+    // ... hardly changes its pH when adding some hydrochloric acid
+    let buffer = recipe.container();
+    let hydrochloric_acid = ContainerCompound {
+        element: Ion::from_string("HCl").unwrap(),
+        moles: Moles::from(0.01),
+    };
+    println!(
+        "pH = {:.2}, after adding HCl: {:.2}",
+        buffer.ph().unwrap(),
+        buffer.ph().unwrap() + buffer.ph_change(&[hydrochloric_acid]).unwrap()
+    );
+
+    // The Latimer diagram of manganese, in acid
+    let latimer = LatimerDiagram::new(&MANGANESE).unwrap();
+    println!("\n{}", latimer);
+    for species in latimer.disproportionating() {
+        println!("{} disproportionates", species.symbol());
+    }
+
+    // Let a container find out for itself what happens
+    let mut beaker =
+        Container::<Ion>::ion_from_string("Ag;+ + NO3;- + Na;+ + Cl;- + 555 H2O [0 J]").unwrap();
+    println!("\npre: {}", beaker);
+
+    // Nitrate is a poor oxidator in neutral solutions, so leave out redox reactions
+    let registry = ReactionRegistry {
+        redox: false,
+        ..ReactionRegistry::new()
+    };
+
+    for step in beaker.simulate(&registry, 100) {
+        println!(
+            "{:?}: {} ({:.0} J/mol)",
+            step.kind,
+            step.reaction.symbol(),
+            step.driving_force
+        );
+    }
+
+    println!("post: {}", beaker);
+
+    // Follow the decomposition of dinitrogen pentoxide over time
+    let n2o5 = Ion::from_string("N2O5").unwrap();
+    let decomposition = KineticReaction::new(
+        ElemReaction::<Ion>::ion_from_string("2N2O5 > 4NO2;0 + O2").unwrap(),
+        Arrhenius::new(4.94e13, Energy::from(103_400.0)),
+    )
+    .with_order(&n2o5, 1.0);
+
+    let mut solution = Container::<Ion>::ion_from_string("N2O5 + 555 H2O [0 J]").unwrap();
+    let trajectory = solution
+        .integrate(
+            &[decomposition],
+            Time::from(86_400.0),
+            Integrator::RungeKutta,
+        )
+        .unwrap();
+
+    println!(
+        "\nhalf-life of N2O5: {:?} s",
+        trajectory.half_life(&n2o5).map(|x| x.0)
+    );
+    println!("after a day: {}", solution);
+
+    // In a nanodroplet, only a few molecules take part
+    let no2 = Ion::from_string("NO2;0").unwrap();
+    let dimerisation = KineticReaction::new(
+        ElemReaction::<Ion>::ion_from_string("2NO2;0 <> N2O4;0").unwrap(),
+        Arrhenius::constant(1e9),
+    )
+    .with_reverse(Arrhenius::constant(1e5));
+
+    let droplet = Container::<Ion>::ion_from_string("20 NO2;0 [0 J]").unwrap();
+    let ensemble = droplet.gillespie(
+        &[dimerisation],
+        Time::from(1e-3),
+        &StochasticSettings::new(1e-18, 0),
+    );
+
+    let (_, mean) = *ensemble.mean_of(&no2).unwrap().last().unwrap();
+    let (_, variance) = *ensemble.variance_of(&no2).unwrap().last().unwrap();
+    println!("\nNO2 molecules left: {:.2} ± {:.2}", mean, variance.sqrt());
+
+    // Nitrogen monoxide is oxidised in two steps
+    let mechanism = Mechanism::new(vec![
+        KineticReaction::new(
+            ElemReaction::<Ion>::ion_from_string("2NO;0 <> N2O2;0").unwrap(),
+            Arrhenius::constant(1e3),
+        )
+        .with_reverse(Arrhenius::constant(1e5)),
+        KineticReaction::new(
+            ElemReaction::<Ion>::ion_from_string("N2O2;0 + O2 > 2NO2;0").unwrap(),
+            Arrhenius::constant(10.0),
+        ),
+    ]);
+
+    let slowest = mechanism
+        .rate_determining_step(Temperature::from(STANDARD_TEMPERATURE))
+        .unwrap();
+
+    println!("\noverall: {}", mechanism.overall_reaction());
+    for intermediate in mechanism.intermediates() {
+        println!("intermediate: {}", intermediate.symbol());
+    }
+    if let Some(rate_law) = mechanism.rate_determining_rate_law(slowest) {
+        println!(
+            "rate-determining step: {}",
+            mechanism.steps[slowest].reaction
+        );
+        for (element, order) in rate_law.forward_orders {
+            println!("order in {}: {}", element.symbol(), order);
+        }
+    }
+
+    // Hydrogen and oxygen don't react until there is a spark
+    let explosion = ActivatedReaction::new(
+        ElemReaction::<Ion>::ion_from_string("2H2 + O2 > 2H2O").unwrap(),
+        Activation::new(Energy::from(150_000.0)),
+    );
+
+    let mut balloon = Container::<Ion>::ion_from_string("2 H2 + O2 [0 J]").unwrap();
+    if let Err(error) = balloon.react(&explosion) {
+        println!("\nwithout a spark: {}", error);
+    }
+
+    balloon.available_energy += Energy::from(150_000.0);
+    if balloon.react(&explosion).is_ok() {
+        println!("with a spark: {}", balloon);
+    }
+
+    // Compress an equilibrium mixture of nitrogen, hydrogen and ammonia
+    let mut registry = ReactionRegistry {
+        redox: false,
+        acid_base: false,
+        precipitation: false,
+        reactions: vec![],
+    };
+    registry.register(ElemReaction::<Ion>::ion_from_string("N2 + 3H2 <> 2NH3").unwrap());
+
+    let mut reactor = Container::<Ion>::ion_from_string("N2 + 3 H2 [200000 J]").unwrap();
+    reactor.equilibrate(&registry);
+    println!("\nequilibrium: {}", reactor);
+
+    let perturbation = reactor
+        .disturb(Disturbance::Pressure(Pressure::from(1e6)), &registry)
+        .unwrap();
+
+    for reason in &perturbation.reasoning {
+        println!("{}", reason);
+    }
+    println!("compressed: {}", reactor);
+
+    // Work out how much of a weak acid dissociates, step by step
+    let acid = ElemReaction::<Ion>::ion_from_string("CH3COOH <> CH3COO;- + H;+").unwrap();
+    let vinegar = Container::<Ion>::ion_from_string("CH3COOH + 555 H2O [0 J]").unwrap();
+
+    if let Some(table) = vinegar.ice_table(&acid, 1.8e-5) {
+        println!("\n{}", table);
+    }
+
+    // Recognise the ions in a salt
+    let salt = Molecule::from_string("Fe2(SO4)3").unwrap();
+    if let Some(parts) = salt.ionic_parts() {
+        println!(
+            "\n{} consists of {} {} and {} {}",
+            salt.name(),
+            parts.cations,
+            parts.cation,
+            parts.anions,
+            parts.anion
+        );
+    }
+
+    // Combine ions into a neutral salt, and split it up again
+    let cation = Ion::from_string("Al;3+").unwrap();
+    let anion = Ion::from_string("SO4;2-").unwrap();
+
+    if let Some(parts) = IonicParts::from_ions(&cation, &anion) {
+        println!("{} is {}", parts.symbol(), parts.name());
+
+        for ion in parts.dissociate(&Moles::from(1.0)) {
+            println!("\t{}", ion);
+        }
+    }
+
+    // Dissolve table salt in water, until it is saturated
+    // (containers read from a string are taken as given, so this is done by hand)
+    let mut brine = Container::<Ion>::ion_from_string("100 NaCl + 555 H2O [0 J]").unwrap();
+    let temperature = brine.temperature;
+
+    for dissolution in brine.dissolve() {
+        println!(
+            "\n{:.3} mol of {} dissolved{}",
+            dissolution.moles.0,
+            dissolution.salt(),
+            if dissolution.is_saturated {
+                ", the solution is saturated"
+            } else {
+                ""
+            }
+        );
+    }
+
+    println!("{} -> {}", temperature, brine.temperature);
+    println!("brine: {}", brine);
 }
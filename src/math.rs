@@ -1,3 +1,5 @@
+use std::f64::consts::PI;
+
 /// Calculate Greatest Common Divisor (GCD), using Euclides' algorithm
 pub fn gcd(x: i32, y: i32) -> i32 {
     // Store the highest in a, the lowest in b
@@ -17,3 +19,171 @@ pub fn gcd(x: i32, y: i32) -> i32 {
 
     a
 }
+
+/// Calculate Least Common Multiple (LCM), using the GCD
+pub fn lcm(x: i32, y: i32) -> i32 {
+    if x == 0 || y == 0 {
+        return 0;
+    }
+
+    (x / gcd(x, y)) * y
+}
+
+/// Solve the linear system A·x = b, using Gaussian elimination with partial pivoting
+/// Returns None if the matrix is singular
+pub fn solve_linear(mut matrix: Vec<Vec<f64>>, mut rhs: Vec<f64>) -> Option<Vec<f64>> {
+    let size = rhs.len();
+
+    for column in 0..size {
+        // Use the largest value in this column as pivot, for numerical stability
+        let pivot = (column..size).max_by(|&a, &b| {
+            matrix[a][column]
+                .abs()
+                .partial_cmp(&matrix[b][column].abs())
+                .unwrap()
+        })?;
+
+        if matrix[pivot][column] == 0.0 || !matrix[pivot][column].is_finite() {
+            return None;
+        }
+
+        matrix.swap(column, pivot);
+        rhs.swap(column, pivot);
+
+        let (done, rest) = matrix.split_at_mut(column + 1);
+        let pivot_row = &done[column];
+
+        for (offset, values) in rest.iter_mut().enumerate() {
+            let factor = values[column] / pivot_row[column];
+
+            for (value, pivot_value) in values.iter_mut().zip(pivot_row.iter()).skip(column) {
+                *value -= factor * pivot_value;
+            }
+            rhs[column + 1 + offset] -= factor * rhs[column];
+        }
+    }
+
+    // Back substitution
+    let mut solution = vec![0.0; size];
+    for row in (0..size).rev() {
+        let sum: f64 = ((row + 1)..size)
+            .map(|i| matrix[row][i] * solution[i])
+            .sum();
+
+        solution[row] = (rhs[row] - sum) / matrix[row][row];
+    }
+
+    Some(solution)
+}
+
+/// Find the real roots of a polynomial of at most degree 4, given its coefficients
+/// from the constant term up, using the quadratic formula, Cardano's formula or
+/// Ferrari's method
+/// Returns None if the degree is higher, or if the polynomial is zero
+pub fn solve_polynomial(coefficients: &[f64]) -> Option<Vec<f64>> {
+    let largest = coefficients.iter().fold(0.0, |a: f64, b| a.max(b.abs()));
+    let degree = coefficients
+        .iter()
+        .rposition(|x| x.abs() > 1e-12 * largest)?;
+
+    let leading = coefficients[degree];
+    let normalised: Vec<f64> = coefficients[..degree].iter().map(|x| x / leading).collect();
+
+    match degree {
+        0 => Some(vec![]),
+        1 => Some(vec![-normalised[0]]),
+        2 => Some(solve_quadratic(normalised[1], normalised[0])),
+        3 => Some(solve_cubic(normalised[2], normalised[1], normalised[0])),
+        4 => Some(solve_quartic(
+            normalised[3],
+            normalised[2],
+            normalised[1],
+            normalised[0],
+        )),
+        _ => None,
+    }
+}
+
+/// Find the real roots of x² + bx + c
+fn solve_quadratic(b: f64, c: f64) -> Vec<f64> {
+    let discriminant = b * b - 4.0 * c;
+
+    if discriminant < 0.0 {
+        return vec![];
+    }
+
+    // Avoid subtracting nearly equal numbers, for numerical stability
+    let q = -0.5 * (b + b.signum() * discriminant.sqrt());
+
+    if q == 0.0 {
+        vec![0.0]
+    } else {
+        vec![q, c / q]
+    }
+}
+
+/// Find the real roots of x³ + bx² + cx + d
+fn solve_cubic(b: f64, c: f64, d: f64) -> Vec<f64> {
+    // Substitute x = t - b/3 to get t³ + pt + q
+    let shift = b / 3.0;
+    let p = c - b * shift;
+    let q = 2.0 * shift.powi(3) - c * shift + d;
+
+    let discriminant = (q / 2.0).powi(2) + (p / 3.0).powi(3);
+
+    let roots = if discriminant > 0.0 {
+        let root = discriminant.sqrt();
+
+        vec![(-q / 2.0 + root).cbrt() + (-q / 2.0 - root).cbrt()]
+    } else if p == 0.0 {
+        vec![(-q).cbrt()]
+    } else {
+        // Three real roots, using the trigonometric form
+        let amplitude = 2.0 * (-p / 3.0).sqrt();
+        let angle = ((3.0 * q / (2.0 * p)) * (-3.0 / p).sqrt())
+            .clamp(-1.0, 1.0)
+            .acos()
+            / 3.0;
+
+        (0..3)
+            .map(|k| amplitude * (angle - 2.0 * PI * f64::from(k) / 3.0).cos())
+            .collect()
+    };
+
+    roots.into_iter().map(|t| t - shift).collect()
+}
+
+/// Find the real roots of x⁴ + bx³ + cx² + dx + e
+fn solve_quartic(b: f64, c: f64, d: f64, e: f64) -> Vec<f64> {
+    // Substitute x = y - b/4 to get y⁴ + py² + qy + r
+    let shift = b / 4.0;
+    let p = c - 6.0 * shift.powi(2);
+    let q = d - 2.0 * c * shift + 8.0 * shift.powi(3);
+    let r = e - d * shift + c * shift.powi(2) - 3.0 * shift.powi(4);
+
+    let mut roots = vec![];
+
+    if q.abs() < 1e-12 * (1.0 + p.abs() + r.abs()) {
+        // Biquadratic: a quadratic in y²
+        for square in solve_quadratic(p, r) {
+            if square >= 0.0 {
+                roots.push(square.sqrt());
+                roots.push(-square.sqrt());
+            }
+        }
+    } else {
+        // Write it as a difference of squares, using a positive root m of the resolvent cubic
+        let m = solve_cubic(p, p * p / 4.0 - r, -q * q / 8.0)
+            .into_iter()
+            .fold(0.0, f64::max);
+
+        if m > 0.0 {
+            let root = (2.0 * m).sqrt();
+
+            roots.extend(solve_quadratic(root, p / 2.0 + m - q / (2.0 * root)));
+            roots.extend(solve_quadratic(-root, p / 2.0 + m + q / (2.0 * root)));
+        }
+    }
+
+    roots.into_iter().map(|y| y - shift).collect()
+}
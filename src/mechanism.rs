@@ -0,0 +1,313 @@
+use kinetics::*;
+use math::solve_linear;
+use reaction::{ElemReaction, ReactionSide};
+use trait_element::Element;
+use types::*;
+
+#[derive(Debug, Clone, PartialEq)]
+/// A reaction mechanism: the elementary steps an overall reaction takes place by
+pub struct Mechanism<E: Element> {
+    /// The elementary steps, in the order they take place
+    /// These can be integrated like any other reactions, see `Container::integrate`
+    pub steps: Vec<KineticReaction<E>>,
+}
+
+/// Get the net change of every species in a reaction, in order of appearance
+fn net_changes<E: Element>(reactions: &[&ElemReaction<E>]) -> Vec<(E, i32)> {
+    let mut changes: Vec<(E, i32)> = vec![];
+
+    for reaction in reactions {
+        for (side, sign) in &[(&reaction.lhs, -1), (&reaction.rhs, 1)] {
+            for compound in &side.compounds {
+                let change = sign * i32::from(compound.amount);
+
+                if let Some(existing) = changes.iter_mut().find(|x| x.0 == compound.element) {
+                    existing.1 += change;
+                } else {
+                    changes.push((compound.element.clone(), change));
+                }
+            }
+        }
+    }
+
+    changes
+}
+
+/// Get the coefficient of an element on a side of a reaction
+fn coefficient<E: Element>(side: &ReactionSide<E>, element: &E) -> f64 {
+    side.compounds
+        .iter()
+        .filter(|x| &x.element == element)
+        .map(|x| f64::from(x.amount))
+        .sum()
+}
+
+/// Add an amount to the order of an element, removing it if it becomes 0
+fn add_order<E: Element>(orders: &mut Vec<(E, f64)>, element: &E, amount: f64) {
+    if let Some(order) = orders.iter_mut().find(|x| &x.0 == element) {
+        order.1 += amount;
+    } else {
+        orders.push((element.clone(), amount));
+    }
+
+    orders.retain(|x| x.1.abs() > 1e-12);
+}
+
+impl<E: Element> Mechanism<E> {
+    /// Create a mechanism from its steps, in the order they take place
+    pub fn new(steps: Vec<KineticReaction<E>>) -> Mechanism<E> {
+        Mechanism { steps }
+    }
+
+    /// Get the net change of every species when every step takes place once
+    fn net_changes(&self) -> Vec<(E, i32)> {
+        let reactions: Vec<&ElemReaction<E>> = self.steps.iter().map(|x| &x.reaction).collect();
+
+        net_changes(&reactions)
+    }
+
+    /// Get the overall reaction: the sum of all steps, without intermediates and catalysts
+    pub fn overall_reaction(&self) -> ElemReaction<E> {
+        let mut lhs = ReactionSide { compounds: vec![] };
+        let mut rhs = ReactionSide { compounds: vec![] };
+
+        for step in &self.steps {
+            lhs.compounds
+                .extend(step.reaction.lhs.compounds.iter().cloned());
+            rhs.compounds
+                .extend(step.reaction.rhs.compounds.iter().cloned());
+        }
+
+        ElemReaction {
+            lhs,
+            rhs,
+            is_equilibrium: false,
+        }
+        .simplify()
+    }
+
+    /// Check if the steps add up to a reaction
+    pub fn adds_up_to(&self, reaction: &ElemReaction<E>) -> bool {
+        let mut expected = net_changes(&[reaction]);
+        let mut actual = self.net_changes();
+
+        expected.retain(|x| x.1 != 0);
+        actual.retain(|x| x.1 != 0);
+
+        expected.len() == actual.len() && expected.iter().all(|x| actual.contains(x))
+    }
+
+    /// Get the species that aren't in the overall reaction,
+    /// together with whether they are formed before they are used
+    fn cancelled_species(&self) -> Vec<(E, bool)> {
+        let cancelled: Vec<E> = self
+            .net_changes()
+            .into_iter()
+            .filter(|x| x.1 == 0)
+            .map(|x| x.0)
+            .collect();
+
+        let mut seen: Vec<(E, bool)> = vec![];
+
+        for step in &self.steps {
+            for (side, is_formed) in &[(&step.reaction.lhs, false), (&step.reaction.rhs, true)] {
+                for compound in &side.compounds {
+                    if cancelled.contains(&compound.element)
+                        && !seen.iter().any(|x| x.0 == compound.element)
+                    {
+                        seen.push((compound.element.clone(), *is_formed));
+                    }
+                }
+            }
+        }
+
+        seen
+    }
+
+    /// Get the intermediates: the species that are formed in a step and used up in a later one
+    pub fn intermediates(&self) -> Vec<E> {
+        self.cancelled_species()
+            .into_iter()
+            .filter(|x| x.1)
+            .map(|x| x.0)
+            .collect()
+    }
+
+    /// Get the catalysts: the species that are used in a step and formed again in a later one
+    pub fn catalysts(&self) -> Vec<E> {
+        self.cancelled_species()
+            .into_iter()
+            .filter(|x| !x.1)
+            .map(|x| x.0)
+            .collect()
+    }
+
+    /// Get the slowest step at a temperature, which is the one with the lowest rate constant
+    /// NOTE: This compares rate constants of possibly different orders, which is only
+    /// meaningful when the concentrations are around 1 mol/L
+    pub fn rate_determining_step(&self, temperature: Temperature) -> Option<usize> {
+        (0..self.steps.len()).min_by(|&a, &b| {
+            let rate_constant = |x: usize| self.steps[x].forward.rate_constant(temperature);
+
+            rate_constant(a).total_cmp(&rate_constant(b))
+        })
+    }
+
+    /// Derive the rate law of the overall reaction, with the given step being rate-determining
+    /// The steps before it are taken to be fast equilibria, so the intermediates in the rate
+    /// law of the rate-determining step can be expressed in the other species.
+    /// The result has a rate constant of k · Π K, with K = k(forward) / k(reverse).
+    /// Returns None if an intermediate isn't formed in a reversible step before it
+    pub fn rate_determining_rate_law(&self, step: usize) -> Option<KineticReaction<E>> {
+        let intermediates = self.intermediates();
+        let slowest = self.steps.get(step)?;
+
+        let mut orders = slowest.forward_orders.clone();
+        let mut pre_exponential = slowest.forward.pre_exponential;
+        let mut activation_energy = slowest.forward.activation_energy;
+
+        // Every substitution removes an intermediate that is formed in an earlier step,
+        // so there are at most that many substitutions
+        for _ in 0..=(step * intermediates.len()) {
+            let (intermediate, order) = match orders.iter().find(|x| intermediates.contains(&x.0)) {
+                Some(x) => x.clone(),
+                None => {
+                    return Some(KineticReaction {
+                        reaction: self.overall_reaction(),
+                        forward: Arrhenius::new(pre_exponential, activation_energy),
+                        reverse: None,
+                        forward_orders: orders,
+                        reverse_orders: vec![],
+                    });
+                }
+            };
+
+            let equilibrium = self.steps[..step].iter().find(|x| {
+                x.reverse.is_some() && coefficient(&x.reaction.rhs, &intermediate) > 0.0
+            })?;
+            let reverse = equilibrium.reverse?;
+
+            // K = Π[products] / Π[reactants], solved for the intermediate
+            let power = order / coefficient(&equilibrium.reaction.rhs, &intermediate);
+
+            pre_exponential *=
+                (equilibrium.forward.pre_exponential / reverse.pre_exponential).powf(power);
+            activation_energy +=
+                (equilibrium.forward.activation_energy - reverse.activation_energy) * power;
+
+            for compound in &equilibrium.reaction.lhs.compounds {
+                add_order(
+                    &mut orders,
+                    &compound.element,
+                    power * f64::from(compound.amount),
+                );
+            }
+
+            for compound in &equilibrium.reaction.rhs.compounds {
+                add_order(
+                    &mut orders,
+                    &compound.element,
+                    -power * f64::from(compound.amount),
+                );
+            }
+        }
+
+        None
+    }
+
+    /// Get the rate of the overall reaction at a temperature [ mol / (L s) ],
+    /// given the concentration of every species that isn't an intermediate [ mol / L ]
+    /// The concentrations of the intermediates are taken to be constant (steady-state),
+    /// and are returned as well.
+    /// Returns None if the rate laws are not linear in the intermediates,
+    /// or if the overall reaction is empty
+    pub fn steady_state_rate<F: Fn(&E) -> f64>(
+        &self,
+        concentration: F,
+        temperature: Temperature,
+    ) -> Option<(f64, Vec<(E, f64)>)> {
+        let intermediates = self.intermediates();
+
+        // Every rate law is k · [I] or just k, with I at most one intermediate
+        let mut terms: Vec<(f64, Option<usize>, Vec<f64>)> = vec![];
+
+        for step in &self.steps {
+            let changes = network_changes(&step.reaction, &intermediates);
+
+            let mut directions = vec![(step.forward, &step.forward_orders, changes.clone())];
+            if let Some(reverse) = step.reverse {
+                let reverse_changes = changes.iter().map(|x| -x).collect();
+                directions.push((reverse, &step.reverse_orders, reverse_changes));
+            }
+
+            for (arrhenius, orders, changes) in directions {
+                let mut factor = arrhenius.rate_constant(temperature);
+                let mut intermediate = None;
+
+                for &(ref element, order) in orders.iter() {
+                    match intermediates.iter().position(|x| x == element) {
+                        Some(index) if order == 1.0 && intermediate.is_none() => {
+                            intermediate = Some(index);
+                        }
+                        Some(_) => return None,
+                        None => factor *= concentration(element).max(0.0).powf(order),
+                    }
+                }
+
+                terms.push((factor, intermediate, changes));
+            }
+        }
+
+        // Σ change · rate = 0 for every intermediate
+        let size = intermediates.len();
+        let mut matrix = vec![vec![0.0; size]; size];
+        let mut rhs = vec![0.0; size];
+
+        for &(factor, intermediate, ref changes) in &terms {
+            for (row, change) in changes.iter().enumerate() {
+                match intermediate {
+                    Some(column) => matrix[row][column] += change * factor,
+                    None => rhs[row] -= change * factor,
+                }
+            }
+        }
+
+        let steady_state = if size > 0 {
+            solve_linear(matrix, rhs)?
+        } else {
+            vec![]
+        };
+
+        // The rate follows from any species in the overall reaction, preferably a product,
+        // as the rates of reactants in pre-equilibria cancel out
+        let overall = self.overall_reaction();
+        let changes = net_changes(&[&overall]);
+        let (element, change) = changes
+            .iter()
+            .find(|x| x.1 > 0)
+            .or_else(|| changes.iter().find(|x| x.1 != 0))?
+            .clone();
+
+        let concentration_of = |x: &E| match intermediates.iter().position(|y| y == x) {
+            Some(index) => steady_state[index],
+            None => concentration(x),
+        };
+
+        let mut rate = 0.0;
+        for step in &self.steps {
+            let change_in_step = coefficient(&step.reaction.rhs, &element)
+                - coefficient(&step.reaction.lhs, &element);
+
+            if change_in_step == 0.0 {
+                continue;
+            }
+
+            rate += change_in_step * step.net_rate(concentration_of, temperature);
+        }
+
+        Some((
+            rate / f64::from(change),
+            intermediates.into_iter().zip(steady_state).collect(),
+        ))
+    }
+}
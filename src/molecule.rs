@@ -1,5 +1,8 @@
 use atom::Atom;
+use container::ContainerCompound;
+use data_ions::*;
 use ion::Ion;
+use math::gcd;
 use namings::*;
 use trait_element::Element;
 use trait_properties::Properties;
@@ -22,27 +25,339 @@ pub struct MoleculeCompound {
     pub amount: u8,
 }
 
+#[derive(Debug, Eq, PartialEq, Clone)]
+/// The ions an ionic compound consists of, like 2 Fe³⁺ and 3 SO₄²⁻ in Fe₂(SO₄)₃
+pub struct IonicParts {
+    /// The cation
+    pub cation: Ion,
+
+    /// The amount of cations
+    pub cations: u8,
+
+    /// The anion
+    pub anion: Ion,
+
+    /// The amount of anions
+    pub anions: u8,
+}
+
+/// Check if some compounds are a whole multiple of a molecule, like N₂O₆ of NO₃
+/// Returns the multiple
+fn multiple_of(compounds: &[MoleculeCompound], molecule: &Molecule) -> Option<u8> {
+    if compounds.len() != molecule.compounds.len() {
+        return None;
+    }
+
+    let multiple = compounds[0].amount / molecule.compounds[0].amount;
+
+    let is_multiple = multiple > 0
+        && compounds
+            .iter()
+            .zip(molecule.compounds.iter())
+            .all(|(x, y)| x.atom == y.atom && x.amount == y.amount * multiple);
+
+    if is_multiple {
+        Some(multiple)
+    } else {
+        None
+    }
+}
+
+/// Find the polyatomic ion some compounds are a multiple of, with the given sign of charge
+fn find_polyatomic_ion(compounds: &[MoleculeCompound], is_cation: bool) -> Option<(Ion, u8)> {
+    POLYATOMIC_IONS
+        .iter()
+        .filter(|x| (x.0.get_charge().unwrap().0 > 0) == is_cation)
+        .filter_map(|x| multiple_of(compounds, &x.0.molecule).map(|y| (x.0.clone(), y)))
+        .next()
+}
+
+/// Find the monatomic anion some compounds are a multiple of, like the Cl⁻ in Cl₃
+fn find_monatomic_anion(compounds: &[MoleculeCompound]) -> Option<(Ion, u8)> {
+    if compounds.len() != 1 || compounds[0].atom.is_metal() {
+        return None;
+    }
+
+    let charge = compounds[0].atom.charge_by_group()?;
+    if charge.0 >= 0 {
+        return None;
+    }
+
+    let anion = Ion {
+        molecule: Molecule {
+            compounds: vec![MoleculeCompound {
+                atom: compounds[0].atom.clone(),
+                amount: 1,
+            }],
+        },
+        charge: Some(charge),
+    };
+
+    Some((anion, compounds[0].amount))
+}
+
+/// Find the ions some compounds consist of, taking them to be cations followed by anions
+fn find_ionic_parts(
+    cations: &[MoleculeCompound],
+    anions: &[MoleculeCompound],
+) -> Option<IonicParts> {
+    let polyatomic_cation = find_polyatomic_ion(cations, true);
+    let is_metal = cations.len() == 1 && cations[0].atom.is_metal();
+
+    if polyatomic_cation.is_none() && !is_metal {
+        return None;
+    }
+
+    // Peroxides are only considered if oxides don't fit, like in Na₂O₂ but not in MnO₂
+    let candidates = find_monatomic_anion(anions)
+        .into_iter()
+        .chain(find_polyatomic_ion(anions, false));
+
+    for (anion, amount) in candidates {
+        let negative_charge = -i32::from(anion.get_charge().unwrap().0) * i32::from(amount);
+
+        if let Some((ref cation, cation_amount)) = polyatomic_cation {
+            let positive_charge =
+                i32::from(cation.get_charge().unwrap().0) * i32::from(cation_amount);
+
+            if positive_charge == negative_charge {
+                return Some(IonicParts {
+                    cation: cation.clone(),
+                    cations: cation_amount,
+                    anion,
+                    anions: amount,
+                });
+            }
+
+            continue;
+        }
+
+        // The charge of a metal follows from the anions, like iron(III) in Fe₂(SO₄)₃
+        let metals = i32::from(cations[0].amount);
+        if negative_charge % metals != 0 {
+            continue;
+        }
+
+        let charge = negative_charge / metals;
+        let expected = cations[0].atom.charge_by_group();
+
+        if !(1..=8).contains(&charge) || expected.is_some_and(|x| i32::from(x.0) != charge) {
+            continue;
+        }
+
+        let cation = Ion {
+            molecule: Molecule {
+                compounds: vec![MoleculeCompound {
+                    atom: cations[0].atom.clone(),
+                    amount: 1,
+                }],
+            },
+            charge: Some(AtomCharge::from(charge as AtomChargeType)),
+        };
+
+        return Some(IonicParts {
+            cation,
+            cations: cations[0].amount,
+            anion,
+            anions: amount,
+        });
+    }
+
+    None
+}
+
+/// Check if a metal always has the same charge in its compounds,
+/// so its name doesn't need one
+fn has_fixed_charge(atom: &Atom) -> bool {
+    atom.charge_by_group().is_some() || ["Al", "Zn", "Ag", "Cd", "Ga", "Sc"].contains(&atom.symbol)
+}
+
+/// Check if an ion consists of more than one atom, so multiples of it need parentheses
+fn is_polyatomic(ion: &Ion) -> bool {
+    ion.molecule.compounds.len() > 1 || ion.molecule.compounds[0].amount > 1
+}
+
+/// Get the name of an ion as part of an ionic compound
+fn ionic_name(ion: &Ion) -> String {
+    if let Some(&(_, name)) = get_polyatomic_ion(&ion.molecule) {
+        return String::from(name);
+    }
+
+    if is_polyatomic(ion) {
+        return ion.molecule.name();
+    }
+
+    let atom = &ion.molecule.compounds[0].atom;
+    let charge = ion.get_charge().unwrap();
+
+    if charge.0 < 0 {
+        anion_name(atom.name)
+    } else if has_fixed_charge(atom) {
+        String::from(atom.name)
+    } else {
+        format!("{}({})", atom.name, number_to_roman(charge.0))
+    }
+}
+
+/// Get the formula of an amount of ions in an ionic compound, like (SO₄)₃ or Fe₂
+fn ionic_symbol(ion: &Ion, amount: u8) -> String {
+    if amount == 1 {
+        ion.molecule.symbol()
+    } else if is_polyatomic(ion) {
+        format!("({}){}", ion.molecule.symbol(), subscript(amount))
+    } else {
+        MoleculeCompound {
+            atom: ion.molecule.compounds[0].atom.clone(),
+            amount,
+        }
+        .symbol()
+    }
+}
+
+impl IonicParts {
+    /// Combine a cation and an anion into the neutral ionic compound with the lowest
+    /// whole-number ratio, like 2 Fe³⁺ and 3 SO₄²⁻
+    /// Returns None if the cation isn't positive or the anion isn't negative
+    pub fn from_ions(cation: &Ion, anion: &Ion) -> Option<IonicParts> {
+        let positive = i32::from(cation.get_charge()?.0);
+        let negative = -i32::from(anion.get_charge()?.0);
+
+        if positive <= 0 || negative <= 0 {
+            return None;
+        }
+
+        let divisor = gcd(positive, negative);
+
+        Some(IonicParts {
+            cation: cation.clone(),
+            cations: (negative / divisor) as u8,
+            anion: anion.clone(),
+            anions: (positive / divisor) as u8,
+        })
+    }
+
+    /// Get the molecule of the ionic compound, with the ions multiplied out
+    pub fn molecule(&self) -> Molecule {
+        let mut compounds = vec![];
+
+        for &(ion, amount) in &[(&self.cation, self.cations), (&self.anion, self.anions)] {
+            for compound in &ion.molecule.compounds {
+                compounds.push(MoleculeCompound {
+                    atom: compound.atom.clone(),
+                    amount: compound.amount * amount,
+                });
+            }
+        }
+
+        Molecule { compounds }
+    }
+
+    /// Check if the formula needs parentheses, like Fe₂(SO₄)₃ but not Na₂SO₄
+    fn has_groups(&self) -> bool {
+        (self.cations > 1 && is_polyatomic(&self.cation))
+            || (self.anions > 1 && is_polyatomic(&self.anion))
+    }
+
+    /// Get the formula, with multiples of polyatomic ions in parentheses, like Fe₂(SO₄)₃
+    pub fn symbol(&self) -> String {
+        ionic_symbol(&self.cation, self.cations) + &ionic_symbol(&self.anion, self.anions)
+    }
+
+    /// Get the name, after the cation and the anion, like iron(III) sulfate
+    pub fn name(&self) -> String {
+        format!("{} {}", ionic_name(&self.cation), ionic_name(&self.anion))
+    }
+
+    /// Get the ions an amount of the compound dissociates into when it dissolves
+    pub fn dissociate(&self, moles: &Moles) -> Vec<ContainerCompound<Ion>> {
+        vec![
+            ContainerCompound {
+                element: self.cation.clone(),
+                moles: moles.clone() * MolesType::from(self.cations),
+            },
+            ContainerCompound {
+                element: self.anion.clone(),
+                moles: moles.clone() * MolesType::from(self.anions),
+            },
+        ]
+    }
+}
+
 impl Molecule {
     /// Convert a string representation of a molecule into one
-    /// TODO: Parse parentheses, e.g.  Ca3(PO4)2
+    /// Groups in parentheses are multiplied out, e.g. Ca3(PO4)2 becomes Ca3P2O8
     pub fn from_string(string: &str) -> Option<Molecule> {
         let mut compounds = vec![];
 
         let mut token = String::new();
+        let mut chars = string.chars().peekable();
 
-        for c in string.chars() {
+        while let Some(c) = chars.next() {
             // Ignore whitespace
             if is_whitespace!(c) || is_separator!(c) {
                 continue;
             }
 
-            if is_upper!(c) && !token.is_empty() {
+            if (is_upper!(c) || c == '(') && !token.is_empty() {
                 let compound = MoleculeCompound::from_string(&token).unwrap();
 
                 compounds.push(compound);
                 token = String::new();
             }
 
+            if c == '(' {
+                let mut group = String::new();
+                let mut depth = 1;
+
+                for c in chars.by_ref() {
+                    match c {
+                        '(' => depth += 1,
+                        ')' => depth -= 1,
+                        _ => {}
+                    }
+
+                    if depth == 0 {
+                        break;
+                    }
+
+                    group.push(c);
+                }
+
+                // The group has to be closed
+                if depth != 0 {
+                    return None;
+                }
+
+                let mut amount: u8 = 0;
+                while let Some(&c) = chars.peek() {
+                    if !is_number!(c) {
+                        break;
+                    }
+
+                    amount = amount.checked_mul(10)?.checked_add(to_number!(c))?;
+                    chars.next();
+                }
+
+                // If no amount given, assume 1
+                if amount == 0 {
+                    amount = 1;
+                }
+
+                for compound in Molecule::from_string(&group)?.compounds {
+                    compounds.push(MoleculeCompound {
+                        atom: compound.atom,
+                        amount: compound.amount.checked_mul(amount)?,
+                    });
+                }
+
+                continue;
+            }
+
+            // A group has to be opened before it is closed
+            if c == ')' {
+                return None;
+            }
+
             token.push(c);
         }
 
@@ -59,6 +374,26 @@ impl Molecule {
             None
         }
     }
+
+    /// Find the cation and the anion this molecule consists of, if it is an ionic compound
+    /// The cation is either a polyatomic ion or a metal, whose charge follows from the anion,
+    /// and it can be written before or after the anion, like in CH3COONa
+    pub fn ionic_parts(&self) -> Option<IonicParts> {
+        // Try every way to split this molecule into a cation and an anion
+        for split in 1..self.compounds.len() {
+            let (first, last) = self.compounds.split_at(split);
+
+            if let Some(parts) = find_ionic_parts(first, last) {
+                return Some(parts);
+            }
+
+            if let Some(parts) = find_ionic_parts(last, first) {
+                return Some(parts);
+            }
+        }
+
+        None
+    }
 }
 
 impl MoleculeCompound {
@@ -104,6 +439,13 @@ impl MoleculeCompound {
 
 impl Properties for Molecule {
     fn symbol(&self) -> String {
+        // Multiples of polyatomic ions are grouped, like in Ca₃(PO₄)₂
+        if let Some(parts) = self.ionic_parts() {
+            if parts.has_groups() {
+                return parts.symbol();
+            }
+        }
+
         let mut symbol = String::new();
 
         for compound in &self.compounds {
@@ -116,6 +458,11 @@ impl Properties for Molecule {
     fn name(&self) -> String {
         let mut name = String::new();
 
+        // Ionic compounds are named after their ions, like iron(III) sulfate
+        if let Some(parts) = self.ionic_parts() {
+            return parts.name();
+        }
+
         // TODO: Add special cases
         // NOTE: https://www.youtube.com/watch?v=mlRhLicNo8Q
         for compound in &self.compounds {
@@ -69,6 +69,17 @@ pub fn number_to_greek(n: u8) -> String {
     }
 }
 
+/// Convert the name of an element into that of its anion (e.g. "chlorine" into "chloride")
+pub fn anion_name(name: &str) -> String {
+    for ending in &["ine", "ygen", "ogen", "orus", "ur", "ium", "on", "ic"] {
+        if let Some(stem) = name.strip_suffix(ending) {
+            return String::from(stem) + "ide";
+        }
+    }
+
+    String::from(name) + "ide"
+}
+
 /// Convert a number to roman notaion
 pub fn number_to_roman(n: i8) -> String {
     match n {
@@ -92,7 +103,7 @@ pub fn number_to_roman(n: i8) -> String {
         14 => String::from("XIV"),
         15 => String::from("XV"),
         16 => String::from("XVI"),
-        _ => panic!("{} uncalculatable", n.to_string()),
+        _ => panic!("{} uncalculatable", n),
     }
 }
 
@@ -110,7 +121,7 @@ pub fn subscript(n: u8) -> String {
         8 => String::from("₈"),
         9 => String::from("₉"),
         n if n >= 10 => subscript(n / 10) + &subscript(n % 10),
-        _ => panic!("{} can't be converted to subscript.", n.to_string()),
+        _ => panic!("{} can't be converted to subscript.", n),
     }
 }
 
@@ -134,7 +145,7 @@ pub fn superscript(n: i8) -> String {
         8 => String::from("⁸"),
         9 => String::from("⁹"),
         n if n >= 10 => superscript(n / 10) + &superscript(n % 10),
-        _ => panic!("{} can't be converted to superscript.", n.to_string()),
+        _ => panic!("{} can't be converted to superscript.", n),
     }
 }
 
@@ -169,3 +180,33 @@ pub fn ion_superscript(ac: &AtomCharge) -> String {
         _ => String::from("^{0}"),
     }
 } */
+
+/// Convert a unicode representation (e.g. "SO₄²⁻") into the plain notation
+/// used by the parsers (e.g. "SO4;2-")
+/// This is the inverse of `subscript` and `ion_superscript`
+pub fn from_unicode_notation(string: &str) -> String {
+    let mut plain = String::new();
+    let mut charge = String::new();
+
+    for c in string.chars() {
+        match c {
+            '₀'..='₉' => plain.push((b'0' + (c as u32 - '₀' as u32) as u8) as char),
+            '⁰' => charge.push('0'),
+            '¹' => charge.push('1'),
+            '²' => charge.push('2'),
+            '³' => charge.push('3'),
+            '⁴'..='⁹' => charge.push((b'4' + (c as u32 - '⁴' as u32) as u8) as char),
+            '⁺' => charge.push('+'),
+            '⁻' => charge.push('-'),
+            _ => plain.push(c),
+        }
+    }
+
+    // The electron is parsed without charge
+    if !charge.is_empty() && plain != "e" {
+        plain.push(';');
+        plain += &charge;
+    }
+
+    plain
+}
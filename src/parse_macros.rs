@@ -1,21 +1,21 @@
 #[macro_export]
 macro_rules! is_upper {
     ($c: expr) => {
-        $c >= 'A' && $c <= 'Z'
+        $c.is_ascii_uppercase()
     };
 }
 
 #[macro_export]
 macro_rules! is_lower {
     ($c: expr) => {
-        $c >= 'a' && $c <= 'z'
+        $c.is_ascii_lowercase()
     };
 }
 
 #[macro_export]
 macro_rules! is_number {
     ($c: expr) => {
-        $c >= '0' && $c <= '9'
+        $c.is_ascii_digit()
     };
 }
 
@@ -51,6 +51,6 @@ macro_rules! is_vowel {
 /// converts a single char into a number
 macro_rules! to_number {
     ($c: expr) => {
-        ($c as u8) - ('0' as u8)
+        ($c as u8) - b'0'
     };
 }
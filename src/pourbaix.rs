@@ -0,0 +1,388 @@
+use atom::Atom;
+use constants::*;
+use data_sep::*;
+use gibbs::*;
+use ion::Ion;
+use reaction::ElemReaction;
+use redox::electrons_in;
+use trait_properties::Properties;
+
+/// Default pH range of a diagram
+pub const PH_RANGE: (f64, f64) = (0.0, 14.0);
+
+/// Default potential range of a diagram [ V ]
+pub const POTENTIAL_RANGE: (f64, f64) = (-2.0, 2.0);
+
+/// Two values closer than this are considered equal [ V ]
+const EPSILON: f64 = 1e-9;
+
+#[derive(Debug, Clone, PartialEq)]
+/// The area of a Pourbaix diagram in which a species predominates
+pub struct PourbaixRegion {
+    /// The predominant species
+    pub species: Ion,
+
+    /// The corners of the region, as (pH, E) in counter-clockwise order
+    pub polygon: Vec<(f64, f64)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A line in a Pourbaix diagram, along which two species are in equilibrium
+pub struct PourbaixBoundary {
+    /// The species on both sides of the line
+    pub between: (Ion, Ion),
+
+    /// The start of the line, as (pH, E)
+    pub from: (f64, f64),
+
+    /// The end of the line, as (pH, E)
+    pub to: (f64, f64),
+}
+
+#[derive(Debug, Clone)]
+/// A potential-pH diagram of an element in water
+pub struct PourbaixDiagram {
+    /// The element this diagram is about
+    pub element: Atom,
+
+    /// The activity of the dissolved species
+    pub activity: f64,
+
+    /// The pH range of the diagram
+    pub ph_range: (f64, f64),
+
+    /// The potential range of the diagram [ V ]
+    pub potential_range: (f64, f64),
+
+    /// The predominance regions
+    pub regions: Vec<PourbaixRegion>,
+
+    /// The lines between the regions
+    pub boundaries: Vec<PourbaixBoundary>,
+
+    /// The energy of formation of every species from the element and water,
+    /// per atom of the element, as linear function a + b * pH + c * E [ V ]
+    energies: Vec<(Ion, [f64; 3])>,
+}
+
+/// Evaluate a linear energy function at a point
+fn evaluate(energy: &[f64; 3], point: (f64, f64)) -> f64 {
+    energy[0] + energy[1] * point.0 + energy[2] * point.1
+}
+
+/// Clip a convex polygon by the half-plane where a linear function is at most 0
+/// (Sutherland-Hodgman)
+fn clip(polygon: &[(f64, f64)], function: &[f64; 3]) -> Vec<(f64, f64)> {
+    let mut clipped = vec![];
+
+    for (i, &current) in polygon.iter().enumerate() {
+        let next = polygon[(i + 1) % polygon.len()];
+
+        let current_value = evaluate(function, current);
+        let next_value = evaluate(function, next);
+
+        if current_value <= EPSILON {
+            clipped.push(current);
+        }
+
+        if (current_value < -EPSILON && next_value > EPSILON)
+            || (current_value > EPSILON && next_value < -EPSILON)
+        {
+            let t = current_value / (current_value - next_value);
+
+            clipped.push((
+                current.0 + t * (next.0 - current.0),
+                current.1 + t * (next.1 - current.1),
+            ));
+        }
+    }
+
+    clipped
+}
+
+/// Calculate the area of a polygon (shoelace formula)
+fn area(polygon: &[(f64, f64)]) -> f64 {
+    let mut area = 0.0;
+
+    for (i, &(x1, y1)) in polygon.iter().enumerate() {
+        let (x2, y2) = polygon[(i + 1) % polygon.len()];
+        area += x1 * y2 - x2 * y1;
+    }
+
+    area.abs() / 2.0
+}
+
+/// Get the line a half-reaction with H⁺ follows through a diagram
+fn water_line(symbol: &str, ph_range: (f64, f64)) -> PourbaixBoundary {
+    let reaction = ElemReaction::<Ion>::ion_from_string(symbol).unwrap();
+    let sep = f64::from(get_sep(&reaction).unwrap().0);
+
+    let protons = Ion::from_string("H;1").unwrap();
+    let count = |ion: &Ion| -> f64 {
+        reaction
+            .lhs
+            .compounds
+            .iter()
+            .filter(|x| &x.element == ion)
+            .map(|x| f64::from(x.amount))
+            .sum()
+    };
+
+    let electrons = f64::from(electrons_in(&reaction));
+    let slope =
+        count(&protons) / electrons * GAS_CONSTANT * STANDARD_TEMPERATURE * 10f64.ln() / FARADAY;
+
+    let oxidised = reaction.lhs.compounds[0].element.clone();
+    let reduced = reaction.rhs.compounds[0].element.clone();
+
+    PourbaixBoundary {
+        between: (oxidised, reduced),
+        from: (ph_range.0, sep - slope * ph_range.0),
+        to: (ph_range.1, sep - slope * ph_range.1),
+    }
+}
+
+impl PourbaixDiagram {
+    /// Generate the diagram of an element for the default pH and potential range
+    /// Returns None if no data of the element is known
+    pub fn new(element: &Atom, activity: f64) -> Option<PourbaixDiagram> {
+        PourbaixDiagram::from_range(element, activity, PH_RANGE, POTENTIAL_RANGE)
+    }
+
+    /// Generate the diagram of an element for given pH and potential range
+    /// All dissolved (charged) species are taken to have the given activity,
+    /// all neutral species are taken to be pure solids, liquids or gases
+    pub fn from_range(
+        element: &Atom,
+        activity: f64,
+        ph_range: (f64, f64),
+        potential_range: (f64, f64),
+    ) -> Option<PourbaixDiagram> {
+        let mut energies: Vec<(Ion, [f64; 3])> = get_species_energies(element, true)
+            .into_iter()
+            .map(|x| {
+                let energy = x.formation_function(activity);
+                (x.species, energy)
+            })
+            .collect();
+
+        if energies.is_empty() {
+            return None;
+        }
+
+        energies.sort_by_key(|x| x.0.symbol());
+
+        let bounding_box = vec![
+            (ph_range.0, potential_range.0),
+            (ph_range.1, potential_range.0),
+            (ph_range.1, potential_range.1),
+            (ph_range.0, potential_range.1),
+        ];
+
+        // Every region is where that species has the lowest energy of all
+        let mut regions = vec![];
+        for (ion, energy) in &energies {
+            let mut polygon = bounding_box.clone();
+
+            for (other, other_energy) in &energies {
+                if other == ion || polygon.is_empty() {
+                    continue;
+                }
+
+                let difference = [
+                    energy[0] - other_energy[0],
+                    energy[1] - other_energy[1],
+                    energy[2] - other_energy[2],
+                ];
+
+                polygon = clip(&polygon, &difference);
+            }
+
+            if polygon.len() >= 3 && area(&polygon) > EPSILON {
+                regions.push(PourbaixRegion {
+                    species: ion.clone(),
+                    polygon,
+                });
+            }
+        }
+
+        let mut diagram = PourbaixDiagram {
+            element: element.clone(),
+            activity,
+            ph_range,
+            potential_range,
+            regions,
+            boundaries: vec![],
+            energies,
+        };
+
+        diagram.boundaries = diagram.find_boundaries();
+
+        Some(diagram)
+    }
+
+    /// Get the energy function of a species
+    fn energy_of(&self, ion: &Ion) -> &[f64; 3] {
+        &self.energies.iter().find(|x| &x.0 == ion).unwrap().1
+    }
+
+    /// Find the region edges that are shared by two regions
+    fn find_boundaries(&self) -> Vec<PourbaixBoundary> {
+        let mut boundaries = vec![];
+
+        for (i, region) in self.regions.iter().enumerate() {
+            let energy = self.energy_of(&region.species);
+
+            for (j, &from) in region.polygon.iter().enumerate() {
+                let to = region.polygon[(j + 1) % region.polygon.len()];
+
+                for other in &self.regions[i + 1..] {
+                    let other_energy = self.energy_of(&other.species);
+
+                    let on_line = |point: (f64, f64)| {
+                        (evaluate(energy, point) - evaluate(other_energy, point)).abs() < 1e-6
+                    };
+
+                    if on_line(from) && on_line(to) {
+                        boundaries.push(PourbaixBoundary {
+                            between: (region.species.clone(), other.species.clone()),
+                            from,
+                            to,
+                        });
+                    }
+                }
+            }
+        }
+
+        boundaries
+    }
+
+    /// Get the species that predominates at a given pH and potential
+    pub fn predominant_species(&self, ph: f64, potential: f64) -> Option<&Ion> {
+        let mut best: Option<(&Ion, f64)> = None;
+
+        for region in &self.regions {
+            let energy = evaluate(self.energy_of(&region.species), (ph, potential));
+
+            if best.is_none_or(|x| energy < x.1) {
+                best = Some((&region.species, energy));
+            }
+        }
+
+        best.map(|x| x.0)
+    }
+
+    /// Get the stability limits of water: the O₂/H₂O and the H⁺/H₂ line
+    pub fn water_lines(&self) -> Vec<PourbaixBoundary> {
+        vec![
+            water_line("O2 + 4H;1 + 4e <> 2H2O", self.ph_range),
+            water_line("2H;1 + 2e <> H2", self.ph_range),
+        ]
+    }
+
+    /// Export the regions as CSV, with one row per corner
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("species,pH,E\n");
+
+        for region in &self.regions {
+            for &(ph, potential) in &region.polygon {
+                csv += &format!("{},{:.4},{:.4}\n", region.species.symbol(), ph, potential);
+            }
+        }
+
+        csv
+    }
+
+    /// Export the boundaries as CSV, with one row per line
+    pub fn boundaries_to_csv(&self) -> String {
+        let mut csv = String::from("species_a,species_b,pH_from,E_from,pH_to,E_to\n");
+
+        for boundary in &self.boundaries {
+            csv += &format!(
+                "{},{},{:.4},{:.4},{:.4},{:.4}\n",
+                boundary.between.0.symbol(),
+                boundary.between.1.symbol(),
+                boundary.from.0,
+                boundary.from.1,
+                boundary.to.0,
+                boundary.to.1
+            );
+        }
+
+        csv
+    }
+
+    /// Export the diagram as an SVG image
+    pub fn to_svg(&self) -> String {
+        let (width, height, margin) = (640.0, 480.0, 50.0);
+
+        let x = |ph: f64| {
+            margin
+                + (ph - self.ph_range.0) / (self.ph_range.1 - self.ph_range.0)
+                    * (width - 2.0 * margin)
+        };
+        let y = |potential: f64| {
+            height
+                - margin
+                - (potential - self.potential_range.0)
+                    / (self.potential_range.1 - self.potential_range.0)
+                    * (height - 2.0 * margin)
+        };
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">\n",
+            width, height
+        );
+
+        for (i, region) in self.regions.iter().enumerate() {
+            let points = region
+                .polygon
+                .iter()
+                .map(|&(ph, potential)| format!("{:.1},{:.1}", x(ph), y(potential)))
+                .collect::<Vec<String>>()
+                .join(" ");
+
+            let hue = i * 360 / self.regions.len();
+            svg += &format!(
+                "<polygon points=\"{}\" fill=\"hsl({}, 60%, 80%)\" stroke=\"black\"/>\n",
+                points, hue
+            );
+
+            // Label the region at its centroid
+            let corners = region.polygon.len() as f64;
+            let ph = region.polygon.iter().map(|x| x.0).sum::<f64>() / corners;
+            let potential = region.polygon.iter().map(|x| x.1).sum::<f64>() / corners;
+
+            svg += &format!(
+                "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{}</text>\n",
+                x(ph),
+                y(potential),
+                region.species.symbol()
+            );
+        }
+
+        for line in self.water_lines() {
+            svg += &format!(
+                "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"blue\" stroke-dasharray=\"6,4\"/>\n",
+                x(line.from.0),
+                y(line.from.1),
+                x(line.to.0),
+                y(line.to.1)
+            );
+        }
+
+        svg += &format!(
+            "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\">pH</text>\n",
+            width / 2.0,
+            height - margin / 4.0
+        );
+        svg += &format!(
+            "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\">E (V)</text>\n",
+            margin / 2.0,
+            margin / 2.0
+        );
+        svg += "</svg>\n";
+
+        svg
+    }
+}
@@ -0,0 +1,328 @@
+use container::*;
+use data_ksp::KSPMAP;
+use gibbs::written_charge;
+use ion::Ion;
+use math::gcd;
+use molecule::IonicParts;
+use reaction::{ElemReaction, ReactionCompound, ReactionSide};
+use trait_properties::Properties;
+use types::*;
+
+/// Supersaturations (log10 Q / Ksp) below this are considered to be in equilibrium
+const EPSILON: f64 = 1e-9;
+
+/// The maximum amount of precipitations done at once, in case salts keep competing
+const MAX_PRECIPITATIONS: usize = 100;
+
+// Reference: Binas 6th edition, table 45A
+/// Solubility rules: anions, whether their salts are soluble, and the cations for which
+/// this is the other way around. Salts of group 1 metals and ammonium are always soluble
+const SOLUBILITY_RULES: &[(&[&str], bool, &[&str])] = &[
+    (&["NO3;-", "CH3COO;-", "ClO4;-", "ClO3;-"], true, &[]),
+    (
+        &["Cl;-", "Br;-", "I;-"],
+        true,
+        &["Ag;+", "Pb;2+", "Hg2;2+", "Cu;+"],
+    ),
+    (
+        &["SO4;2-"],
+        true,
+        &["Ba;2+", "Pb;2+", "Ca;2+", "Ag;+", "Hg2;2+"],
+    ),
+    (&["F;-"], true, &["Ca;2+", "Mg;2+", "Ba;2+", "Pb;2+"]),
+    (&["OH;-"], false, &["Ba;2+", "Ca;2+"]),
+    (
+        &["CO3;2-", "PO4;3-", "S;2-", "SO3;2-", "CrO4;2-", "C2O4;2-"],
+        false,
+        &[],
+    ),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+/// A salt that precipitated out of a solution
+pub struct Precipitate {
+    /// The dissolution of the salt, with the salt on the left-hand side
+    pub dissolution: ElemReaction<Ion>,
+
+    /// The pKsp of the salt, or None if it is insoluble according to the solubility rules
+    pub pksp: Option<PK>,
+
+    /// The amount of moles that precipitated
+    pub moles: Moles,
+}
+
+impl Precipitate {
+    /// Get the salt that precipitated
+    pub fn salt(&self) -> Ion {
+        self.dissolution.lhs.compounds[0].element.clone()
+    }
+}
+
+/// Check if an ion matches any of the given symbols
+fn is_any_of(ion: &Ion, symbols: &[&str]) -> bool {
+    symbols.iter().any(|x| &Ion::from_string(x).unwrap() == ion)
+}
+
+/// Check if a salt of given ions is soluble in water, according to the solubility rules
+/// Returns None if no rule is known for this combination
+pub fn is_soluble(cation: &Ion, anion: &Ion) -> Option<bool> {
+    // Salts of group 1 metals and ammonium are soluble
+    let is_alkali = cation.molecule.compounds.len() == 1
+        && cation.molecule.compounds[0].atom.group == AtomGroup::from(1)
+        && cation.molecule.compounds[0].atom.number != AtomNumber::from(1);
+
+    if is_alkali || is_any_of(cation, &["NH4;+"]) {
+        return Some(true);
+    }
+
+    for &(anions, soluble, exceptions) in SOLUBILITY_RULES {
+        if is_any_of(anion, anions) {
+            return Some(soluble != is_any_of(cation, exceptions));
+        }
+    }
+
+    None
+}
+
+/// Create the dissolution of the neutral salt of two ions
+pub fn salt_dissolution(cation: &Ion, anion: &Ion) -> ElemReaction<Ion> {
+    let cation_charge = written_charge(cation) as i32;
+    let anion_charge = -written_charge(anion) as i32;
+    let divisor = gcd(cation_charge, anion_charge);
+
+    let cations = (anion_charge / divisor) as u16;
+    let anions = (cation_charge / divisor) as u16;
+
+    let parts = IonicParts {
+        cation: cation.clone(),
+        cations: cations as u8,
+        anion: anion.clone(),
+        anions: anions as u8,
+    };
+
+    let salt = Ion {
+        molecule: parts.molecule(),
+        charge: Some(AtomCharge::from(0)),
+    };
+
+    ElemReaction {
+        lhs: ReactionSide {
+            compounds: vec![ReactionCompound {
+                element: salt,
+                amount: 1,
+            }],
+        },
+        rhs: ReactionSide {
+            compounds: vec![
+                ReactionCompound {
+                    element: cation.clone(),
+                    amount: cations,
+                },
+                ReactionCompound {
+                    element: anion.clone(),
+                    amount: anions,
+                },
+            ],
+        },
+        is_equilibrium: true,
+    }
+}
+
+impl Container<Ion> {
+    /// Get all salts that could precipitate from the ions in this container,
+    /// together with their pKsp (None if insoluble according to the solubility rules)
+    pub fn precipitation_candidates(&self) -> Vec<(ElemReaction<Ion>, Option<PK>)> {
+        let is_present = |ion: &Ion| self.moles_of(ion) > Moles::from(0.0);
+
+        let mut candidates: Vec<(ElemReaction<Ion>, Option<PK>)> = KSPMAP
+            .iter()
+            .filter(|(reaction, _)| {
+                reaction
+                    .rhs
+                    .compounds
+                    .iter()
+                    .all(|x| is_present(&x.element))
+            })
+            .map(|(reaction, &pksp)| (reaction.clone(), Some(pksp)))
+            .collect();
+
+        // Use the solubility rules for the salts without data
+        let ions: Vec<Ion> = self
+            .contents
+            .iter()
+            .map(|x| x.element.clone())
+            .filter(|x| is_present(x))
+            .collect();
+
+        // H⁺ forms acids instead of salts
+        let protons = Ion::from_string("H;1").unwrap();
+
+        for cation in ions
+            .iter()
+            .filter(|x| written_charge(x) > 0.0 && *x != &protons)
+        {
+            for anion in ions.iter().filter(|x| written_charge(x) < 0.0) {
+                let has_data = candidates.iter().any(|(reaction, _)| {
+                    reaction.rhs.compounds.iter().any(|x| &x.element == cation)
+                        && reaction.rhs.compounds.iter().any(|x| &x.element == anion)
+                });
+
+                if !has_data && is_soluble(cation, anion) == Some(false) {
+                    candidates.push((salt_dissolution(cation, anion), None));
+                }
+            }
+        }
+
+        // Make sure the outcome doesn't depend on the order of the map
+        candidates.sort_by_key(|(reaction, _)| reaction.lhs.symbol());
+
+        candidates
+    }
+
+    /// Calculate the supersaturation log10(Q / Ksp) of a salt in this container
+    /// Returns None if the container doesn't contain water
+    pub fn supersaturation(&self, dissolution: &ElemReaction<Ion>, pksp: PK) -> Option<f64> {
+        let mut log_q = 0.0;
+        for compound in &dissolution.rhs.compounds {
+            let concentration = self.concentration_of(&compound.element)?;
+
+            log_q += f64::from(compound.amount) * concentration.log10();
+        }
+
+        Some(log_q + f64::from(pksp.0))
+    }
+
+    /// Get the amount of a salt that has to precipitate for the solution to be saturated [ mol ]
+    fn precipitation_extent(&self, dissolution: &ElemReaction<Ion>, pksp: Option<PK>) -> f64 {
+        // The amount of ions that run out first
+        let maximum = dissolution
+            .rhs
+            .compounds
+            .iter()
+            .map(|x| self.moles_of(&x.element).0 / f64::from(x.amount))
+            .fold(f64::INFINITY, f64::min);
+
+        let pksp = match pksp {
+            Some(pksp) => f64::from(pksp.0),
+            None => return maximum,
+        };
+
+        let volume = self.solution_volume().unwrap();
+
+        // Σ ν log10(c - ν x) + pKsp decreases with x, and is 0 at saturation
+        let supersaturation = |extent: f64| -> f64 {
+            dissolution
+                .rhs
+                .compounds
+                .iter()
+                .map(|x| {
+                    let amount = f64::from(x.amount);
+                    let left = self.moles_of(&x.element).0 - amount * extent;
+
+                    amount * (left.max(0.0) / volume).log10()
+                })
+                .sum::<f64>()
+                + pksp
+        };
+
+        let (mut low, mut high) = (0.0, maximum);
+        for _ in 0..100 {
+            let middle = (low + high) / 2.0;
+
+            if supersaturation(middle) > 0.0 {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+
+        low
+    }
+
+    /// Get the salts that would precipitate, without changing the container
+    pub fn get_precipitates(&self) -> Vec<Precipitate> {
+        let mut container = self.clone();
+
+        container.precipitate()
+    }
+
+    /// Let all salts precipitate until the solution is saturated with all of them
+    /// The least soluble salts (the most supersaturated) precipitate first.
+    /// Salts without Ksp data that are insoluble according to the solubility rules precipitate
+    /// completely. Returns the salts that precipitated, which is empty without water
+    pub fn precipitate(&mut self) -> Vec<Precipitate> {
+        let mut precipitates: Vec<Precipitate> = vec![];
+
+        if self.solution_volume().is_none() {
+            return precipitates;
+        }
+
+        for _ in 0..MAX_PRECIPITATIONS {
+            // Find the most supersaturated salt
+            let mut best: Option<(ElemReaction<Ion>, Option<PK>, f64)> = None;
+
+            for (dissolution, pksp) in self.precipitation_candidates() {
+                let supersaturation = match pksp {
+                    Some(pksp) => self.supersaturation(&dissolution, pksp).unwrap(),
+                    None => f64::INFINITY,
+                };
+
+                if supersaturation > EPSILON && best.as_ref().is_none_or(|x| supersaturation > x.2)
+                {
+                    best = Some((dissolution, pksp, supersaturation));
+                }
+            }
+
+            let (dissolution, pksp) = match best {
+                Some((dissolution, pksp, _)) => (dissolution, pksp),
+                None => break,
+            };
+
+            let extent = self.precipitation_extent(&dissolution, pksp);
+            if extent <= 0.0 {
+                break;
+            }
+
+            // Move the ions out of the solution into the salt
+            let mut ions: Vec<ContainerCompound<Ion>> = dissolution
+                .rhs
+                .compounds
+                .iter()
+                .map(|x| ContainerCompound {
+                    element: x.element.clone(),
+                    moles: Moles::from(extent * f64::from(x.amount)),
+                })
+                .collect();
+
+            for ion in &mut ions {
+                // Prevent rounding errors from requiring more than available
+                let available = self.moles_of(&ion.element);
+                if ion.moles > available {
+                    ion.moles = available;
+                }
+            }
+
+            self.remove_elements(&ions).unwrap();
+            self.add_undissolved(&[ContainerCompound {
+                element: dissolution.lhs.compounds[0].element.clone(),
+                moles: Moles::from(extent),
+            }]);
+
+            if let Some(precipitate) = precipitates
+                .iter_mut()
+                .find(|x| x.dissolution == dissolution)
+            {
+                precipitate.moles += Moles::from(extent);
+                continue;
+            }
+
+            precipitates.push(Precipitate {
+                dissolution,
+                pksp,
+                moles: Moles::from(extent),
+            });
+        }
+
+        precipitates
+    }
+}
@@ -52,7 +52,7 @@ impl<E: Element> ElemReaction<E> {
 
         for c in string.chars() {
             if c == '<' || c == '>' || c == '⇌' || c == '→' {
-                if lhs == None {
+                if lhs.is_none() {
                     lhs = ReactionSide::<Ion>::ion_from_string(&token);
                     token = String::new();
                 }
@@ -93,7 +93,7 @@ impl<E: Element> ElemReaction<E> {
 
         for c in string.chars() {
             if c == '<' || c == '>' || c == '⇌' || c == '→' {
-                if lhs == None {
+                if lhs.is_none() {
                     lhs = ReactionSide::<Molecule>::molecule_from_string(&token);
                     token = String::new();
                 }
@@ -147,6 +147,31 @@ impl<E: Element> ElemReaction<E> {
         mem::swap(&mut self.lhs, &mut self.rhs);
         self
     }
+
+    /// Merge compounds that occur more than once on a side,
+    /// and cancel out compounds that occur on both sides
+    pub fn simplify(self) -> Self {
+        let mut lhs = self.lhs.merge();
+        let mut rhs = self.rhs.merge();
+
+        for l_compound in &mut lhs.compounds {
+            if let Some(r_compound) = rhs.compounds.iter_mut().find(|x| *x == l_compound) {
+                let common = l_compound.amount.min(r_compound.amount);
+
+                l_compound.amount -= common;
+                r_compound.amount -= common;
+            }
+        }
+
+        lhs.compounds.retain(|x| x.amount > 0);
+        rhs.compounds.retain(|x| x.amount > 0);
+
+        ElemReaction {
+            lhs,
+            rhs,
+            is_equilibrium: self.is_equilibrium,
+        }
+    }
 }
 
 impl<E: Element> ReactionSide<E> {
@@ -226,28 +251,47 @@ impl<E: Element> ReactionSide<E> {
         }
     }
 
+    /// Merge compounds that occur more than once into a single compound
+    pub fn merge(self) -> Self {
+        let mut compounds: Vec<ReactionCompound<E>> = vec![];
+
+        for compound in self.compounds {
+            if let Some(existing) = compounds.iter_mut().find(|x| **x == compound) {
+                existing.amount += compound.amount;
+            } else {
+                compounds.push(compound);
+            }
+        }
+
+        ReactionSide { compounds }
+    }
+
     /// Calculate the total charge of this reaction side
     pub fn total_charge(&self) -> AtomCharge {
         let mut total_charge = AtomCharge::from(0);
 
         for compound in &self.compounds {
             if let Some(charge) = compound.element.get_charge() {
-                total_charge += charge;
+                total_charge += charge * compound.amount as AtomChargeType;
             }
         }
 
         total_charge
     }
 
-    /// Calculate the energy this side has
+    /// Calculate the energy this side has [ J ]
+    /// The SEF data is in kJ, but is converted to J, like the Gibbs energy of a cell
+    /// and the available energy of a container it is compared with
     pub fn energy(&self) -> Energy {
         let mut energy = 0.0;
 
         for compound in &self.compounds {
             let sef = get_sef(&compound.element.clone().get_ion().unwrap());
 
-            if sef.is_some() {
-                energy += EnergyType::from(SEFType::from(sef.unwrap()))
+            if let Some(sef) = sef {
+                // [ kJ ] -> [ J ]
+                energy += EnergyType::from(SEFType::from(sef))
+                    * 1000.0
                     * EnergyType::from(compound.amount);
             } else {
                 let mol = compound.element.clone().get_molecule().unwrap();
@@ -328,11 +372,7 @@ impl<E: Element> ReactionCompound<E> {
             amount = 1;
         }
 
-        if let Some(element) = element {
-            Some(ReactionCompound { amount, element })
-        } else {
-            None
-        }
+        element.map(|element| ReactionCompound { amount, element })
     }
 
     /// Convert a string representation of a reaction compound into one
@@ -363,11 +403,7 @@ impl<E: Element> ReactionCompound<E> {
             amount = 1;
         }
 
-        if let Some(element) = element {
-            Some(ReactionCompound { amount, element })
-        } else {
-            None
-        }
+        element.map(|element| ReactionCompound { amount, element })
     }
 }
 
@@ -385,12 +421,10 @@ impl<E: Element> Reaction<E> for ElemReaction<E> {
         }
 
         for (atom_number, l_amount) in total_left {
-            let r_amount: u16;
-
-            match total_right.get(&atom_number) {
-                Some(&x) => r_amount = x,
-                None => r_amount = 0,
-            }
+            let r_amount: u16 = match total_right.get(&atom_number) {
+                Some(&x) => x,
+                None => 0,
+            };
 
             if r_amount == 0 {
                 println!("It's impossible to make this reaction work: {}", self);
@@ -398,13 +432,7 @@ impl<E: Element> Reaction<E> for ElemReaction<E> {
             }
 
             if l_amount != r_amount {
-                let difference = {
-                    if l_amount > r_amount {
-                        l_amount - r_amount
-                    } else {
-                        r_amount - l_amount
-                    }
-                };
+                let difference = l_amount.abs_diff(r_amount);
 
                 if difference > 0 {
                     // Increase right side
@@ -1,4 +1,8 @@
+use cell::GalvanicCell;
+use container::Container;
+use data_ions::HYDROXIDE;
 use data_sep::*;
+use electron::is_electron;
 use ion::Ion;
 use math::gcd;
 use reaction::{ElemReaction, ReactionCompound, ReactionSide};
@@ -7,6 +11,39 @@ use trait_properties::Properties;
 use trait_reaction::Reaction;
 use types::*;
 
+/// Get the amount of electrons transferred in a half-reaction
+pub fn electrons_in(half_reaction: &ElemReaction<Ion>) -> u16 {
+    half_reaction
+        .lhs
+        .compounds
+        .iter()
+        .chain(half_reaction.rhs.compounds.iter())
+        .filter(|x| is_electron(&x.element))
+        .map(|x| x.amount)
+        .sum()
+}
+
+/// Check if an ion is part of the medium a half-reaction takes place in (H⁺, OH⁻ or H₂O)
+pub fn is_medium(ion: &Ion) -> bool {
+    let symbol = ion.symbol();
+
+    symbol == "H⁺" || symbol == "OH⁻" || symbol == "H₂O"
+}
+
+/// Write a half-reaction as a reduction, with the electrons on the left-hand side
+pub fn as_reduction(half_reaction: ElemReaction<Ion>) -> ElemReaction<Ion> {
+    if half_reaction
+        .rhs
+        .compounds
+        .iter()
+        .any(|x| is_electron(&x.element))
+    {
+        half_reaction.swap()
+    } else {
+        half_reaction
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Clone)]
 /// A Redox reaction
 pub struct RedoxReaction {
@@ -28,8 +65,14 @@ impl Reaction<Ion> for RedoxReaction {
         get_sep(&self.oxidator) > get_sep(&self.reductor) && self.elem_reaction().is_valid()
     }
 
+    /// The Gibbs free energy (ΔG = -nFE) of the cell formed by both half-reactions
+    /// Falls back to the SEF values if one of them has no known SEP
     fn energy_cost(&self) -> Energy {
-        self.reductor.energy_cost() + self.oxidator.energy_cost()
+        if let Some(gibbs) = GalvanicCell::from_redox(self).and_then(|x| x.gibbs_energy()) {
+            gibbs
+        } else {
+            self.elem_reaction().energy_cost()
+        }
     }
 
     fn elem_reaction(&self) -> ElemReaction<Ion> {
@@ -90,6 +133,7 @@ impl Reaction<Ion> for RedoxReaction {
 
             is_equilibrium: true,
         }
+        .simplify()
     }
 }
 
@@ -124,3 +168,139 @@ impl Properties for RedoxReaction {
         false
     }
 }
+
+#[derive(Debug, Clone, PartialEq)]
+/// A pairing of an oxidator and a reductor that could react in a container
+pub struct RedoxCandidate {
+    /// The redox reaction of this pairing
+    pub redox: RedoxReaction,
+
+    /// The balanced total reaction
+    pub reaction: ElemReaction<Ion>,
+
+    /// The cell potential, the driving force of this pairing
+    pub emf: SEP,
+
+    /// The reactant that runs out first, if the reaction can take place
+    pub limiting: Option<Ion>,
+
+    /// The maximum amount of moles the total reaction can proceed
+    pub extent: Moles,
+
+    /// The co-reactants (H⁺, OH⁻ or H₂O) the container is missing for this pairing
+    pub missing: Vec<Ion>,
+}
+
+impl RedoxCandidate {
+    /// Check if the container has everything needed for this pairing
+    pub fn is_feasible(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+/// Check if the container holds all species on a side of a half-reaction,
+/// ignoring electrons and the medium
+fn has_main_species(side: &ReactionSide<Ion>, container: &Container<Ion>) -> bool {
+    side.compounds.iter().all(|x| {
+        is_electron(&x.element)
+            || is_medium(&x.element)
+            || container.moles_of(&x.element) > Moles::from(0.0)
+    })
+}
+
+/// Get every pairing of an oxidator and a reductor in the container that has a positive
+/// cell potential, sorted by driving force (highest first).
+/// Pairings that are only missing a co-reactant (H⁺, OH⁻ or H₂O) are included as well,
+/// see `RedoxCandidate::is_feasible`
+pub fn get_redox_candidates(container: &Container<Ion>) -> Vec<RedoxCandidate> {
+    let mut candidates = vec![];
+
+    let proton = Ion::from_string("H;+").unwrap();
+
+    for (oxidator, &oxi_sep) in SEPMAP.iter() {
+        if !has_main_species(&oxidator.lhs, container) {
+            continue;
+        }
+
+        for (reductor, &red_sep) in SEPMAP.iter() {
+            let emf = oxi_sep - red_sep;
+
+            if emf <= SEP::from(0.0) || !has_main_species(&reductor.rhs, container) {
+                continue;
+            }
+
+            let redox = RedoxReaction {
+                oxidator: oxidator.clone(),
+                reductor: reductor.clone().swap(),
+            };
+            let reaction = redox.elem_reaction();
+
+            // Reactions between the medium alone (like H⁺ + OH⁻) are no redox pairings
+            if reaction.lhs.compounds.iter().all(|x| is_medium(&x.element)) {
+                continue;
+            }
+
+            // Couples in acid and in base can't be combined
+            let consumes = |ion: &Ion| reaction.lhs.compounds.iter().any(|x| x.element == *ion);
+
+            if consumes(&proton) && consumes(&HYDROXIDE) {
+                continue;
+            }
+
+            // The total reaction has to be balanced
+            if !reaction.is_valid() || reaction.lhs.total_charge() != reaction.rhs.total_charge() {
+                continue;
+            }
+
+            let mut missing = vec![];
+            let mut limiting = None;
+            let mut extent = Moles::from(MolesType::INFINITY);
+
+            for compound in &reaction.lhs.compounds {
+                let available = container.moles_of(&compound.element);
+
+                if available == Moles::from(0.0) {
+                    missing.push(compound.element.clone());
+                    continue;
+                }
+
+                let max_extent = available / MolesType::from(compound.amount);
+
+                if max_extent < extent {
+                    extent = max_extent;
+                    limiting = Some(compound.element.clone());
+                }
+            }
+
+            // Only the medium can be missing
+            // TODO: But what if there exists an oxidator that provides this reductor with its needed molecules?
+            if !missing.iter().all(is_medium) {
+                continue;
+            }
+
+            if !missing.is_empty() {
+                limiting = None;
+                extent = Moles::from(0.0);
+            }
+
+            candidates.push(RedoxCandidate {
+                redox,
+                reaction,
+                emf,
+                limiting,
+                extent,
+                missing,
+            });
+        }
+    }
+
+    // Sort by driving force, equal ones alphabetically to keep the order stable
+    candidates.sort_by(|a, b| {
+        b.emf.0.total_cmp(&a.emf.0).then_with(|| {
+            (a.reaction.lhs.symbol() + &a.reaction.rhs.symbol())
+                .cmp(&(b.reaction.lhs.symbol() + &b.reaction.rhs.symbol()))
+        })
+    });
+
+    candidates
+}
@@ -0,0 +1,576 @@
+use acid_base::get_acid_base_systems;
+use constants::*;
+use container::*;
+use data_phases::get_phase;
+use dissolution::solution_enthalpy;
+use gas::{reaction_enthalpy, reaction_entropy, Phase};
+use ion::Ion;
+use reaction::{ElemReaction, ReactionCompound, ReactionSide};
+use redox::{get_redox_candidates, RedoxReaction};
+use trait_properties::Properties;
+use trait_reaction::Reaction;
+use types::*;
+
+/// Reactions with a driving force ΔG / RT = ln(Q / K) closer to 0 than this are in equilibrium
+const EQUILIBRIUM_TOLERANCE: f64 = 1e-3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Where a reaction in a simulation comes from
+pub enum ReactionKind {
+    /// A redox reaction from the `SEPMAP`
+    Redox,
+
+    /// A proton transfer from the `PKAMAP`, water included
+    AcidBase,
+
+    /// The precipitation or dissolution of a salt from the `KSPMAP` or the `SOLUBILITYMAP`
+    Precipitation,
+
+    /// A reaction registered by the user
+    Registered,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The reactions a simulation looks for
+pub struct ReactionRegistry {
+    /// Look for redox reactions
+    pub redox: bool,
+
+    /// Look for proton transfers
+    pub acid_base: bool,
+
+    /// Look for precipitations and dissolutions
+    pub precipitation: bool,
+
+    /// Other reactions to look for
+    /// Equilibria are looked for in both directions.
+    /// Their equilibrium constant follows from ΔG° = ΔH° - TΔS° = -RT ln(K),
+    /// so reactions of which the entropy of a species is unknown are left out
+    pub reactions: Vec<ElemReaction<Ion>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A reaction that can take place in a container
+pub struct SimulationCandidate {
+    /// Where the reaction comes from
+    pub kind: ReactionKind,
+
+    /// The reaction
+    pub reaction: ElemReaction<Ion>,
+
+    /// The redox reaction, for redox reactions
+    pub redox: Option<RedoxReaction>,
+
+    /// The Gibbs energy change per mole of reaction in the current container [ J / mol ]
+    /// The more negative, the larger the driving force
+    pub driving_force: Energy,
+
+    /// How far the reaction proceeds until equilibrium (or completion) [ mol ]
+    pub extent: MolesType,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A reaction that took place during a simulation
+pub struct SimulationStep {
+    /// Where the reaction comes from
+    pub kind: ReactionKind,
+
+    /// The reaction
+    pub reaction: ElemReaction<Ion>,
+
+    /// The Gibbs energy change per mole of reaction before it took place [ J / mol ]
+    pub driving_force: Energy,
+
+    /// What happened
+    pub outcome: ReactionOutcome<Ion>,
+}
+
+impl Default for ReactionRegistry {
+    /// Look for all built-in reactions
+    fn default() -> ReactionRegistry {
+        ReactionRegistry {
+            redox: true,
+            acid_base: true,
+            precipitation: true,
+            reactions: vec![],
+        }
+    }
+}
+
+impl ReactionRegistry {
+    /// Create a registry that looks for all built-in reactions
+    pub fn new() -> ReactionRegistry {
+        ReactionRegistry::default()
+    }
+
+    /// Register a reaction to look for
+    pub fn register(&mut self, reaction: ElemReaction<Ion>) {
+        self.reactions.push(reaction);
+    }
+}
+
+/// Create a reaction with one of every species on both sides
+/// A species that shows up twice on a side is written once, with an amount of 2
+fn simple_reaction(lhs: &[&Ion], rhs: &[&Ion]) -> ElemReaction<Ion> {
+    let side = |ions: &[&Ion]| {
+        let mut compounds: Vec<ReactionCompound<Ion>> = vec![];
+
+        for &ion in ions {
+            if let Some(compound) = compounds.iter_mut().find(|x| &x.element == ion) {
+                compound.amount += 1;
+                continue;
+            }
+
+            compounds.push(ReactionCompound {
+                element: ion.clone(),
+                amount: 1,
+            });
+        }
+
+        ReactionSide { compounds }
+    };
+
+    ElemReaction {
+        lhs: side(lhs),
+        rhs: side(rhs),
+        is_equilibrium: false,
+    }
+}
+
+/// Get every acid with its conjugated base and pKa, the pairs of water included
+/// H⁺ stands for H₃O⁺, so its conjugated base is the solvent, which is left out (None)
+fn get_acid_base_pairs() -> Vec<(Ion, Option<Ion>, PK)> {
+    let water = Ion::from_string("H2O").unwrap();
+
+    let mut pairs = vec![
+        (Ion::from_string("H;1").unwrap(), None, PK::from(0.0)),
+        (
+            water,
+            Some(Ion::from_string("OH;-").unwrap()),
+            PK::from(PKW as PKType),
+        ),
+    ];
+
+    for system in get_acid_base_systems() {
+        for (i, &pka) in system.pkas.iter().enumerate() {
+            pairs.push((
+                system.species[i].clone(),
+                Some(system.species[i + 1].clone()),
+                pka,
+            ));
+        }
+    }
+
+    pairs
+}
+
+impl Container<Ion> {
+    /// Get the factor that converts the amount of a species into its activity [ 1 / mol ]
+    /// Returns None for pure phases (the solvent, solids and liquids), which have activity 1.
+    /// Everything else is dissolved if the container holds water, and a gas otherwise
+    fn activity_factor(&self, ion: &Ion, solids: &[Ion]) -> Option<f64> {
+        if solids.contains(ion) || Container::is_solvent(ion) {
+            return None;
+        }
+
+        match get_phase(ion) {
+            Some(Phase::Solid) | Some(Phase::Liquid) => return None,
+            _ => {}
+        }
+
+        if let Some(volume) = self.solution_volume() {
+            return Some(1.0 / volume);
+        }
+
+        let gas_moles = self.gas_moles();
+        if get_phase(ion) == Some(Phase::Gas) && gas_moles > 0.0 {
+            Some(self.pressure().0 / (STANDARD_PRESSURE * gas_moles))
+        } else {
+            None
+        }
+    }
+
+    /// Find how far a reaction with the given ln(K) proceeds until equilibrium,
+    /// together with its driving force ΔG = RT ln(Q / K) [ J / mol ]
+    /// ln(K) is given per extent, as the reaction heat can change it along the way.
+    /// Without any of a product, ΔG would be -∞, so the standard ΔG° = -RT ln(K) is given instead
+    /// Returns None if the reaction doesn't proceed
+    fn equilibrium_extent<F: Fn(MolesType) -> f64>(
+        &self,
+        reaction: &ElemReaction<Ion>,
+        ln_k: F,
+        solids: &[Ion],
+    ) -> Option<(Energy, MolesType)> {
+        // The net change of every species per mole of reaction
+        let mut changes: Vec<(Ion, f64)> = vec![];
+        for (side, sign) in &[(&reaction.lhs, -1.0), (&reaction.rhs, 1.0)] {
+            for compound in &side.compounds {
+                let change = sign * f64::from(compound.amount);
+
+                if let Some(x) = changes.iter_mut().find(|x| x.0 == compound.element) {
+                    x.1 += change;
+                    continue;
+                }
+
+                changes.push((compound.element.clone(), change));
+            }
+        }
+
+        let maximum = changes
+            .iter()
+            .filter(|x| x.1 < 0.0)
+            .map(|x| self.moles_of(&x.0).0 / -x.1)
+            .fold(MolesType::INFINITY, MolesType::min);
+
+        if maximum <= self.contents.epsilon() || maximum.is_infinite() {
+            return None;
+        }
+
+        let species: Vec<(f64, f64, f64)> = changes
+            .iter()
+            .filter(|x| x.1 != 0.0)
+            .filter_map(|x| {
+                let factor = self.activity_factor(&x.0, solids)?;
+
+                Some((factor, self.moles_of(&x.0).0, x.1))
+            })
+            .collect();
+
+        let ln_q = |extent: f64| -> f64 {
+            species
+                .iter()
+                .map(|&(factor, moles, change)| {
+                    change * (factor * (moles + change * extent).max(0.0)).ln()
+                })
+                .sum()
+        };
+
+        let rt = GAS_CONSTANT * self.temperature.0;
+        let mut driving_force = rt * (ln_q(0.0) - ln_k(0.0));
+
+        if driving_force > -rt * EQUILIBRIUM_TOLERANCE {
+            return None;
+        }
+
+        if driving_force.is_infinite() {
+            driving_force = -rt * ln_k(0.0);
+        }
+
+        if ln_q(maximum) <= ln_k(maximum) {
+            return Some((Energy::from(driving_force), maximum));
+        }
+
+        let (mut low, mut high) = (0.0, maximum);
+        for _ in 0..100 {
+            let middle = (low + high) / 2.0;
+
+            if ln_q(middle) < ln_k(middle) {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+
+        // Amounts this small would be used up right away
+        if low <= self.contents.epsilon() {
+            return None;
+        }
+
+        Some((Energy::from(driving_force), low))
+    }
+
+    /// Get every reaction of the registry that can take place in this container,
+    /// sorted by driving force (largest first)
+    pub fn get_simulation_candidates(
+        &self,
+        registry: &ReactionRegistry,
+    ) -> Vec<SimulationCandidate> {
+        let mut candidates = vec![];
+        let rt = GAS_CONSTANT * self.temperature.0;
+
+        let mut push =
+            |kind, reaction: ElemReaction<Ion>, redox, equilibrium: Option<(Energy, MolesType)>| {
+                // Unbalanced reactions would create or destroy atoms
+                if !reaction.is_valid() {
+                    return;
+                }
+
+                if let Some((driving_force, extent)) = equilibrium {
+                    candidates.push(SimulationCandidate {
+                        kind,
+                        reaction,
+                        redox,
+                        driving_force,
+                        extent,
+                    });
+                }
+            };
+
+        if registry.redox {
+            let protons = Ion::from_string("H;1").unwrap();
+            let hydroxide = Ion::from_string("OH;-").unwrap();
+
+            for candidate in get_redox_candidates(self) {
+                let uses = |ion: &Ion| {
+                    candidate
+                        .reaction
+                        .lhs
+                        .compounds
+                        .iter()
+                        .any(|x| &x.element == ion)
+                };
+
+                // A reaction can't take place in an acidic and a basic solution at once
+                if !candidate.is_feasible() || (uses(&protons) && uses(&hydroxide)) {
+                    continue;
+                }
+
+                // ΔG° = -nFE° = -RT ln(K)
+                let ln_k = -candidate.redox.energy_cost().0 / rt;
+                let equilibrium = self.equilibrium_extent(&candidate.reaction, |_| ln_k, &[]);
+
+                push(
+                    ReactionKind::Redox,
+                    candidate.reaction,
+                    Some(candidate.redox),
+                    equilibrium,
+                );
+            }
+        }
+
+        let has_water = self.solution_volume().is_some();
+
+        if registry.acid_base && has_water {
+            let pairs = get_acid_base_pairs();
+
+            for (acid, conjugated_base, acid_pka) in &pairs {
+                if self.moles_of(acid) == Moles::from(0.0) {
+                    continue;
+                }
+
+                for (conjugated_acid, base, base_pka) in &pairs {
+                    // The solvent is always there to take up a proton
+                    let has_base = base
+                        .as_ref()
+                        .is_none_or(|x| self.moles_of(x) > Moles::from(0.0));
+                    if conjugated_acid == acid || !has_base {
+                        continue;
+                    }
+
+                    // The conjugated bases of strong acids (weaker than H⁺) don't take up protons
+                    if base_pka.0 < 0.0 {
+                        continue;
+                    }
+
+                    // HA + B⁻ → A⁻ + HB, K = Ka(HA) / Ka(HB)
+                    let lhs: Vec<&Ion> = Some(acid).into_iter().chain(base.as_ref()).collect();
+                    let rhs: Vec<&Ion> = conjugated_base
+                        .as_ref()
+                        .into_iter()
+                        .chain(Some(conjugated_acid))
+                        .collect();
+
+                    let reaction = simple_reaction(&lhs, &rhs);
+                    let ln_k = 10f64.ln() * f64::from(base_pka.0 - acid_pka.0);
+                    let equilibrium = self.equilibrium_extent(&reaction, |_| ln_k, &[]);
+
+                    push(ReactionKind::AcidBase, reaction, None, equilibrium);
+                }
+            }
+        }
+
+        if registry.precipitation && has_water {
+            // Ions → salt, K = 1 / Ksp
+            // Salts without Ksp data are left out, as their driving force is unknown
+            for (dissolution, pksp) in self.precipitation_candidates() {
+                let pksp = match pksp {
+                    Some(pksp) => pksp,
+                    None => continue,
+                };
+
+                let salt = dissolution.lhs.compounds[0].element.clone();
+                let ln_k = 10f64.ln() * f64::from(pksp.0);
+                let equilibrium =
+                    self.equilibrium_extent(&dissolution.clone().swap(), |_| ln_k, &[salt]);
+
+                push(
+                    ReactionKind::Precipitation,
+                    dissolution.swap(),
+                    None,
+                    equilibrium,
+                );
+            }
+
+            // Salt → ions, K = Ksp
+            // Soluble salts without data are left out as well, `dissolve` handles those
+            for (dissolution, pksp) in self.dissolution_candidates() {
+                let pksp = match pksp {
+                    Some(pksp) => pksp,
+                    None => continue,
+                };
+
+                let salt = dissolution.lhs.compounds[0].element.clone();
+                let ln_k = -10f64.ln() * f64::from(pksp.0);
+                let equilibrium = self.equilibrium_extent(&dissolution, |_| ln_k, &[salt]);
+
+                push(ReactionKind::Precipitation, dissolution, None, equilibrium);
+            }
+        }
+
+        for registered in &registry.reactions {
+            let mut reactions = vec![registered.clone()];
+            if registered.is_equilibrium {
+                reactions.push(registered.clone().swap());
+            }
+
+            for reaction in reactions {
+                let enthalpy = reaction_enthalpy(&reaction).0;
+                let entropy = match reaction_entropy(&reaction) {
+                    Some(entropy) => entropy,
+                    None => continue,
+                };
+
+                // The reaction heat changes the temperature while it proceeds,
+                // an endothermic reaction can't cool the contents down to 0 K though
+                let heat_capacity = self.heat_capacity();
+                let temperature = |extent: MolesType| {
+                    if heat_capacity > 0.0 {
+                        (self.temperature.0 - enthalpy * extent / heat_capacity).max(f64::EPSILON)
+                    } else {
+                        self.temperature.0
+                    }
+                };
+
+                // ΔG° = ΔH° - TΔS° = -RT ln(K)
+                let ln_k = |extent: MolesType| {
+                    let temperature = temperature(extent);
+
+                    -(enthalpy - temperature * entropy) / (GAS_CONSTANT * temperature)
+                };
+                let mut equilibrium = self.equilibrium_extent(&reaction, ln_k, &[]);
+
+                // The available energy might not be enough to reach equilibrium,
+                // as `react_extent` pays for the reaction enthalpy [ J ] with it
+                let energy_cost = reaction.energy_cost();
+                if let Some((_, ref mut extent)) = equilibrium {
+                    if energy_cost > Energy::from(0.0) {
+                        *extent = extent.min(self.available_energy.0 / energy_cost.0);
+                    }
+                }
+
+                push(ReactionKind::Registered, reaction, None, equilibrium);
+            }
+        }
+
+        // Sort by driving force, rounded to the equilibrium tolerance, as coupled equilibria
+        // (like a hydrolysis and the autoionisation of water) have about the same one.
+        // Equal ones proceed the furthest first, and alphabetically to keep the order stable
+        let rounded =
+            |x: &SimulationCandidate| (x.driving_force.0 / (rt * EQUILIBRIUM_TOLERANCE)).round();
+
+        candidates.sort_by(|a, b| {
+            rounded(a)
+                .total_cmp(&rounded(b))
+                .then_with(|| b.extent.total_cmp(&a.extent))
+                .then_with(|| {
+                    (a.reaction.lhs.symbol() + &a.reaction.rhs.symbol())
+                        .cmp(&(b.reaction.lhs.symbol() + &b.reaction.rhs.symbol()))
+                })
+        });
+
+        candidates
+    }
+
+    /// Let a reaction of the simulation take place
+    /// Redox and registered reactions are applied like `react_extent`,
+    /// proton transfers don't change the available energy,
+    /// and the heat of solution of a salt is taken up like in `take_up_enthalpy`
+    fn apply_candidate(
+        &mut self,
+        candidate: &SimulationCandidate,
+    ) -> Result<ReactionOutcome<Ion>, ReactionError<Ion>> {
+        match candidate.kind {
+            ReactionKind::Redox => {
+                self.react_extent(candidate.redox.as_ref().unwrap(), candidate.extent)
+            }
+
+            ReactionKind::Registered => self.react_extent(&candidate.reaction, candidate.extent),
+
+            ReactionKind::AcidBase | ReactionKind::Precipitation => {
+                let consumed = scaled_compounds(&candidate.reaction.lhs, candidate.extent);
+                let produced = scaled_compounds(&candidate.reaction.rhs, candidate.extent);
+
+                self.remove_elements(&consumed)?;
+                self.add_undissolved(&produced);
+
+                let mut energy_delta = Energy::from(0.0);
+                let mut heat = Energy::from(0.0);
+                if candidate.kind == ReactionKind::Precipitation {
+                    if let Some(enthalpy) = solution_enthalpy(&candidate.reaction) {
+                        (energy_delta, heat) =
+                            self.take_up_enthalpy(Energy::from(enthalpy.0 * candidate.extent));
+                    }
+                }
+
+                let limiting_reagent = consumed
+                    .iter()
+                    .find(|x| self.moles_of(&x.element) == Moles::from(0.0))
+                    .map(|x| x.element.clone());
+
+                let leftover = consumed
+                    .iter()
+                    .filter(|x| self.moles_of(&x.element) > Moles::from(0.0))
+                    .map(|x| ContainerCompound {
+                        element: x.element.clone(),
+                        moles: self.moles_of(&x.element),
+                    })
+                    .collect();
+
+                Ok(ReactionOutcome {
+                    consumed,
+                    produced,
+                    energy_delta,
+                    heat,
+                    extent: candidate.extent,
+                    limiting_reagent,
+                    leftover,
+                })
+            }
+        }
+    }
+
+    /// Let the reactions of the registry take place, the one with the largest driving force
+    /// first, until nothing changes anymore (equilibrium) or the maximum amount of steps
+    /// is reached. Returns the steps that took place
+    pub fn simulate(
+        &mut self,
+        registry: &ReactionRegistry,
+        max_steps: usize,
+    ) -> Vec<SimulationStep> {
+        let mut steps = vec![];
+
+        while steps.len() < max_steps {
+            let mut step = None;
+
+            for candidate in self.get_simulation_candidates(registry) {
+                if let Ok(outcome) = self.apply_candidate(&candidate) {
+                    step = Some(SimulationStep {
+                        kind: candidate.kind,
+                        reaction: candidate.reaction,
+                        driving_force: candidate.driving_force,
+                        outcome,
+                    });
+
+                    break;
+                }
+            }
+
+            match step {
+                Some(step) => steps.push(step),
+                None => break,
+            }
+        }
+
+        steps
+    }
+}
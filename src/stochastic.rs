@@ -0,0 +1,371 @@
+use constants::AVOGADRO;
+use container::Container;
+use kinetics::*;
+use trait_element::Element;
+use types::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// How a stochastic simulation is run
+pub struct StochasticSettings {
+    /// The volume the molecules are in [ L ]
+    pub volume: f64,
+
+    /// The amount of independent runs in the ensemble
+    pub runs: usize,
+
+    /// The amount of intervals the duration is divided in to record the counts
+    pub samples: usize,
+
+    /// The seed of the random numbers, so the simulation can be reproduced
+    pub seed: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The molecule counts at a moment during a stochastic simulation
+pub struct StochasticPoint {
+    /// The time since the start
+    pub time: Time,
+
+    /// The amount of molecules of every species
+    pub counts: Vec<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The outcome of many independent stochastic simulations of the same container
+pub struct Ensemble<E: Element> {
+    /// The species taking part in the reactions, in order of appearance
+    pub species: Vec<E>,
+
+    /// The volume the molecules are in [ L ]
+    pub volume: f64,
+
+    /// The counts of every run, at the same evenly spaced moments
+    pub runs: Vec<Vec<StochasticPoint>>,
+}
+
+/// A SplitMix64 generator, which is small, fast and good enough for simulations
+struct Random {
+    state: u64,
+}
+
+/// A rate law in terms of molecule counts, by species index
+struct Propensity {
+    /// The rate constant, converted to molecules per second
+    rate_constant: f64,
+
+    /// The order in every species
+    orders: Vec<(usize, f64)>,
+}
+
+impl Random {
+    fn new(seed: u64) -> Random {
+        Random { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Get a uniformly distributed number in (0, 1]
+    fn next_f64(&mut self) -> f64 {
+        ((self.next_u64() >> 11) as f64 + 1.0) / (1u64 << 53) as f64
+    }
+}
+
+impl Propensity {
+    /// Convert a rate law for concentrations into one for counts,
+    /// given the amount of molecules per mol/L
+    fn new(rate_constant: f64, orders: &[(usize, f64)], system_size: f64) -> Propensity {
+        let total_order: f64 = orders.iter().map(|x| x.1).sum();
+
+        Propensity {
+            rate_constant: rate_constant * system_size.powf(1.0 - total_order),
+            orders: orders.to_vec(),
+        }
+    }
+
+    /// Get the chance per second that the reaction takes place
+    /// Molecules can't collide with themselves, so whole orders count the distinct combinations
+    fn get(&self, counts: &[u64]) -> f64 {
+        let mut propensity = self.rate_constant;
+
+        for &(species, order) in &self.orders {
+            let count = counts[species] as f64;
+
+            if order.fract() == 0.0 {
+                for i in 0..(order as u64) {
+                    propensity *= (count - i as f64).max(0.0);
+                }
+            } else {
+                propensity *= count.powf(order);
+            }
+        }
+
+        propensity
+    }
+}
+
+impl StochasticSettings {
+    /// Create settings for a volume [ L ], with 100 runs recorded at 100 intervals
+    pub fn new(volume: f64, seed: u64) -> StochasticSettings {
+        StochasticSettings {
+            volume,
+            runs: 100,
+            samples: 100,
+            seed,
+        }
+    }
+}
+
+impl<E: Element> Container<E> {
+    /// Let a network of reactions take place for a duration, one molecule at a time,
+    /// using the stochastic simulation algorithm of Gillespie
+    /// The amounts in the container are taken to be molecule counts, rounded to whole
+    /// molecules, and the container itself is left untouched.
+    /// The rate laws are the same as those of `integrate`, at the temperature of the container
+    pub fn gillespie(
+        &self,
+        reactions: &[KineticReaction<E>],
+        duration: Time,
+        settings: &StochasticSettings,
+    ) -> Ensemble<E> {
+        let species = network_species(reactions);
+        let system_size = AVOGADRO * settings.volume;
+
+        let mut propensities = vec![];
+        let mut changes: Vec<Vec<i64>> = vec![];
+
+        for reaction in reactions {
+            let index = |orders: &[(E, f64)]| -> Vec<(usize, f64)> {
+                orders
+                    .iter()
+                    .map(|x| (species.iter().position(|y| y == &x.0).unwrap(), x.1))
+                    .collect()
+            };
+
+            let forward: Vec<i64> = network_changes(&reaction.reaction, &species)
+                .iter()
+                .map(|&x| x as i64)
+                .collect();
+
+            propensities.push(Propensity::new(
+                reaction.forward.rate_constant(self.temperature),
+                &index(&reaction.forward_orders),
+                system_size,
+            ));
+            changes.push(forward.clone());
+
+            if let Some(reverse) = reaction.reverse {
+                propensities.push(Propensity::new(
+                    reverse.rate_constant(self.temperature),
+                    &index(&reaction.reverse_orders),
+                    system_size,
+                ));
+                changes.push(forward.iter().map(|x| -x).collect());
+            }
+        }
+
+        let initial: Vec<u64> = species
+            .iter()
+            .map(|x| self.moles_of(x).0.round() as u64)
+            .collect();
+
+        let mut random = Random::new(settings.seed);
+        let mut runs = vec![];
+
+        // Reused for every event, as this is the hot loop
+        let mut rates = vec![0.0; propensities.len()];
+
+        for _ in 0..settings.runs {
+            let mut counts = initial.clone();
+            let mut time = 0.0;
+            let mut points = vec![];
+
+            for sample in 0..=settings.samples {
+                let sample_time = duration.0 * sample as f64 / settings.samples.max(1) as f64;
+
+                // Let reactions take place until the next one would be after this sample
+                loop {
+                    for ((rate, propensity), change) in rates
+                        .iter_mut()
+                        .zip(propensities.iter())
+                        .zip(changes.iter())
+                    {
+                        // A reaction can't use more molecules than there are
+                        let possible = change
+                            .iter()
+                            .zip(counts.iter())
+                            .all(|(&change, &count)| count as i64 + change >= 0);
+
+                        *rate = if possible {
+                            propensity.get(&counts)
+                        } else {
+                            0.0
+                        };
+                    }
+
+                    let total: f64 = rates.iter().sum();
+                    if total <= 0.0 {
+                        time = duration.0;
+                        break;
+                    }
+
+                    let wait = -random.next_f64().ln() / total;
+                    let mut choice = random.next_f64() * total;
+
+                    if time + wait > sample_time {
+                        // Waiting times are memoryless, so the next one can be drawn anew
+                        time = sample_time;
+                        break;
+                    }
+
+                    time += wait;
+
+                    let reaction = rates
+                        .iter()
+                        .position(|&rate| {
+                            choice -= rate;
+                            choice <= 0.0
+                        })
+                        .unwrap_or(rates.len() - 1);
+
+                    for (count, change) in counts.iter_mut().zip(changes[reaction].iter()) {
+                        *count = (*count as i64 + change) as u64;
+                    }
+                }
+
+                points.push(StochasticPoint {
+                    time: Time::from(sample_time),
+                    counts: counts.clone(),
+                });
+            }
+
+            runs.push(points);
+        }
+
+        Ensemble {
+            species,
+            volume: settings.volume,
+            runs,
+        }
+    }
+}
+
+impl<E: Element> Ensemble<E> {
+    /// Get the counts of an element in every run at every sample
+    fn counts_of(&self, element: &E) -> Option<Vec<Vec<f64>>> {
+        let index = self.species.iter().position(|x| x == element)?;
+
+        Some(
+            self.runs
+                .iter()
+                .map(|run| run.iter().map(|x| x.counts[index] as f64).collect())
+                .collect(),
+        )
+    }
+
+    /// Get the times at which the counts were recorded
+    fn times(&self) -> Vec<Time> {
+        self.runs
+            .first()
+            .map(|run| run.iter().map(|x| x.time).collect())
+            .unwrap_or_default()
+    }
+
+    /// Get the mean count of an element over all runs, over time
+    /// Returns None if the element didn't take part in the reactions
+    pub fn mean_of(&self, element: &E) -> Option<Vec<(Time, f64)>> {
+        let counts = self.counts_of(element)?;
+        let runs = counts.len() as f64;
+
+        Some(
+            self.times()
+                .iter()
+                .enumerate()
+                .map(|(i, &time)| (time, counts.iter().map(|run| run[i]).sum::<f64>() / runs))
+                .collect(),
+        )
+    }
+
+    /// Get the variance of the count of an element over all runs, over time
+    /// Returns None if the element didn't take part in the reactions
+    pub fn variance_of(&self, element: &E) -> Option<Vec<(Time, f64)>> {
+        let counts = self.counts_of(element)?;
+        let means = self.mean_of(element)?;
+        let runs = counts.len() as f64;
+
+        Some(
+            means
+                .iter()
+                .enumerate()
+                .map(|(i, &(time, mean))| {
+                    let sum: f64 = counts.iter().map(|run| (run[i] - mean).powi(2)).sum();
+
+                    (time, sum / runs)
+                })
+                .collect(),
+        )
+    }
+
+    /// Get the mean concentrations over time, to compare with the deterministic `integrate`
+    pub fn mean_trajectory(&self) -> Trajectory<E> {
+        let system_size = AVOGADRO * self.volume;
+        let means: Vec<Vec<(Time, f64)>> = self
+            .species
+            .iter()
+            .map(|x| self.mean_of(x).unwrap())
+            .collect();
+
+        let points = self
+            .times()
+            .iter()
+            .enumerate()
+            .map(|(i, &time)| KineticsPoint {
+                time,
+                concentrations: means.iter().map(|x| x[i].1 / system_size).collect(),
+            })
+            .collect();
+
+        Trajectory {
+            species: self.species.clone(),
+            points,
+        }
+    }
+
+    /// Export the mean and the variance of every count over time as CSV
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("time");
+        for element in &self.species {
+            csv += &format!(",{0} mean,{0} variance", element.symbol());
+        }
+        csv += "\n";
+
+        let means: Vec<Vec<(Time, f64)>> = self
+            .species
+            .iter()
+            .map(|x| self.mean_of(x).unwrap())
+            .collect();
+        let variances: Vec<Vec<(Time, f64)>> = self
+            .species
+            .iter()
+            .map(|x| self.variance_of(x).unwrap())
+            .collect();
+
+        for (i, time) in self.times().iter().enumerate() {
+            csv += &format!("{:.6}", time);
+
+            for (mean, variance) in means.iter().zip(variances.iter()) {
+                csv += &format!(",{:.4},{:.4}", mean[i].1, variance[i].1);
+            }
+
+            csv += "\n";
+        }
+
+        csv
+    }
+}
@@ -0,0 +1,312 @@
+use constants::*;
+use container::*;
+use data_sep::get_sep;
+use electron::is_electron;
+use gibbs::written_charge;
+use ion::Ion;
+use reaction::ElemReaction;
+use redox::*;
+use types::*;
+
+/// How much steeper than the median slope a jump has to be to count as an equivalence point
+const EQUIVALENCE_FACTOR: f64 = 5.0;
+
+/// The amount of times the volume around an equivalence point is narrowed down
+const EQUIVALENCE_ITERATIONS: usize = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// What is measured during a titration
+pub enum TitrationKind {
+    /// The pH, for acid-base titrations
+    AcidBase,
+
+    /// The potential of the solution [ V ], for redox titrations
+    Redox,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A measurement during a titration
+pub struct TitrationPoint {
+    /// The volume of titrant added [ mL ]
+    pub volume: f64,
+
+    /// The measured pH or potential
+    pub value: f64,
+}
+
+#[derive(Debug, Clone)]
+/// A titration of an analyte with a titrant from a burette
+pub struct Titration {
+    /// What is measured
+    pub kind: TitrationKind,
+
+    /// The reaction taking place, for redox titrations
+    pub reaction: Option<RedoxReaction>,
+
+    /// The measurements, skipping those where the value is undefined
+    /// (like the potential before any titrant is added)
+    pub points: Vec<TitrationPoint>,
+
+    /// The analyte after the titration
+    pub analyte: Container<Ion>,
+
+    /// The analyte before the titration
+    initial: Container<Ion>,
+
+    /// The burette before the titration
+    burette: Container<Ion>,
+}
+
+/// Calculate the potential of a half-reaction in a container using the Nernst equation
+/// Returns None if a dissolved species of the half-reaction is used up
+fn nernst_potential(container: &Container<Ion>, half_reaction: &ElemReaction<Ion>) -> Option<f64> {
+    let half_reaction = as_reduction(half_reaction.clone());
+    let sep = f64::from(get_sep(&half_reaction)?.0);
+
+    let equilibrium = container.acid_base_equilibrium()?;
+
+    // Pure solids, liquids and gases have an activity of 1
+    let activity = |ion: &Ion| -> Option<f64> {
+        if ion == &Ion::from_string("H;1").unwrap() || ion == &Ion::from_string("OH;-").unwrap() {
+            Some(equilibrium.concentration_of(ion))
+        } else if written_charge(ion) == 0.0 {
+            Some(1.0)
+        } else if container.moles_of(ion).0 > DEPLETED {
+            container.concentration_of(ion)
+        } else {
+            None
+        }
+    };
+
+    let mut log_q = 0.0;
+    for (side, sign) in &[(&half_reaction.lhs, -1.0), (&half_reaction.rhs, 1.0)] {
+        for compound in side.compounds.iter().filter(|x| !is_electron(&x.element)) {
+            log_q += sign * f64::from(compound.amount) * activity(&compound.element)?.ln();
+        }
+    }
+
+    let electrons = f64::from(electrons_in(&half_reaction));
+
+    Some(sep - GAS_CONSTANT * STANDARD_TEMPERATURE / (electrons * FARADAY) * log_q)
+}
+
+/// Measure the potential of the solution if a reaction is given, the pH otherwise
+fn measure(container: &Container<Ion>, reaction: Option<&RedoxReaction>) -> Option<f64> {
+    match reaction {
+        Some(redox) => solution_potential(container, redox),
+        None => container.ph(),
+    }
+}
+
+/// Get the potential of the solution, from the couple of which most of both sides is present
+fn solution_potential(container: &Container<Ion>, redox: &RedoxReaction) -> Option<f64> {
+    let mut best: Option<(f64, f64)> = None;
+
+    for couple in &[&redox.oxidator, &redox.reductor] {
+        let potential = match nernst_potential(container, couple) {
+            Some(potential) => potential,
+            None => continue,
+        };
+
+        // The least abundant dissolved species determines how well-defined the potential is
+        let minimum = couple
+            .lhs
+            .compounds
+            .iter()
+            .chain(couple.rhs.compounds.iter())
+            .filter(|x| !is_electron(&x.element) && !is_medium(&x.element))
+            .filter(|x| written_charge(&x.element) != 0.0)
+            .map(|x| container.moles_of(&x.element).0)
+            .fold(MolesType::INFINITY, MolesType::min);
+
+        if best.is_none_or(|x| minimum > x.1) {
+            best = Some((potential, minimum));
+        }
+    }
+
+    best.map(|x| x.0)
+}
+
+impl Titration {
+    /// Titrate an analyte with a titrant from a burette, measuring the pH
+    /// The titrant is added in steps of `step` mL, until `total` mL is added
+    /// or the burette is empty. Returns None if the analyte doesn't contain water
+    pub fn acid_base(
+        analyte: &Container<Ion>,
+        burette: &Container<Ion>,
+        step: f64,
+        total: f64,
+    ) -> Option<Titration> {
+        Titration::run(analyte, burette, None, step, total)
+    }
+
+    /// Titrate an analyte with a titrant from a burette, measuring the potential
+    /// The given reaction takes place completely after every addition,
+    /// e.g. MnO₄⁻ + 8H⁺ + 5e⁻ ⇌ Mn²⁺ + 4H₂O with Fe²⁺ ⇌ Fe³⁺ + e⁻ for permanganometry.
+    /// The titrant is added in steps of `step` mL, until `total` mL is added
+    /// or the burette is empty. Returns None if the analyte doesn't contain water
+    pub fn redox(
+        analyte: &Container<Ion>,
+        burette: &Container<Ion>,
+        reaction: &RedoxReaction,
+        step: f64,
+        total: f64,
+    ) -> Option<Titration> {
+        Titration::run(analyte, burette, Some(reaction), step, total)
+    }
+
+    /// Run a titration, measuring the potential if a reaction is given, the pH otherwise
+    fn run(
+        analyte: &Container<Ion>,
+        burette: &Container<Ion>,
+        reaction: Option<&RedoxReaction>,
+        step: f64,
+        total: f64,
+    ) -> Option<Titration> {
+        let initial = analyte.clone();
+        let titrant = burette.clone();
+        let mut analyte = analyte.clone();
+        let mut burette = burette.clone();
+
+        let kind = if reaction.is_some() {
+            TitrationKind::Redox
+        } else {
+            TitrationKind::AcidBase
+        };
+
+        // Without water there is nothing to titrate
+        analyte.solution_volume()?;
+
+        let mut points = vec![];
+        let mut volume = 0.0;
+
+        if let Some(value) = measure(&analyte, reaction) {
+            points.push(TitrationPoint { volume, value });
+        }
+
+        while volume + step <= total + step * 1e-9 {
+            // [ mL ] -> [ L ]
+            match burette.aliquot(step / 1000.0) {
+                Some(portion) => analyte.merge(portion),
+                None => break,
+            }
+
+            volume += step;
+
+            // Nothing happens as long as one of the reactants is missing
+            if let Some(redox) = reaction {
+                analyte.react_to_completion(redox).ok();
+            }
+
+            if let Some(value) = measure(&analyte, reaction) {
+                points.push(TitrationPoint { volume, value });
+            }
+        }
+
+        Some(Titration {
+            kind,
+            reaction: reaction.cloned(),
+            points,
+            analyte,
+            initial,
+            burette: titrant,
+        })
+    }
+
+    /// Measure the pH or potential after adding a volume of titrant at once [ mL ]
+    /// As the reaction takes place completely, this is the same as adding it in steps
+    fn measure_at(&self, volume: f64) -> Option<f64> {
+        let mut analyte = self.initial.clone();
+
+        // [ mL ] -> [ L ]
+        analyte.merge(self.burette.clone().aliquot(volume / 1000.0)?);
+
+        if let Some(ref redox) = self.reaction {
+            analyte.react_to_completion(redox).ok();
+        }
+
+        measure(&analyte, self.reaction.as_ref())
+    }
+
+    /// Find the volume at which the curve is the steepest between two volumes [ mL ],
+    /// using a golden-section search
+    fn steepest_between(&self, mut low: f64, mut high: f64) -> f64 {
+        let slope = |volume: f64, width: f64| -> f64 {
+            match (
+                self.measure_at(volume - width),
+                self.measure_at(volume + width),
+            ) {
+                (Some(before), Some(after)) => ((after - before) / (2.0 * width)).abs(),
+                _ => 0.0,
+            }
+        };
+
+        let ratio = (5f64.sqrt() - 1.0) / 2.0;
+
+        for _ in 0..EQUIVALENCE_ITERATIONS {
+            let width = (high - low) / 10.0;
+            let left = high - ratio * (high - low);
+            let right = low + ratio * (high - low);
+
+            if slope(left, width) > slope(right, width) {
+                high = right;
+            } else {
+                low = left;
+            }
+        }
+
+        (low + high) / 2.0
+    }
+
+    /// Find the equivalence points: the volumes at which the curve is the steepest [ mL ]
+    /// The steepest jumps between the measurements are narrowed down further
+    pub fn equivalence_points(&self) -> Vec<f64> {
+        let slopes: Vec<(f64, f64)> = self
+            .points
+            .windows(2)
+            .map(|pair| {
+                let volume = (pair[0].volume + pair[1].volume) / 2.0;
+                let slope = (pair[1].value - pair[0].value) / (pair[1].volume - pair[0].volume);
+
+                (volume, slope.abs())
+            })
+            .collect();
+
+        if slopes.len() < 3 {
+            return vec![];
+        }
+
+        let mut sorted: Vec<f64> = slopes.iter().map(|x| x.1).collect();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let median = sorted[sorted.len() / 2];
+
+        // The start and the end of the curve can't be equivalence points
+        let mut equivalence_points = vec![];
+        for window in slopes.windows(3) {
+            let (previous, (volume, slope), next) = (window[0].1, window[1], window[2].1);
+
+            if slope > previous && slope >= next && slope > EQUIVALENCE_FACTOR * median {
+                // The jump lies between the measurements around the steepest slope
+                let step = volume - window[0].0;
+
+                equivalence_points.push(self.steepest_between(volume - step, volume + step));
+            }
+        }
+
+        equivalence_points
+    }
+
+    /// Export the titration curve as CSV
+    pub fn to_csv(&self) -> String {
+        let mut csv = match self.kind {
+            TitrationKind::AcidBase => String::from("volume,pH\n"),
+            TitrationKind::Redox => String::from("volume,E\n"),
+        };
+
+        for point in &self.points {
+            csv += &format!("{:.4},{:.4}\n", point.volume, point.value);
+        }
+
+        csv
+    }
+}
@@ -1,3 +1,4 @@
+use container::{Container, ContainerCompound};
 use ion::Ion;
 use molecule::Molecule;
 use trait_properties::Properties;
@@ -14,4 +15,12 @@ pub trait Element: Properties + Hash {
 
     /// Get the ion associated with the current Element
     fn get_ion(self) -> Option<Ion>;
+
+    /// Let the salts in a container of these elements dissolve after elements were added,
+    /// see `Container::dissolve`. Only ions can dissolve (default: nothing happens)
+    fn dissolve_in(_container: &mut Container<Self>, _added: &[ContainerCompound<Self>])
+    where
+        Self: Sized,
+    {
+    }
 }
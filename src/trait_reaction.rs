@@ -1,3 +1,4 @@
+use activation::Activation;
 use reaction::ElemReaction;
 use trait_element::Element;
 use trait_properties::Properties;
@@ -18,4 +19,9 @@ pub trait Reaction<E: Element>: Properties {
 
     /// Get the ElemReaction version
     fn elem_reaction(&self) -> ElemReaction<E>;
+
+    /// Get the activation barrier of this reaction, if it has one (default: None)
+    fn activation(&self) -> Option<Activation<E>> {
+        None
+    }
 }
@@ -22,6 +22,7 @@ macro_rules! overload_operators {
     ($class:ident, $type:ident) => {
         impl Eq for $class {}
 
+        #[allow(clippy::derive_ord_xor_partial_ord)]
         impl Ord for $class {
             fn cmp(&self, rhs: &Self) -> cmp::Ordering {
                 if self.0 < rhs.0 {
@@ -183,6 +184,7 @@ allow_display!(Moles);
 // pub type SEP = f32;
 pub type SEPType = f32;
 #[derive(Debug, Clone, PartialEq, PartialOrd, Copy)]
+#[allow(clippy::upper_case_acronyms)]
 pub struct SEP(pub SEPType);
 allow_conversion!(SEP, SEPType);
 overload_operators!(SEP, SEPType);
@@ -192,7 +194,55 @@ allow_display!(SEP);
 // pub type SEP = f32;
 pub type SEFType = f32;
 #[derive(Debug, Clone, PartialEq, PartialOrd, Copy)]
+#[allow(clippy::upper_case_acronyms)]
 pub struct SEF(pub SEFType);
 allow_conversion!(SEF, SEFType);
 overload_operators!(SEF, SEFType);
 allow_display!(SEF);
+
+/// should fit 0.0 to 1e5+, with high precision [ ampere ]
+// pub type Current = f64;
+pub type CurrentType = f64;
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct Current(pub CurrentType);
+allow_conversion!(Current, CurrentType);
+overload_operators!(Current, CurrentType);
+allow_display!(Current);
+
+/// should fit 0.0 to 1e9+, with high precision [ second ]
+// pub type Time = f64;
+pub type TimeType = f64;
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct Time(pub TimeType);
+allow_conversion!(Time, TimeType);
+overload_operators!(Time, TimeType);
+allow_display!(Time);
+
+/// should fit -20.0 to 60.0, with normal precision [  ]
+/// The negative base-10 logarithm of an equilibrium constant (pKa, pKsp, ...)
+// pub type PK = f32;
+pub type PKType = f32;
+#[derive(Debug, Clone, PartialEq, PartialOrd, Copy)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct PK(pub PKType);
+allow_conversion!(PK, PKType);
+overload_operators!(PK, PKType);
+allow_display!(PK);
+
+/// should fit 0.0 to 1e4, with high precision [ kelvin ]
+// pub type Temperature = f64;
+pub type TemperatureType = f64;
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct Temperature(pub TemperatureType);
+allow_conversion!(Temperature, TemperatureType);
+overload_operators!(Temperature, TemperatureType);
+allow_display!(Temperature);
+
+/// should fit 0.0 to 1e9, with high precision [ pascal ]
+// pub type Pressure = f64;
+pub type PressureType = f64;
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct Pressure(pub PressureType);
+allow_conversion!(Pressure, PressureType);
+overload_operators!(Pressure, PressureType);
+allow_display!(Pressure);